@@ -1,27 +1,42 @@
+pub mod output_encoding;
 pub mod sub;
 
+pub use output_encoding::OutputEncoding;
 pub use sub::Sub;
 
-use crate::args::{Args, Quantity, Threads};
+use crate::args::{Args, Depth, InputFormat, Quantity, Threads};
 use crate::bytes_format::BytesFormat;
+use crate::data_tree::{CullThresholds, DataTree, SortKey};
 use crate::device::DeviceBoundary;
 use crate::get_size::{GetApparentSize, GetSize};
-use crate::json_data::{JsonData, JsonDataBody, JsonShared, JsonTree};
+use crate::group_by::{CategoryRules, Grouping};
+use crate::json_data::{
+    BinaryVersion, JsonData, JsonDataBody, JsonTree, ScanSettings, SchemaVersion,
+};
+use crate::node_metadata::MetadataFields;
+use crate::os_string_display::OsStringDisplay;
 use crate::reporter::{ErrorOnlyReporter, ErrorReport, ProgressAndErrorReporter, ProgressReport};
 use crate::runtime_error::RuntimeError;
-use crate::visualizer::{BarAlignment, ColumnWidthDistribution, Direction, Visualizer};
+use crate::visualizer::{
+    BarAlignment, ChartColumn, ChartOptions, Coloring, ColumnWidthDistribution, Direction,
+    FlatList, LsColors, QuotingStyle, Visualizer,
+};
 use crate::{hardlink, size};
 use clap::Parser;
 use hdd::any_path_is_in_hdd;
 use host::Host;
-use pipe_trait::Pipe;
-use std::io::stdin;
+use shaped_output::ShapedOutput;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
 use std::time::Duration;
 use sub::JsonOutputParam;
 use sysinfo::Disks;
 
 #[cfg(unix)]
 use crate::get_size::{GetBlockCount, GetBlockSize};
+#[cfg(not(unix))]
+use pipe_trait::Pipe;
 
 /// The main application.
 pub struct App {
@@ -31,32 +46,124 @@ pub struct App {
 
 /// Geometry options that control how the chart is laid out.
 #[derive(Clone, Copy)]
-struct ChartLayout {
+struct ChartLayout<'a> {
     /// How the available width is distributed across the columns.
     column_width_distribution: ColumnWidthDistribution,
     /// Whether the tree grows from the top down or from the bottom up.
     direction: Direction,
     /// Whether the bars are aligned to the left or to the right.
     bar_alignment: BarAlignment,
+    /// Columns to display besides the tree and the bars.
+    columns: &'a [ChartColumn],
+    /// How to write the names of the entries.
+    quoting_style: QuotingStyle,
+    /// How to color the chart, if at all.
+    coloring: Option<Coloring<'a>>,
+    /// Whether to list the entries with their full paths instead of drawing the tree.
+    flat: bool,
+    /// Whether to omit the entries that contain other listed entries from the flat list.
+    exclude_ancestors: bool,
+    /// Whether the root does not represent a real entry.
+    synthetic_root: bool,
 }
 
-/// Tree-shaping options applied to a deserialized `--json-input` tree before visualization.
+/// Tree-shaping options applied to a deserialized tree before visualization.
 #[derive(Clone, Copy)]
-struct JsonInputShaping {
+struct InputShaping {
     /// Maximum number of levels to display.
     max_depth: u64,
-    /// Minimal size proportion required to appear.
-    min_ratio: f32,
+    /// Sizes required to appear.
+    cull_thresholds: CullThresholds,
+    /// Whether to replace the culled entries of each directory with a single entry.
+    aggregate_culled: bool,
+    /// Maximum number of children to display for each directory.
+    max_children: Option<usize>,
+    /// Maximum number of nodes to display.
+    max_rows: Option<usize>,
+    /// Property by which the entries are sorted.
+    sort: SortKey,
+    /// Whether to reverse the order of the entries.
+    reverse: bool,
     /// Whether to preserve the input order of the entries.
     no_sort: bool,
 }
 
+impl InputShaping {
+    /// Cull the descendants of `data_tree` that are too small to appear.
+    fn cull<Size>(self, data_tree: &mut DataTree<OsStringDisplay, Size>)
+    where
+        Size: size::Size + Into<u64> + Send,
+    {
+        if self.aggregate_culled {
+            data_tree.par_cull_into_aggregates(self.cull_thresholds, |culled| {
+                aggregate_name(culled.len() as u64)
+            });
+        } else {
+            data_tree.par_cull(self.cull_thresholds);
+        }
+    }
+
+    /// Remove the descendants of `data_tree` that exceed `--max-children` or `--max-rows`.
+    fn limit<Name, Size>(self, data_tree: &mut DataTree<Name, Size>)
+    where
+        DataTree<Name, Size>: Send,
+        Size: size::Size,
+    {
+        if let Some(max_children) = self.max_children {
+            data_tree.par_retain_largest_children(max_children);
+        }
+        if let Some(max_rows) = self.max_rows {
+            data_tree.retain_largest_nodes(max_rows);
+        }
+    }
+
+    /// Sort the descendants of `data_tree` by `--sort` and `--reverse` unless `--no-sort`.
+    fn sort<Name, Size>(self, data_tree: &mut DataTree<Name, Size>)
+    where
+        DataTree<Name, Size>: Send,
+        Name: AsRef<OsStr>,
+        Size: size::Size,
+    {
+        if !self.no_sort {
+            data_tree.par_sort_by_key(self.sort, self.reverse);
+        }
+    }
+}
+
+/// Name of the entry that stands for `count` culled entries, such as `(1,234 smaller items)`.
+fn aggregate_name(count: u64) -> OsStringDisplay {
+    let digits = count.to_string();
+    let mut grouped = String::with_capacity(digits.len() * 4 / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    let noun = if count == 1 { "item" } else { "items" };
+    OsStringDisplay::os_string_from(format!("({grouped} smaller {noun})"))
+}
+
+/// Read and parse the file of `--category-rules`.
+fn load_category_rules(path: &Path) -> Result<CategoryRules, RuntimeError> {
+    let text = fs::read_to_string(path).map_err(|error| RuntimeError::ReadInputFailure {
+        path: path.to_path_buf(),
+        error,
+    })?;
+    CategoryRules::from_toml(&text).map_err(|error| RuntimeError::InvalidCategoryRules {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
 impl App {
     /// Initialize the application from the environment.
     pub fn from_env() -> Self {
-        App {
-            args: Args::parse(),
+        let args = Args::parse();
+        if let Err(error) = args.validate() {
+            error.exit();
         }
+        App { args }
     }
 
     /// Run the application.
@@ -70,17 +177,30 @@ impl App {
 
         let column_width_distribution = self.args.column_width_distribution();
 
-        if self.args.json_input {
-            if !self.args.files.is_empty() {
-                return Err(RuntimeError::JsonInputArgConflict);
-            }
-
+        let output_encoding = OutputEncoding::from_cli_flags(
+            self.args.output_format(),
+            self.args.zstd,
+            self.args.bytes_format(),
+        );
+
+        let ls_colors =
+            (output_encoding.is_none() && self.args.color.enabled()).then(LsColors::from_env);
+        let coloring = ls_colors.as_ref().map(|ls_colors| Coloring {
+            bar_coloring: self.args.bar_coloring,
+            ls_colors,
+            synthetic_root: false,
+        });
+
+        let input_options = || {
             let Args {
-                bytes_format,
                 top_down,
                 align_right,
                 max_depth,
-                min_ratio,
+                aggregate_culled,
+                max_children,
+                max_rows,
+                sort,
+                reverse,
                 no_sort,
                 ..
             } = self.args;
@@ -88,45 +208,160 @@ impl App {
                 column_width_distribution,
                 direction: Direction::from_top_down(top_down),
                 bar_alignment: BarAlignment::from_align_right(align_right),
+                columns: &self.args.columns,
+                quoting_style: self.args.quoting_style,
+                coloring,
+                flat: self.args.flat,
+                exclude_ancestors: self.args.exclude_ancestors,
+                synthetic_root: false,
             };
-            let shaping = JsonInputShaping {
+            let shaping = InputShaping {
                 max_depth: max_depth.get(),
-                min_ratio: min_ratio.into(),
+                cull_thresholds: self.args.cull_thresholds(),
+                aggregate_culled,
+                max_children,
+                max_rows,
+                sort,
+                reverse,
                 no_sort,
             };
+            (layout, shaping)
+        };
+
+        if let Some(database) = &self.args.growth_report {
+            let (layout, shaping) = input_options();
+            let [old, new] = history::load_span(database, self.args.growth_snapshots)?;
+            return snapshot_diff::compare_snapshots(
+                (&old.0, old.1),
+                (&new.0, new.1),
+                output_encoding,
+                self.args.bytes_format(),
+                layout,
+                shaping,
+            );
+        }
+
+        if let Some(snapshots) = &self.args.diff {
+            let (layout, shaping) = input_options();
+            return snapshot_diff::run(
+                snapshots,
+                self.args.input_format().unwrap_or(InputFormat::Json),
+                output_encoding,
+                self.args.bytes_format(),
+                layout,
+                shaping,
+            );
+        }
+
+        if let Some(input_format) = self.args.input_format() {
+            let bytes_format = self.args.bytes_format();
+            let (layout, shaping) = input_options();
+
+            let (header, body) = json_input::load(&self.args.files, input_format)?;
+
+            // The root is synthetic if it was created by combining multiple inputs or roots.
+            let synthetic_root = self.args.files.len() > 1
+                || header.as_ref().is_some_and(|header| header.roots.len() > 1);
+
+            let layout = ChartLayout {
+                coloring: layout.coloring.map(|coloring| Coloring {
+                    synthetic_root,
+                    ..coloring
+                }),
+                synthetic_root,
+                ..layout
+            };
+
+            if self.args.interactive {
+                let source = interactive::Source::Snapshot {
+                    body,
+                    synthetic_root,
+                };
+                return interactive::run(source, bytes_format);
+            }
+
+            let body = match &self.args.save_to {
+                Some(save_to) => {
+                    history::save_body(save_to, header.as_ref(), body, synthetic_root)?
+                }
+                None => body,
+            };
 
-            let body = stdin()
-                .pipe(serde_json::from_reader::<_, JsonData>)
-                .map_err(RuntimeError::DeserializationFailure)?
-                .body;
+            if let Some(output_encoding) = output_encoding {
+                if output_encoding.format.is_shaped() {
+                    let table_bytes_format = self.args.csv_bytes_format;
+                    macro_rules! write_shaped {
+                        ($tree:expr, $table_bytes_format:expr, $bytes_format:expr) => {
+                            json_input::write_shaped(
+                                $tree,
+                                shaping,
+                                ShapedOutput {
+                                    encoding: output_encoding,
+                                    synthetic_root,
+                                    table_columns: &self.args.csv_columns,
+                                    table_bytes_format: $table_bytes_format,
+                                    svg_layout: self.args.svg_layout,
+                                    bytes_format: $bytes_format,
+                                },
+                            )
+                        };
+                    }
+                    return match body {
+                        JsonDataBody::Bytes(tree) => {
+                            write_shaped!(tree, table_bytes_format, bytes_format)
+                        }
+                        JsonDataBody::Blocks(tree) => {
+                            write_shaped!(tree, table_bytes_format.map(|_| ()), ())
+                        }
+                    };
+                }
+                let json_data = JsonData {
+                    schema_version: SchemaVersion,
+                    binary_version: Some(BinaryVersion::current()),
+                    header,
+                    body,
+                };
+                return output_encoding.write_snapshot(&json_data);
+            }
 
             trait VisualizeJsonTree: size::Size + Into<u64> + Send {
                 fn visualize_json_tree(
                     tree: JsonTree<Self>,
                     bytes_format: Self::DisplayFormat,
-                    layout: ChartLayout,
-                    shaping: JsonInputShaping,
+                    layout: ChartLayout<'_>,
+                    shaping: InputShaping,
                 ) -> Result<String, RuntimeError> {
                     let JsonTree { tree, shared } = tree;
                     let ChartLayout {
                         column_width_distribution,
                         direction,
                         bar_alignment,
+                        columns,
+                        quoting_style,
+                        coloring,
+                        flat,
+                        exclude_ancestors,
+                        synthetic_root,
                     } = layout;
-                    let JsonInputShaping {
-                        max_depth,
-                        min_ratio,
-                        no_sort,
-                    } = shaping;
+                    let InputShaping { max_depth, .. } = shaping;
 
                     let mut data_tree = tree
                         .par_try_into_tree()
                         .map_err(|error| RuntimeError::InvalidInputReflection(error.to_string()))?
                         .into_par_retained(|_, depth| depth + 1 < max_depth);
-                    data_tree.par_cull_insignificant_data(min_ratio);
-                    if !no_sort {
-                        data_tree
-                            .par_sort_by(|left, right| left.size().cmp(&right.size()).reverse());
+                    shaping.cull(&mut data_tree);
+                    shaping.limit(&mut data_tree);
+                    shaping.sort(&mut data_tree);
+
+                    if flat {
+                        let flat_list = FlatList {
+                            data_tree: &data_tree,
+                            bytes_format,
+                            quoting_style,
+                            exclude_ancestors,
+                            synthetic_root,
+                        };
+                        return Ok(flat_list.to_string());
                     }
 
                     let visualizer = Visualizer {
@@ -135,10 +370,14 @@ impl App {
                         column_width_distribution,
                         direction,
                         bar_alignment,
-                    };
+                    }
+                    .with_options(ChartOptions {
+                        columns,
+                        quoting_style,
+                        coloring,
+                    });
 
-                    let JsonShared { details, summary } = shared;
-                    let summary = summary.or_else(|| details.map(|details| details.summarize()));
+                    let summary = shared.summarize();
 
                     let visualization = if let Some(summary) = summary {
                         let summary = summary.display(bytes_format);
@@ -217,6 +456,80 @@ impl App {
             ErrorReport::TEXT
         };
 
+        if self.args.interactive {
+            let bytes_format = self.args.bytes_format();
+            let source = interactive::Source::Scan {
+                scan: interactive::LiveScan {
+                    files: if self.args.files.is_empty() {
+                        vec![".".into()]
+                    } else {
+                        self.args.files
+                    },
+                    device_boundary: DeviceBoundary::from_one_file_system(
+                        self.args.one_file_system,
+                    ),
+                    deduplicate_hardlinks: self.args.deduplicate_hardlinks,
+                },
+                quantity: self.args.quantity,
+                report_error,
+                allow_delete: self.args.allow_delete,
+            };
+            return interactive::run(source, bytes_format);
+        }
+
+        let metadata_fields = {
+            let fields = self.args.metadata_fields();
+            let chart_needs =
+                |column| output_encoding.is_none() && self.args.columns.contains(&column);
+            let sort_needs = |key| !self.args.no_sort && self.args.sort == key;
+            MetadataFields {
+                count: fields.count
+                    || chart_needs(ChartColumn::Count)
+                    || sort_needs(SortKey::Count),
+                mtime: fields.mtime
+                    || chart_needs(ChartColumn::Mtime)
+                    || sort_needs(SortKey::Mtime),
+                ..fields
+            }
+        };
+
+        let cull_thresholds = self.args.cull_thresholds();
+        let scan_settings = self.args.scan_header.then(|| ScanSettings {
+            quantity: self.args.quantity.into(),
+            threads: rayon::current_num_threads(),
+            device_boundary: DeviceBoundary::from_one_file_system(self.args.one_file_system),
+            deduplicate_hardlinks: cfg!(unix) && self.args.deduplicate_hardlinks,
+            max_depth: match self.args.max_depth {
+                Depth::Infinite => None,
+                Depth::Finite(max_depth) => Some(max_depth.get()),
+            },
+            min_ratio: cull_thresholds.min_ratio,
+            min_ratio_of_parent: Some(cull_thresholds.min_ratio_of_parent)
+                .filter(|ratio| *ratio > 0.0),
+            min_size: Some(cull_thresholds.min_size).filter(|size| *size > 0),
+            max_children: self.args.max_children,
+            max_rows: self.args.max_rows,
+            group_by: self
+                .args
+                .group_by
+                .map(|group_by| group_by.name().to_string()),
+            category_rules: self
+                .args
+                .category_rules
+                .as_ref()
+                .map(OsStringDisplay::os_string_from),
+            largest_files: self.args.largest_files,
+        });
+
+        let grouping = match (self.args.group_by, &self.args.category_rules) {
+            (Some(group_by), _) => Some(Grouping::By(group_by)),
+            (None, Some(path)) => Some(Grouping::Rules(load_category_rules(path)?)),
+            (None, None) => None,
+        };
+
+        let bytes_format = self.args.bytes_format();
+        let stream_json_lines = self.args.streams_json_lines();
+
         trait GetSizeUtils: GetSize<Size: size::Size> {
             const INSTANCE: Self;
             const QUANTITY: Quantity;
@@ -333,15 +646,28 @@ impl App {
                     #[cfg(not(unix))] deduplicate_hardlinks: _,
                     one_file_system,
                     files,
-                    json_output,
-                    bytes_format,
                     top_down,
                     align_right,
+                    flat,
+                    exclude_ancestors,
                     max_depth,
-                    min_ratio,
+                    aggregate_culled,
+                    max_children,
+                    max_rows,
+                    sort,
+                    reverse,
                     no_sort,
+                    largest_files,
+                    largest_files_only,
                     omit_json_shared_details,
                     omit_json_shared_summary,
+                    csv_columns,
+                    csv_bytes_format,
+                    svg_layout,
+                    save_to,
+                    columns,
+                    quoting_style,
+                    bar_coloring,
                     ..
                 } => Sub {
                     direction: Direction::from_top_down(top_down),
@@ -352,11 +678,32 @@ impl App {
                     reporter: <$size_getter as CreateReporter<$progress>>::create_reporter(report_error),
                     bytes_format: <$size_getter as GetSizeUtils>::formatter(bytes_format),
                     files,
-                    json_output: JsonOutputParam::from_cli_flags(json_output, omit_json_shared_details, omit_json_shared_summary),
+                    json_output: JsonOutputParam::from_cli_flags(output_encoding, omit_json_shared_details, omit_json_shared_summary),
+                    stream_json_lines,
+                    metadata_fields,
+                    scan_settings,
+                    table_columns: csv_columns,
+                    table_bytes_format: csv_bytes_format.map(<$size_getter as GetSizeUtils>::formatter),
+                    svg_layout,
+                    save_to,
+                    ls_colors,
+                    bar_coloring,
+                    chart_columns: columns,
+                    quoting_style,
                     column_width_distribution,
                     max_depth,
-                    min_ratio,
+                    cull_thresholds,
+                    aggregate_culled,
+                    max_children,
+                    max_rows,
+                    sort,
+                    reverse,
                     no_sort,
+                    grouping,
+                    largest_files,
+                    largest_files_only,
+                    flat,
+                    exclude_ancestors,
                 }
                 .run(),
             )*} };
@@ -380,6 +727,13 @@ impl App {
 }
 
 mod hdd;
+mod history;
 mod host;
+mod input_reader;
+mod interactive;
+mod json_input;
 mod mount_point;
 mod overlapping_arguments;
+mod provenance;
+mod shaped_output;
+mod snapshot_diff;
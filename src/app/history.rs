@@ -0,0 +1,300 @@
+use crate::data_tree::{DataTree, DataTreeReflection};
+use crate::json_data::{JsonData, JsonDataBody, JsonTree, JsonUnit, ScanHeader, SchemaVersion};
+use crate::json_lines::JsonLine;
+use crate::os_string_display::OsStringDisplay;
+use crate::runtime_error::RuntimeError;
+use crate::size::{self, Blocks, Bytes};
+use rusqlite::types::Value;
+use rusqlite::{Connection, params};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Tables of a history database.
+///
+/// Each scan is a row of `snapshots`, and each of its nodes is a row of `nodes` keyed by
+/// its full path. Paths that are valid UTF-8 are stored as text, the others as blobs.
+const SCHEMA: &str = "
+    PRAGMA foreign_keys = ON;
+    CREATE TABLE IF NOT EXISTS snapshots (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        started_at INTEGER,
+        finished_at INTEGER,
+        hostname TEXT,
+        unit TEXT NOT NULL,
+        header TEXT
+    );
+    CREATE TABLE IF NOT EXISTS nodes (
+        snapshot_id INTEGER NOT NULL REFERENCES snapshots (id) ON DELETE CASCADE,
+        path TEXT NOT NULL,
+        parent TEXT,
+        depth INTEGER NOT NULL,
+        size INTEGER NOT NULL,
+        PRIMARY KEY (snapshot_id, path)
+    ) WITHOUT ROWID;
+";
+
+/// Create a function that wraps an error of the database at `path`.
+fn database_failure(path: &Path) -> impl Fn(rusqlite::Error) -> RuntimeError + '_ {
+    |error| RuntimeError::DatabaseFailure {
+        path: path.to_path_buf(),
+        error,
+    }
+}
+
+/// Open the history database at `path`, creating its tables if necessary.
+fn open(path: &Path) -> Result<Connection, RuntimeError> {
+    let connection = Connection::open(path).map_err(database_failure(path))?;
+    connection
+        .execute_batch(SCHEMA)
+        .map_err(database_failure(path))?;
+    Ok(connection)
+}
+
+/// Convert a path into a value of the `path` or `parent` column.
+fn path_value(path: &OsStringDisplay) -> Value {
+    match path.as_os_str().to_str() {
+        Some(path) => Value::Text(path.to_string()),
+        None => Value::Blob(path.as_os_str().as_encoded_bytes().to_vec()),
+    }
+}
+
+/// Convert a value of the `path` or `parent` column back into a path.
+fn path_from_value(value: Value) -> Option<OsString> {
+    match value {
+        Value::Text(path) => Some(path.into()),
+        #[cfg(unix)]
+        Value::Blob(path) => Some(std::os::unix::ffi::OsStringExt::from_vec(path)),
+        #[cfg(not(unix))]
+        Value::Blob(path) => Some(String::from_utf8_lossy(&path).into_owned().into()),
+        _ => None,
+    }
+}
+
+/// Append `tree` to the history database at `path` as a new snapshot.
+///
+/// If `synthetic_root` is `true`, the root of `tree` does not represent a real entry, so
+/// only its children are stored.
+pub(super) fn save<Size>(
+    path: &Path,
+    header: Option<&ScanHeader>,
+    tree: &DataTree<OsStringDisplay, Size>,
+    synthetic_root: bool,
+) -> Result<(), RuntimeError>
+where
+    Size: JsonUnit + Into<u64>,
+{
+    let header_json = header
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(RuntimeError::SerializationFailure)?;
+    let mut connection = open(path)?;
+    let transaction = connection.transaction().map_err(database_failure(path))?;
+    transaction
+        .execute(
+            "INSERT INTO snapshots (started_at, finished_at, hostname, unit, header)
+            VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                header.map(|header| header.started_at),
+                header.map(|header| header.finished_at),
+                header.and_then(|header| header.hostname.as_deref()),
+                Size::UNIT_NAME,
+                header_json,
+            ],
+        )
+        .map_err(database_failure(path))?;
+    let snapshot_id = transaction.last_insert_rowid();
+    {
+        let mut statement = transaction
+            .prepare(
+                "INSERT INTO nodes (snapshot_id, path, parent, depth, size)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+            )
+            .map_err(database_failure(path))?;
+        JsonLine::visit(tree, synthetic_root, &mut |line| {
+            let size: u64 = line.size.into();
+            statement
+                .execute(params![
+                    snapshot_id,
+                    path_value(&line.path),
+                    line.parent.as_ref().map(path_value),
+                    line.depth,
+                    size,
+                ])
+                .map(|_| ())
+        })
+        .map_err(database_failure(path))?;
+    }
+    transaction.commit().map_err(database_failure(path))
+}
+
+/// Append the data read by `--json-input` to the history database at `path`.
+///
+/// The data is returned so that it can be output as usual.
+pub(super) fn save_body(
+    path: &Path,
+    header: Option<&ScanHeader>,
+    body: JsonDataBody,
+    synthetic_root: bool,
+) -> Result<JsonDataBody, RuntimeError> {
+    fn save_tree<Size>(
+        path: &Path,
+        header: Option<&ScanHeader>,
+        json_tree: JsonTree<Size>,
+        synthetic_root: bool,
+    ) -> Result<JsonDataBody, RuntimeError>
+    where
+        Size: JsonUnit + Into<u64> + Send,
+        JsonTree<Size>: Into<JsonDataBody>,
+    {
+        let JsonTree { tree, shared } = json_tree;
+        let tree = tree
+            .par_try_into_tree()
+            .map_err(|error| RuntimeError::InvalidInputReflection(error.to_string()))?;
+        save(path, header, &tree, synthetic_root)?;
+        let tree = tree.into_reflection();
+        Ok(JsonTree { tree, shared }.into())
+    }
+
+    match body {
+        JsonDataBody::Bytes(tree) => save_tree(path, header, tree, synthetic_root),
+        JsonDataBody::Blocks(tree) => save_tree(path, header, tree, synthetic_root),
+    }
+}
+
+/// Load the oldest and the newest of the `count` most recent snapshots of the history
+/// database at `path`.
+///
+/// Each snapshot is labeled by the path of the database followed by its ID.
+pub(super) fn load_span(path: &Path, count: u64) -> Result<[(PathBuf, JsonData); 2], RuntimeError> {
+    let connection = open(path)?;
+    let snapshots = connection
+        .prepare("SELECT id, unit, header FROM snapshots ORDER BY id DESC LIMIT ?1")
+        .and_then(|mut statement| {
+            statement
+                .query_map([count], |row| {
+                    Ok((
+                        row.get::<_, i64>(0)?,
+                        row.get::<_, String>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                    ))
+                })?
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(database_failure(path))?;
+    let [newest, .., oldest] = snapshots.as_slice() else {
+        return Err(RuntimeError::InsufficientSnapshots(snapshots.len()));
+    };
+    let load = |(id, unit, header): &(i64, String, Option<String>)| -> Result<_, RuntimeError> {
+        let header = header
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(RuntimeError::DeserializationFailure)?;
+        let body = match unit.as_str() {
+            Bytes::UNIT_NAME => load_tree::<Bytes>(&connection, path, *id)?.into(),
+            Blocks::UNIT_NAME => load_tree::<Blocks>(&connection, path, *id)?.into(),
+            _ => {
+                return Err(RuntimeError::InvalidInputReflection(format!(
+                    "Snapshot {id} has an unknown unit: {unit:?}",
+                )));
+            }
+        };
+        let label = PathBuf::from(format!("{}#{id}", path.display()));
+        let json_data = JsonData {
+            schema_version: SchemaVersion,
+            binary_version: None,
+            header,
+            body,
+        };
+        Ok((label, json_data))
+    };
+    Ok([load(oldest)?, load(newest)?])
+}
+
+/// Rebuild the tree of a snapshot from its nodes.
+///
+/// Multiple roots are combined under a synthetic root named `(total)`.
+fn load_tree<Size>(
+    connection: &Connection,
+    path: &Path,
+    snapshot_id: i64,
+) -> Result<JsonTree<Size>, RuntimeError>
+where
+    Size: size::Size + From<u64>,
+{
+    let nodes = connection
+        .prepare("SELECT path, parent, size FROM nodes WHERE snapshot_id = ?1 ORDER BY depth DESC")
+        .and_then(|mut statement| {
+            statement
+                .query_map([snapshot_id], |row| {
+                    Ok((
+                        row.get::<_, Value>(0)?,
+                        row.get::<_, Value>(1)?,
+                        row.get::<_, u64>(2)?,
+                    ))
+                })?
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(database_failure(path))?;
+
+    let invalid_node = || {
+        RuntimeError::InvalidInputReflection(format!(
+            "Snapshot {snapshot_id} contains a node with an invalid path",
+        ))
+    };
+
+    // Nodes are visited from the deepest, so the children of a node are complete before the node itself.
+    let mut children_of: HashMap<OsString, Vec<DataTreeReflection<OsStringDisplay, Size>>> =
+        HashMap::new();
+    let mut roots = Vec::new();
+    for (node_path, parent, size) in nodes {
+        let node_path = path_from_value(node_path).ok_or_else(invalid_node)?;
+        let parent = match parent {
+            Value::Null => None,
+            parent => Some(path_from_value(parent).ok_or_else(invalid_node)?),
+        };
+        let children = children_of.remove(&node_path).unwrap_or_default();
+        let name = match &parent {
+            None => node_path,
+            Some(_) => Path::new(&node_path)
+                .file_name()
+                .ok_or_else(invalid_node)?
+                .to_os_string(),
+        };
+        let node = DataTreeReflection::new(
+            OsStringDisplay::os_string_from(name),
+            Size::from(size),
+            children,
+        );
+        match parent {
+            None => roots.push(node),
+            Some(parent) => children_of.entry(parent).or_default().push(node),
+        }
+    }
+
+    if let Some(orphan) = children_of.keys().next() {
+        return Err(RuntimeError::InvalidInputReflection(format!(
+            "Snapshot {snapshot_id} lacks the parent {orphan:?}",
+        )));
+    }
+
+    let tree = match <[_; 1]>::try_from(roots) {
+        Ok([root]) => root,
+        Err(roots) if roots.is_empty() => {
+            return Err(RuntimeError::InvalidInputReflection(format!(
+                "Snapshot {snapshot_id} has no nodes",
+            )));
+        }
+        Err(roots) => DataTreeReflection::new(
+            OsStringDisplay::os_string_from("(total)"),
+            roots.iter().map(|root| root.size).sum(),
+            roots,
+        ),
+    };
+
+    Ok(JsonTree {
+        tree,
+        shared: Default::default(),
+    })
+}
@@ -0,0 +1,82 @@
+use crate::runtime_error::RuntimeError;
+use flate2::bufread::MultiGzDecoder;
+use pipe_trait::Pipe;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Chain, Cursor, Read, stdin};
+use std::path::Path;
+
+/// Magic number at the start of a gzip stream.
+const GZIP_MAGIC: &[u8] = &[0x1F, 0x8B];
+
+/// Magic number at the start of a zstd frame.
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xB5, 0x2F, 0xFD];
+
+/// Buffered source of an input, with the bytes consumed by compression detection put back in front.
+type Source = BufReader<Chain<Cursor<Vec<u8>>, Box<dyn Read>>>;
+
+/// Reader of an input whose compression is detected and undone transparently.
+pub(super) enum InputReader {
+    /// The input is not compressed.
+    Plain(Source),
+    /// The input is compressed with gzip.
+    Gzip(MultiGzDecoder<Source>),
+    /// The input is compressed with zstd.
+    Zstd(zstd::Decoder<'static, Source>),
+}
+
+impl InputReader {
+    /// Open a file, or stdin if `path` is `-`.
+    pub(super) fn open(path: &Path) -> Result<Self, RuntimeError> {
+        let map_error = |error| RuntimeError::ReadInputFailure {
+            path: path.to_path_buf(),
+            error,
+        };
+        let source: Box<dyn Read> = if path == Path::new("-") {
+            Box::new(stdin())
+        } else {
+            File::open(path).map_err(map_error)?.pipe(Box::new)
+        };
+        InputReader::detect(source).map_err(map_error)
+    }
+
+    /// Detect the compression of `source` from its first few bytes.
+    fn detect(mut source: Box<dyn Read>) -> io::Result<Self> {
+        let mut prefix = Vec::with_capacity(ZSTD_MAGIC.len());
+        source
+            .by_ref()
+            .take(ZSTD_MAGIC.len() as u64)
+            .read_to_end(&mut prefix)?;
+        let is_gzip = prefix.starts_with(GZIP_MAGIC);
+        let is_zstd = prefix.starts_with(ZSTD_MAGIC);
+        let source = Cursor::new(prefix).chain(source).pipe(BufReader::new);
+        Ok(if is_gzip {
+            source.pipe(MultiGzDecoder::new).pipe(InputReader::Gzip)
+        } else if is_zstd {
+            source
+                .pipe(zstd::Decoder::with_buffer)?
+                .pipe(InputReader::Zstd)
+        } else {
+            InputReader::Plain(source)
+        })
+    }
+}
+
+impl Read for InputReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            InputReader::Plain(reader) => reader.read(buf),
+            InputReader::Gzip(reader) => reader.read(buf),
+            InputReader::Zstd(reader) => reader.read(buf),
+        }
+    }
+}
+
+/// Open an input, ready to be deserialized.
+///
+/// Only the output of a decoder is buffered, as the plain input is already buffered.
+pub(super) fn open_buffered(path: &Path) -> Result<Box<dyn BufRead>, RuntimeError> {
+    Ok(match InputReader::open(path)? {
+        InputReader::Plain(source) => Box::new(source),
+        reader => Box::new(BufReader::new(reader)),
+    })
+}
@@ -0,0 +1,584 @@
+use crate::args::Quantity;
+use crate::browser::{Browser, Order};
+use crate::bytes_format::BytesFormat;
+use crate::data_tree::DataTree;
+use crate::device::DeviceBoundary;
+use crate::fs_tree_builder::FsTreeBuilder;
+use crate::get_size::{GetApparentSize, GetSize};
+use crate::hardlink::{HardlinkIgnorant, RecordHardlinks};
+use crate::json_data::{JsonDataBody, JsonTree};
+use crate::os_string_display::OsStringDisplay;
+use crate::reporter::{ErrorOnlyReporter, ErrorReport, Reporter};
+use crate::runtime_error::RuntimeError;
+use crate::size::{self, Blocks, Bytes};
+use crate::visualizer::{QuotableName, QuotingStyle};
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{
+    self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+    enable_raw_mode,
+};
+use crossterm::{execute, queue};
+use std::fs;
+use std::io::{self, Stdout, Write, stdout};
+use std::iter::once;
+use std::path::{Path, PathBuf};
+use zero_copy_pads::UnicodeWidthChar;
+
+#[cfg(unix)]
+use crate::get_size::{GetBlockCount, GetBlockSize};
+#[cfg(unix)]
+use crate::hardlink::{DeduplicateSharedSize, HardlinkAware};
+
+/// Width of the bar column.
+const BAR_WIDTH: usize = 20;
+
+/// Tree being browsed, in either unit.
+enum Session {
+    Bytes(Browser<Bytes>),
+    Blocks(Browser<Blocks>),
+}
+
+impl Session {
+    /// Create a read-only session from the data read by `--json-input`.
+    ///
+    /// The hardlinks recorded in `.shared.details` remain available.
+    fn from_body(body: JsonDataBody, synthetic_root: bool) -> Result<Self, RuntimeError> {
+        fn browser<Size: size::Size + Send>(
+            json_tree: JsonTree<Size>,
+            synthetic_root: bool,
+        ) -> Result<Browser<Size>, RuntimeError> {
+            let JsonTree { tree, shared } = json_tree;
+            let tree = tree
+                .par_try_into_tree()
+                .map_err(|error| RuntimeError::InvalidInputReflection(error.to_string()))?;
+            Ok(Browser::new(tree, synthetic_root).with_hardlinks(shared.details))
+        }
+
+        Ok(match body {
+            JsonDataBody::Bytes(tree) => Session::Bytes(browser(tree, synthetic_root)?),
+            JsonDataBody::Blocks(tree) => Session::Blocks(browser(tree, synthetic_root)?),
+        })
+    }
+
+    /// Names of the nodes from a child of the root to the directory being viewed.
+    fn location(&self) -> &[OsStringDisplay] {
+        match self {
+            Session::Bytes(browser) => browser.location(),
+            Session::Blocks(browser) => browser.location(),
+        }
+    }
+
+    /// Follow `location` from the root as far as it leads.
+    fn navigate(&mut self, location: &[OsStringDisplay]) {
+        match self {
+            Session::Bytes(browser) => browser.navigate(location),
+            Session::Blocks(browser) => browser.navigate(location),
+        }
+    }
+}
+
+/// Settings to scan the filesystem again whenever the quantity is toggled.
+pub(super) struct LiveScan {
+    /// List of files and/or directories.
+    pub files: Vec<PathBuf>,
+    /// Whether to cross device boundary into a different filesystem.
+    pub device_boundary: DeviceBoundary,
+    /// Whether to detect and deduplicate hardlinks.
+    pub deduplicate_hardlinks: bool,
+}
+
+impl LiveScan {
+    /// Quantities that can be toggled between, in order.
+    #[cfg(unix)]
+    const QUANTITIES: &[Quantity] = &[
+        Quantity::ApparentSize,
+        Quantity::BlockSize,
+        Quantity::BlockCount,
+    ];
+    /// Quantities that can be toggled between, in order.
+    #[cfg(not(unix))]
+    const QUANTITIES: &[Quantity] = &[Quantity::ApparentSize];
+
+    /// Scan the filesystem to measure `quantity`.
+    fn scan(&self, quantity: Quantity, report_error: fn(ErrorReport)) -> Session {
+        match quantity {
+            Quantity::ApparentSize => Session::Bytes(self.browse(GetApparentSize, report_error)),
+            #[cfg(unix)]
+            Quantity::BlockSize => Session::Bytes(self.browse(GetBlockSize, report_error)),
+            #[cfg(unix)]
+            Quantity::BlockCount => Session::Blocks(self.browse(GetBlockCount, report_error)),
+        }
+    }
+
+    /// Scan the filesystem with `size_getter` and start browsing from the root.
+    fn browse<Size, SizeGetter>(
+        &self,
+        size_getter: SizeGetter,
+        report_error: fn(ErrorReport),
+    ) -> Browser<Size>
+    where
+        Size: size::Size + Send + Sync + 'static,
+        SizeGetter: GetSize<Size = Size> + Copy + Sync,
+    {
+        let reporter = ErrorOnlyReporter::new(report_error);
+        let synthetic_root = self.files.len() > 1;
+        let (mut tree, hardlinks) = if cfg!(unix) && self.deduplicate_hardlinks {
+            #[cfg(unix)]
+            {
+                let handler = HardlinkAware::new();
+                let mut tree = self.build(size_getter, &handler, &reporter);
+                let Ok(record) = handler.deduplicate(&mut tree);
+                (tree, Some(record.into_reflection()))
+            }
+            #[cfg(not(unix))]
+            unreachable!("hardlinks deduplication is only available on unix")
+        } else {
+            (self.build(size_getter, &HardlinkIgnorant, &reporter), None)
+        };
+        if synthetic_root {
+            *tree.name_mut() = OsStringDisplay::os_string_from("(total)");
+        }
+        Browser::new(tree, synthetic_root).with_hardlinks(hardlinks)
+    }
+
+    /// Build a tree of every listed path, combined under a nameless root if there are many.
+    fn build<Size, SizeGetter, HardlinksRecorder, Report>(
+        &self,
+        size_getter: SizeGetter,
+        hardlinks_recorder: &HardlinksRecorder,
+        reporter: &Report,
+    ) -> DataTree<OsStringDisplay, Size>
+    where
+        Size: size::Size + Send + Sync,
+        SizeGetter: GetSize<Size = Size> + Copy + Sync,
+        HardlinksRecorder: RecordHardlinks<Size, Report> + Sync,
+        Report: Reporter<Size> + Sync,
+    {
+        let mut trees = self
+            .files
+            .iter()
+            .map(|root| -> DataTree<OsStringDisplay, Size> {
+                FsTreeBuilder {
+                    root: root.clone(),
+                    size_getter,
+                    hardlinks_recorder,
+                    reporter,
+                    device_boundary: self.device_boundary,
+                    max_depth: u64::MAX,
+                }
+                .into()
+            });
+        let first = trees.next().expect("files should not be empty");
+        if trees.len() == 0 {
+            return first;
+        }
+        // This name is for hardlinks deduplication to work correctly as empty string is considered to be the start of any path.
+        let children = once(first).chain(trees).collect();
+        DataTree::dir(
+            OsStringDisplay::os_string_from(""),
+            Size::default(),
+            children,
+        )
+    }
+}
+
+/// Where the data being browsed comes from.
+pub(super) enum Source {
+    /// Data read by `--json-input`, which can neither be rescanned nor deleted.
+    Snapshot {
+        /// The data.
+        body: JsonDataBody,
+        /// Whether the root was created by combining multiple inputs or roots.
+        synthetic_root: bool,
+    },
+    /// The filesystem.
+    Scan {
+        /// How to scan the filesystem.
+        scan: LiveScan,
+        /// Quantity of the first scan.
+        quantity: Quantity,
+        /// How to report errors of the first scan.
+        report_error: fn(ErrorReport),
+        /// Whether entries can be deleted.
+        allow_delete: bool,
+    },
+}
+
+/// Browse the data in a full-screen terminal interface until the user quits.
+pub(super) fn run(source: Source, bytes_format: BytesFormat) -> Result<(), RuntimeError> {
+    let (mut session, mut live) = match source {
+        Source::Snapshot {
+            body,
+            synthetic_root,
+        } => (Session::from_body(body, synthetic_root)?, None),
+        Source::Scan {
+            scan,
+            quantity,
+            report_error,
+            allow_delete,
+        } => (
+            scan.scan(quantity, report_error),
+            Some((scan, quantity, allow_delete)),
+        ),
+    };
+
+    let mut screen = Screen::enter().map_err(RuntimeError::TerminalFailure)?;
+    let mut message = None;
+    loop {
+        let permissions = Permissions {
+            toggle_quantity: live.is_some(),
+            delete: live
+                .as_ref()
+                .is_some_and(|(_, _, allow_delete)| *allow_delete),
+        };
+        let action = match &mut session {
+            Session::Bytes(browser) => browse(
+                &mut screen,
+                browser,
+                bytes_format,
+                permissions,
+                message.take(),
+            ),
+            Session::Blocks(browser) => {
+                browse(&mut screen, browser, (), permissions, message.take())
+            }
+        }
+        .map_err(RuntimeError::TerminalFailure)?;
+        match action {
+            Action::Quit => return Ok(()),
+            Action::ToggleQuantity => {
+                let Some((scan, quantity, _)) = &mut live else {
+                    continue;
+                };
+                let position = LiveScan::QUANTITIES
+                    .iter()
+                    .position(|candidate| candidate == quantity)
+                    .unwrap_or_default();
+                *quantity = LiveScan::QUANTITIES[(position + 1) % LiveScan::QUANTITIES.len()];
+                screen
+                    .status(&format!("Scanning {}...", quantity.name()))
+                    .map_err(RuntimeError::TerminalFailure)?;
+                let location = session.location().to_vec();
+                // errors cannot be printed while the terminal is in raw mode
+                session = scan.scan(*quantity, ErrorReport::SILENT);
+                session.navigate(&location);
+                message = Some(format!("Measuring {}", quantity.name()));
+            }
+        }
+    }
+}
+
+/// What the user is allowed to do.
+#[derive(Clone, Copy)]
+struct Permissions {
+    /// Whether the filesystem can be scanned again to measure another quantity.
+    toggle_quantity: bool,
+    /// Whether entries can be deleted from the filesystem.
+    delete: bool,
+}
+
+/// Actions that [`browse`] cannot handle by itself.
+enum Action {
+    Quit,
+    ToggleQuantity,
+}
+
+/// Handle keys until the user either quits or toggles the quantity.
+fn browse<Size>(
+    screen: &mut Screen,
+    browser: &mut Browser<Size>,
+    bytes_format: Size::DisplayFormat,
+    permissions: Permissions,
+    mut message: Option<String>,
+) -> io::Result<Action>
+where
+    Size: size::Size + Into<u64>,
+{
+    loop {
+        screen.draw(browser, bytes_format, message.take().as_deref())?;
+        let key = screen.read_key()?;
+        let page = screen.rows() as isize;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Action::Quit),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(Action::Quit);
+            }
+            KeyCode::Up | KeyCode::Char('k') => browser.move_cursor(-1),
+            KeyCode::Down | KeyCode::Char('j') => browser.move_cursor(1),
+            KeyCode::PageUp => browser.move_cursor(-page),
+            KeyCode::PageDown => browser.move_cursor(page),
+            KeyCode::Home => browser.move_to_first(),
+            KeyCode::End => browser.move_to_last(),
+            KeyCode::Right | KeyCode::Enter | KeyCode::Char('l') => {
+                browser.enter();
+            }
+            KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h') => {
+                browser.leave();
+            }
+            KeyCode::Char('s') => {
+                browser.toggle_order();
+                message = Some(match browser.order() {
+                    Order::Size => "Sorted by size".to_string(),
+                    Order::Name => "Sorted by name".to_string(),
+                });
+            }
+            KeyCode::Char('t') if permissions.toggle_quantity => {
+                return Ok(Action::ToggleQuantity);
+            }
+            KeyCode::Char('t') => {
+                message = Some("The quantity of a snapshot cannot be changed".to_string());
+            }
+            KeyCode::Char('i') => message = Some(hardlink_info(browser, bytes_format)),
+            KeyCode::Char('d') if permissions.delete => {
+                message = delete_selected(screen, browser, bytes_format)?;
+            }
+            KeyCode::Char('d') => {
+                message =
+                    Some("Deleting is disabled, run with --allow-delete to enable it".to_string());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Describe the hardlinks of the selected entry.
+fn hardlink_info<Size: size::Size>(
+    browser: &Browser<Size>,
+    bytes_format: Size::DisplayFormat,
+) -> String {
+    let Some(entry) = browser.selected_hardlinks() else {
+        return "No hardlinks detected for the selected entry".to_string();
+    };
+    let mut others: Vec<_> = entry
+        .paths
+        .0
+        .iter()
+        .filter(|path| Some(path.as_path()) != browser.selected_path().as_deref())
+        .map(|path| {
+            QuotingStyle::Escape
+                .quote_os_str(path.as_os_str())
+                .into_owned()
+        })
+        .collect();
+    others.sort();
+    format!(
+        "inode {ino}, {size}, {links} links, also at: {others}",
+        ino = entry.ino,
+        size = entry.size.display(bytes_format),
+        links = entry.links,
+        others = if others.is_empty() {
+            "(outside the scanned paths)".to_string()
+        } else {
+            others.join(", ")
+        },
+    )
+}
+
+/// Ask for confirmation, then delete the selected entry from both the filesystem and the tree.
+fn delete_selected<Size>(
+    screen: &mut Screen,
+    browser: &mut Browser<Size>,
+    bytes_format: Size::DisplayFormat,
+) -> io::Result<Option<String>>
+where
+    Size: size::Size + Into<u64>,
+{
+    let (Some(path), Some(selected)) = (browser.selected_path(), browser.selected()) else {
+        return Ok(None);
+    };
+    let size = selected.size();
+    screen.draw(
+        browser,
+        bytes_format,
+        Some(&format!(
+            "Delete {path:?} ({size})? [y/N]",
+            size = size.display(bytes_format),
+        )),
+    )?;
+    if screen.read_key()?.code != KeyCode::Char('y') {
+        return Ok(Some("Cancelled".to_string()));
+    }
+    if let Err(error) = remove_path(&path) {
+        return Ok(Some(format!("Failed to delete {path:?}: {error}")));
+    }
+    browser.remove_selected();
+    Ok(Some(format!(
+        "Deleted {path:?}, freed {size}",
+        size = size.display(bytes_format),
+    )))
+}
+
+/// Remove a file, a symbolic link, or a directory with all of its content.
+fn remove_path(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// The terminal in raw mode on the alternate screen, restored when dropped.
+struct Screen {
+    /// Where to draw.
+    stdout: Stdout,
+    /// Index of the first visible entry.
+    scroll: usize,
+}
+
+impl Screen {
+    /// Switch the terminal into raw mode on the alternate screen.
+    fn enter() -> io::Result<Self> {
+        enable_raw_mode()?;
+        let mut stdout = stdout();
+        if let Err(error) = execute!(stdout, EnterAlternateScreen, Hide) {
+            let _ = disable_raw_mode();
+            return Err(error);
+        }
+        Ok(Screen { stdout, scroll: 0 })
+    }
+
+    /// Number of rows available to the entries.
+    fn rows(&self) -> usize {
+        let (_, height) = terminal::size().unwrap_or((80, 24));
+        (height as usize).saturating_sub(2).max(1)
+    }
+
+    /// Wait for a key to be pressed.
+    fn read_key(&mut self) -> io::Result<KeyEvent> {
+        loop {
+            if let Event::Key(key) = event::read()?
+                && key.kind != KeyEventKind::Release
+            {
+                return Ok(key);
+            }
+        }
+    }
+
+    /// Replace the status line with `text`.
+    fn status(&mut self, text: &str) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        queue!(
+            self.stdout,
+            MoveTo(0, height.saturating_sub(1)),
+            Clear(ClearType::CurrentLine),
+            Print(truncate(text, width as usize)),
+        )?;
+        self.stdout.flush()
+    }
+
+    /// Draw the directory being viewed, followed by `message` or a list of keys.
+    fn draw<Size>(
+        &mut self,
+        browser: &Browser<Size>,
+        bytes_format: Size::DisplayFormat,
+        message: Option<&str>,
+    ) -> io::Result<()>
+    where
+        Size: size::Size + Into<u64>,
+    {
+        let (width, height) = terminal::size()?;
+        let width = width as usize;
+        let rows = self.rows();
+        let cursor = browser.cursor();
+        if cursor < self.scroll {
+            self.scroll = cursor;
+        } else if cursor >= self.scroll + rows {
+            self.scroll = cursor + 1 - rows;
+        }
+
+        let directory = browser.directory();
+        let total: u64 = directory.size().into();
+        let path = browser.directory_path();
+        let title = if path.as_os_str().is_empty() {
+            format!(
+                "{} {}",
+                directory.name().quoted(QuotingStyle::Escape),
+                directory.size().display(bytes_format)
+            )
+        } else {
+            format!(
+                "{} {}",
+                QuotingStyle::Escape.quote_os_str(path.as_os_str()),
+                directory.size().display(bytes_format)
+            )
+        };
+        queue!(
+            self.stdout,
+            Clear(ClearType::All),
+            MoveTo(0, 0),
+            SetAttribute(Attribute::Bold),
+            Print(truncate(&title, width)),
+            SetAttribute(Attribute::Reset),
+        )?;
+
+        let entries = browser.entries();
+        for (row, (index, entry)) in entries
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(rows)
+            .enumerate()
+        {
+            let size: u64 = entry.size().into();
+            let ratio = if total == 0 {
+                0.0
+            } else {
+                size as f64 / total as f64
+            };
+            let filled = ((ratio * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+            let suffix = if entry.children().is_empty() { "" } else { "/" };
+            let line = format!(
+                "{size:>10} {percent:>5.1}% [{bar:<BAR_WIDTH$}] {name}{suffix}",
+                size = entry.size().display(bytes_format).to_string(),
+                percent = ratio * 100.0,
+                bar = "#".repeat(filled),
+                name = entry.name().quoted(QuotingStyle::Escape),
+            );
+            queue!(self.stdout, MoveTo(0, row as u16 + 1))?;
+            if index == cursor {
+                queue!(self.stdout, SetAttribute(Attribute::Reverse))?;
+            }
+            queue!(
+                self.stdout,
+                Print(truncate(&line, width)),
+                SetAttribute(Attribute::Reset),
+            )?;
+        }
+        if entries.is_empty() {
+            queue!(self.stdout, MoveTo(0, 1), Print("(empty)"))?;
+        }
+
+        let status = message
+            .unwrap_or("arrows: navigate  s: sort  t: quantity  i: hardlinks  d: delete  q: quit");
+        queue!(
+            self.stdout,
+            MoveTo(0, height.saturating_sub(1)),
+            Print(truncate(status, width)),
+        )?;
+        self.stdout.flush()
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(self.stdout, Show, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+/// Cut `text` to fit in `width` columns.
+///
+/// The text is cut by width rather than by length to keep wide characters intact.
+fn truncate(text: &str, width: usize) -> &str {
+    let mut text_width = 0;
+    let end = text
+        .char_indices()
+        .find(|(_, char)| {
+            text_width += char.width().unwrap_or(0);
+            text_width > width
+        })
+        .map_or(text.len(), |(index, _)| index);
+    &text[..end]
+}
@@ -0,0 +1,128 @@
+use super::InputShaping;
+use super::input_reader::open_buffered;
+use super::provenance::ensure_combinable;
+use super::shaped_output::ShapedOutput;
+use crate::args::InputFormat;
+use crate::json_data::{JsonData, JsonDataBody, JsonTree, ScanHeader};
+use crate::os_string_display::OsStringDisplay;
+use crate::runtime_error::RuntimeError;
+use crate::size::{self, Blocks, Bytes};
+use derive_more::TryIntoError;
+use pipe_trait::Pipe;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Load the JSON data specified by `--json-input` or `--input-format`.
+///
+/// It reads from stdin if `files` is empty or if a file is named `-`. Compressed files
+/// are decompressed transparently. Multiple files are combined under a synthetic root
+/// named `(total)`, each of which is labeled by its path, so a path cannot be given twice.
+/// The combination has no header, and it is refused if the headers of the files indicate
+/// that their sizes are incomparable.
+pub(super) fn load(
+    files: &[PathBuf],
+    format: InputFormat,
+) -> Result<(Option<ScanHeader>, JsonDataBody), RuntimeError> {
+    let read_file = |path: &Path| {
+        read_json_data(path, format).map(|json_data| (json_data.header, json_data.body))
+    };
+    match files {
+        [] => read_file("-".as_ref()),
+        [file] => read_file(file),
+        [first, rest @ ..] => {
+            // the hardlinks of each file are labeled by its path
+            let mut paths = HashSet::new();
+            if let Some(path) = files.iter().find(|path| !paths.insert(*path)) {
+                return Err(RuntimeError::MergeConflict(format!(
+                    "{path:?} is given more than once",
+                )));
+            }
+            let mut first_header = None;
+            let mut labeled_body = |path: &PathBuf| -> Result<_, RuntimeError> {
+                let (header, mut body) = read_file(path)?;
+                match &first_header {
+                    None => first_header = header.map(|header| (path.clone(), header)),
+                    Some((first_path, first_header)) => ensure_combinable(
+                        (first_path, Some(first_header)),
+                        (path, header.as_ref()),
+                    )?,
+                }
+                let label = OsStringDisplay::os_string_from(path);
+                match &mut body {
+                    JsonDataBody::Bytes(tree) => tree.rename_root(label),
+                    JsonDataBody::Blocks(tree) => tree.rename_root(label),
+                }
+                Ok(body)
+            };
+            let first = labeled_body(first)?;
+            let unit_name = first.unit_name();
+            let rest = rest.iter().map(labeled_body);
+            let body = match first {
+                JsonDataBody::Bytes(first) => merge::<Bytes>(unit_name, first, rest),
+                JsonDataBody::Blocks(first) => merge::<Blocks>(unit_name, first, rest),
+            }?;
+            Ok((None, body))
+        }
+    }
+}
+
+/// Read and deserialize JSON data from a file, or from stdin if `path` is `-`.
+pub(super) fn read_json_data(path: &Path, format: InputFormat) -> Result<JsonData, RuntimeError> {
+    let reader = open_buffered(path)?;
+    match format {
+        InputFormat::Json => {
+            serde_json::from_reader(reader).map_err(RuntimeError::DeserializationFailure)
+        }
+        InputFormat::Cbor => {
+            ciborium::from_reader(reader).map_err(RuntimeError::CborDeserializationFailure)
+        }
+    }
+}
+
+/// Shape a loaded tree like a chart would be, then write it in a
+/// [shaped](crate::args::OutputFormat::is_shaped) format.
+pub(super) fn write_shaped<Size>(
+    tree: JsonTree<Size>,
+    shaping: InputShaping,
+    output: ShapedOutput<'_, Size>,
+) -> Result<(), RuntimeError>
+where
+    Size: size::Size + Into<u64> + Serialize + Send,
+{
+    let InputShaping { max_depth, .. } = shaping;
+    let mut data_tree = tree
+        .tree
+        .par_try_into_tree()
+        .map_err(|error| RuntimeError::InvalidInputReflection(error.to_string()))?
+        .into_par_retained(|_, depth| depth + 1 < max_depth);
+    shaping.cull(&mut data_tree);
+    shaping.limit(&mut data_tree);
+    shaping.sort(&mut data_tree);
+    output.write(&data_tree)
+}
+
+/// Graft trees of the same unit under a synthetic root.
+fn merge<Size>(
+    unit_name: &'static str,
+    first: JsonTree<Size>,
+    rest: impl Iterator<Item = Result<JsonDataBody, RuntimeError>>,
+) -> Result<JsonDataBody, RuntimeError>
+where
+    Size: size::Size,
+    JsonTree<Size>: Into<JsonDataBody>,
+    JsonDataBody: TryInto<JsonTree<Size>, Error = TryIntoError<JsonDataBody>>,
+{
+    let mut trees = vec![first];
+    for body in rest {
+        let tree = body?
+            .try_into()
+            .map_err(|error: TryIntoError<JsonDataBody>| {
+                RuntimeError::UnitMismatch(unit_name, error.input.unit_name())
+            })?;
+        trees.push(tree);
+    }
+    JsonTree::merge(OsStringDisplay::os_string_from("(total)"), trees)
+        .pipe(Into::into)
+        .pipe(Ok)
+}
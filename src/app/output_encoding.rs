@@ -0,0 +1,249 @@
+use crate::args::OutputFormat;
+use crate::bytes_format::BytesFormat;
+use crate::data_tree::DataTree;
+use crate::fs_tree_builder::ReceiveNode;
+use crate::html_report::HtmlReport;
+use crate::json_data::JsonData;
+use crate::json_lines::JsonLine;
+use crate::os_string_display::OsStringDisplay;
+use crate::runtime_error::RuntimeError;
+use crate::size;
+use crate::table::Table;
+use serde::Serialize;
+use std::ffi::OsStr;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::io::{BufWriter, Write, stdout};
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+
+/// How serializable data should be written to stdout.
+#[derive(Debug, Clone, Copy)]
+pub struct OutputEncoding {
+    /// Format of the data.
+    pub format: OutputFormat,
+    /// Whether to compress the data with zstd.
+    pub zstd: bool,
+    /// Format of the sizes of the formats that display them, such as HTML.
+    pub bytes_format: BytesFormat,
+}
+
+impl OutputEncoding {
+    /// Infer from the CLI flags.
+    pub(super) fn from_cli_flags(
+        format: Option<OutputFormat>,
+        zstd: bool,
+        bytes_format: BytesFormat,
+    ) -> Option<Self> {
+        format.map(|format| OutputEncoding {
+            format,
+            zstd,
+            bytes_format,
+        })
+    }
+
+    /// Serialize `data` and write it to stdout.
+    pub fn write<Data: Serialize>(self, data: &Data) -> Result<(), RuntimeError> {
+        self.with_stdout(|writer| self.write_to(writer, data))
+    }
+
+    /// Write a snapshot to stdout, either serialized or embedded in an [`HtmlReport`].
+    pub fn write_snapshot(self, json_data: &JsonData) -> Result<(), RuntimeError> {
+        if self.format != OutputFormat::Html {
+            return self.write(json_data);
+        }
+        let json = serde_json::to_string(json_data).map_err(RuntimeError::SerializationFailure)?;
+        self.write_text(&HtmlReport {
+            json: &json,
+            bytes_format: self.bytes_format,
+        })
+    }
+
+    /// Write every node of `tree` to stdout as a [`JsonLine`].
+    ///
+    /// If `synthetic_root` is `true`, the root of `tree` is omitted and its children are
+    /// written as roots.
+    pub fn write_json_lines<Size>(
+        self,
+        tree: &DataTree<OsStringDisplay, Size>,
+        synthetic_root: bool,
+    ) -> Result<(), RuntimeError>
+    where
+        Size: size::Size + Serialize,
+    {
+        self.with_stdout(|writer| {
+            let mut writer = BufWriter::new(writer);
+            JsonLine::visit(tree, synthetic_root, &mut |line| {
+                write_json_line(&mut writer, &line)
+            })?;
+            writer.flush().map_err(RuntimeError::WriteOutputFailure)
+        })
+    }
+
+    /// Pass a [receiver](ReceiveNode) to `scan`, which writes the [`JsonLine`] of every node
+    /// it receives to stdout right away, except the nodes that are smaller than `min_size`.
+    pub fn stream_json_lines<Size>(
+        self,
+        min_size: u64,
+        scan: impl FnOnce(&dyn ReceiveNode<Size>),
+    ) -> Result<(), RuntimeError>
+    where
+        Size: size::Size + Into<u64> + Serialize + Send + Sync,
+    {
+        if !self.zstd {
+            return JsonLinesStream::run(BufWriter::new(stdout()), min_size, scan)?
+                .flush()
+                .map_err(RuntimeError::WriteOutputFailure);
+        }
+        let encoder = zstd::Encoder::new(stdout(), zstd::DEFAULT_COMPRESSION_LEVEL)
+            .map_err(RuntimeError::WriteOutputFailure)?;
+        JsonLinesStream::run(BufWriter::new(encoder), min_size, scan)?
+            .into_inner()
+            .map_err(|error| RuntimeError::WriteOutputFailure(error.into_error()))?
+            .finish()
+            .map_err(RuntimeError::WriteOutputFailure)?
+            .flush()
+            .map_err(RuntimeError::WriteOutputFailure)
+    }
+
+    /// Write a [`Table`] to stdout.
+    pub fn write_table<Name, Size>(self, table: &Table<'_, Name, Size>) -> Result<(), RuntimeError>
+    where
+        Name: AsRef<OsStr>,
+        Size: size::Size + Into<u64>,
+    {
+        self.with_stdout(|writer| {
+            let mut writer = BufWriter::new(writer);
+            table
+                .write_to(&mut writer)
+                .map_err(RuntimeError::WriteOutputFailure)?;
+            writer.flush().map_err(RuntimeError::WriteOutputFailure)
+        })
+    }
+
+    /// Write an [`SvgChart`](crate::svg_chart::SvgChart) or any other text to stdout.
+    pub fn write_text(self, text: &impl Display) -> Result<(), RuntimeError> {
+        self.with_stdout(|writer| {
+            let mut writer = BufWriter::new(writer);
+            write!(writer, "{text}").map_err(RuntimeError::WriteOutputFailure)?;
+            writer.flush().map_err(RuntimeError::WriteOutputFailure)
+        })
+    }
+
+    /// Pass stdout to `write`, wrapped in a zstd encoder if compression is requested.
+    fn with_stdout(
+        self,
+        write: impl FnOnce(&mut dyn Write) -> Result<(), RuntimeError>,
+    ) -> Result<(), RuntimeError> {
+        let mut stdout = stdout().lock();
+        if !self.zstd {
+            return write(&mut stdout);
+        }
+        let mut encoder = zstd::Encoder::new(stdout, zstd::DEFAULT_COMPRESSION_LEVEL)
+            .map_err(RuntimeError::WriteOutputFailure)?;
+        write(&mut encoder)?;
+        encoder
+            .finish()
+            .map_err(RuntimeError::WriteOutputFailure)?
+            .flush()
+            .map_err(RuntimeError::WriteOutputFailure)
+    }
+
+    /// Serialize `data` and write it to `writer`.
+    fn write_to<Data, Writer>(self, writer: Writer, data: &Data) -> Result<(), RuntimeError>
+    where
+        Data: Serialize,
+        Writer: Write,
+    {
+        let mut writer = BufWriter::new(writer);
+        match self.format {
+            OutputFormat::Json => serde_json::to_writer(&mut writer, data)
+                .map_err(RuntimeError::SerializationFailure)?,
+            OutputFormat::Cbor => ciborium::into_writer(data, &mut writer)
+                .map_err(RuntimeError::CborSerializationFailure)?,
+            OutputFormat::Jsonl
+            | OutputFormat::Csv
+            | OutputFormat::Tsv
+            | OutputFormat::Html
+            | OutputFormat::Svg => {
+                return Err(RuntimeError::UnsupportedOutputFormat(self.format.name()));
+            }
+        }
+        writer.flush().map_err(RuntimeError::WriteOutputFailure)
+    }
+}
+
+/// [Receiver](ReceiveNode) of [`OutputEncoding::stream_json_lines`].
+struct JsonLinesStream<Writer> {
+    /// Nodes smaller than this are not written.
+    min_size: u64,
+    /// Destination of the lines, or the first error that occurred while writing them.
+    writer: Mutex<Result<Writer, RuntimeError>>,
+}
+
+impl<Writer: Write + Send> JsonLinesStream<Writer> {
+    /// Pass a stream to `scan`, then return `writer` unless writing has failed.
+    fn run<Size>(
+        writer: Writer,
+        min_size: u64,
+        scan: impl FnOnce(&dyn ReceiveNode<Size>),
+    ) -> Result<Writer, RuntimeError>
+    where
+        Size: size::Size + Into<u64> + Serialize + Send + Sync,
+    {
+        let stream = JsonLinesStream {
+            min_size,
+            writer: Mutex::new(Ok(writer)),
+        };
+        scan(&stream);
+        stream
+            .writer
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<Writer> Debug for JsonLinesStream<Writer> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("JsonLinesStream")
+            .field("min_size", &self.min_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Size, Writer> ReceiveNode<Size> for JsonLinesStream<Writer>
+where
+    Size: size::Size + Into<u64> + Serialize + Send + Sync,
+    Writer: Write + Send,
+{
+    fn receive(
+        &self,
+        path: &Path,
+        parent: Option<&Path>,
+        depth: u64,
+        node: &DataTree<OsStringDisplay, Size>,
+    ) {
+        if node.size().into() < self.min_size {
+            return;
+        }
+        let line = JsonLine::new(node, path, parent, depth);
+        let mut writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Ok(output) = &mut *writer
+            && let Err(error) = write_json_line(output, &line)
+        {
+            *writer = Err(error);
+        }
+    }
+}
+
+/// Serialize `data` as a single line of JSON, terminated by a line feed.
+fn write_json_line<Data, Writer>(writer: &mut Writer, data: &Data) -> Result<(), RuntimeError>
+where
+    Data: Serialize,
+    Writer: Write,
+{
+    serde_json::to_writer(&mut *writer, data).map_err(RuntimeError::SerializationFailure)?;
+    writer
+        .write_all(b"\n")
+        .map_err(RuntimeError::WriteOutputFailure)
+}
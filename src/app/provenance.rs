@@ -0,0 +1,99 @@
+use super::mount_point::find_mount_point;
+use crate::device::DeviceNumber;
+use crate::json_data::{ScanHeader, ScanRoot, ScanSettings};
+use crate::os_string_display::OsStringDisplay;
+use crate::runtime_error::RuntimeError;
+use std::fs::canonicalize;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use sysinfo::{Disks, System};
+
+/// Convert a [`SystemTime`] into seconds since the Unix epoch.
+pub(super) fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// Create a [`ScanHeader`] for a scan of `files` that started at `started_at` and has just finished.
+pub(super) fn scan_header(
+    files: &[PathBuf],
+    started_at: SystemTime,
+    settings: ScanSettings,
+) -> ScanHeader {
+    let disks = Disks::new_with_refreshed_list();
+    ScanHeader {
+        started_at: unix_seconds(started_at),
+        finished_at: unix_seconds(SystemTime::now()),
+        hostname: System::host_name(),
+        roots: files.iter().map(|path| scan_root(path, &disks)).collect(),
+        settings,
+    }
+}
+
+/// Create a [`ScanRoot`] of a scanned path.
+fn scan_root(path: &Path, disks: &Disks) -> ScanRoot {
+    let path = canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mount_points = disks.iter().map(|disk| disk.mount_point());
+    let filesystem = find_mount_point(&path, mount_points)
+        .and_then(|mount_point| disks.iter().find(|disk| disk.mount_point() == mount_point))
+        .map(|disk| disk.file_system().to_string_lossy().into_owned());
+    ScanRoot {
+        device: device_number(&path),
+        filesystem,
+        path: OsStringDisplay::os_string_from(path),
+    }
+}
+
+/// Get the device number of the filesystem that contains `path`.
+#[cfg(unix)]
+fn device_number(path: &Path) -> Option<DeviceNumber> {
+    std::fs::symlink_metadata(path)
+        .ok()
+        .map(|stats| DeviceNumber::get(&stats))
+}
+
+/// Get the device number of the filesystem that contains `path`.
+#[cfg(not(unix))]
+fn device_number(_: &Path) -> Option<DeviceNumber> {
+    None
+}
+
+/// Refuse to combine two inputs whose headers indicate that their sizes are incomparable.
+///
+/// Inputs without headers are assumed to be combinable.
+pub(super) fn ensure_combinable(
+    left: (&Path, Option<&ScanHeader>),
+    right: (&Path, Option<&ScanHeader>),
+) -> Result<(), RuntimeError> {
+    ensure(left, right, |left, right| {
+        left.settings.incompatibility(&right.settings)
+    })
+}
+
+/// Refuse to compare two snapshots whose headers indicate that they are of different scans.
+///
+/// Snapshots without headers are assumed to be comparable.
+pub(super) fn ensure_comparable(
+    left: (&Path, Option<&ScanHeader>),
+    right: (&Path, Option<&ScanHeader>),
+) -> Result<(), RuntimeError> {
+    ensure(left, right, ScanHeader::difference)
+}
+
+/// Report the difference found by `find_difference` between two headers as an error.
+fn ensure(
+    (left_path, left): (&Path, Option<&ScanHeader>),
+    (right_path, right): (&Path, Option<&ScanHeader>),
+    find_difference: impl FnOnce(&ScanHeader, &ScanHeader) -> Option<&'static str>,
+) -> Result<(), RuntimeError> {
+    let (Some(left), Some(right)) = (left, right) else {
+        return Ok(());
+    };
+    match find_difference(left, right) {
+        None => Ok(()),
+        Some(setting) => Err(RuntimeError::IncomparableSnapshots(format!(
+            "{left_path:?} and {right_path:?} were scanned with different {setting}",
+        ))),
+    }
+}
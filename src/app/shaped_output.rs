@@ -0,0 +1,65 @@
+use super::OutputEncoding;
+use crate::args::OutputFormat;
+use crate::data_tree::DataTree;
+use crate::os_string_display::OsStringDisplay;
+use crate::runtime_error::RuntimeError;
+use crate::size;
+use crate::svg_chart::{SvgChart, SvgLayout};
+use crate::table::{Column, Table};
+use serde::Serialize;
+
+/// Output in a [shaped](OutputFormat::is_shaped) format along with its options.
+pub(super) struct ShapedOutput<'a, Size: size::Size> {
+    /// How to write the output.
+    pub encoding: OutputEncoding,
+    /// Whether the root of the tree does not represent a real entry.
+    pub synthetic_root: bool,
+    /// Columns of the CSV or TSV output.
+    pub table_columns: &'a [Column],
+    /// Format of the sizes in the CSV or TSV output, or [`None`] for raw numbers.
+    pub table_bytes_format: Option<Size::DisplayFormat>,
+    /// Shape of the SVG output.
+    pub svg_layout: SvgLayout,
+    /// Format of the sizes in the SVG output.
+    pub bytes_format: Size::DisplayFormat,
+}
+
+impl<Size> ShapedOutput<'_, Size>
+where
+    Size: size::Size + Into<u64> + Serialize,
+{
+    /// Write `data_tree`, which has already been shaped like a chart would be.
+    pub(super) fn write(
+        self,
+        data_tree: &DataTree<OsStringDisplay, Size>,
+    ) -> Result<(), RuntimeError> {
+        let ShapedOutput {
+            encoding,
+            synthetic_root,
+            table_columns,
+            table_bytes_format,
+            svg_layout,
+            bytes_format,
+        } = self;
+        if let Some(delimiter) = encoding.format.table_delimiter() {
+            return encoding.write_table(&Table {
+                data_tree,
+                columns: table_columns,
+                delimiter,
+                bytes_format: table_bytes_format,
+                synthetic_root,
+            });
+        }
+        if encoding.format == OutputFormat::Svg {
+            let (width, height) = svg_layout.dimensions();
+            return encoding.write_text(&SvgChart {
+                data_tree,
+                layout: svg_layout,
+                bytes_format,
+                width,
+                height,
+            });
+        }
+        encoding.write_json_lines(data_tree, synthetic_root)
+    }
+}
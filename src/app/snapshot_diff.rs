@@ -0,0 +1,144 @@
+use super::json_input::read_json_data;
+use super::provenance::ensure_comparable;
+use super::{ChartLayout, InputShaping, OutputEncoding};
+use crate::args::InputFormat;
+use crate::bytes_format::BytesFormat;
+use crate::diff::{DiffLabel, DiffStatus, DiffTree, JsonDiff, JsonDiffBody, JsonDiffTree};
+use crate::json_data::{BinaryVersion, JsonData, JsonDataBody, JsonTree, SchemaVersion};
+use crate::runtime_error::RuntimeError;
+use crate::size;
+use crate::visualizer::{ChartOptions, Visualizer};
+use std::path::{Path, PathBuf};
+
+/// Compare two JSON snapshots and print the difference.
+pub(super) fn run(
+    snapshots: &[PathBuf],
+    input_format: InputFormat,
+    output_encoding: Option<OutputEncoding>,
+    bytes_format: BytesFormat,
+    layout: ChartLayout<'_>,
+    shaping: InputShaping,
+) -> Result<(), RuntimeError> {
+    let [old, new] = snapshots else {
+        panic!("Impossible! clap should have required exactly 2 values for --diff");
+    };
+
+    let old_data = read_json_data(old, input_format)?;
+    let new_data = read_json_data(new, input_format)?;
+    compare_snapshots(
+        (old, old_data),
+        (new, new_data),
+        output_encoding,
+        bytes_format,
+        layout,
+        shaping,
+    )
+}
+
+/// Compare two snapshots, each of which is labeled by its path, and print the difference.
+pub(super) fn compare_snapshots(
+    (old_path, old_data): (&Path, JsonData),
+    (new_path, new_data): (&Path, JsonData),
+    output_encoding: Option<OutputEncoding>,
+    bytes_format: BytesFormat,
+    layout: ChartLayout<'_>,
+    shaping: InputShaping,
+) -> Result<(), RuntimeError> {
+    ensure_comparable(
+        (old_path, old_data.header.as_ref()),
+        (new_path, new_data.header.as_ref()),
+    )?;
+    let old = old_data.body;
+    let new = new_data.body;
+
+    macro_rules! compare {
+        ($old:expr, $new:expr, $bytes_format:expr) => {
+            compare_trees($old, $new, output_encoding, $bytes_format, layout, shaping)
+        };
+    }
+
+    match (old, new) {
+        (JsonDataBody::Bytes(old), JsonDataBody::Bytes(new)) => compare!(old, new, bytes_format),
+        (JsonDataBody::Blocks(old), JsonDataBody::Blocks(new)) => compare!(old, new, ()),
+        (old, new) => Err(RuntimeError::UnitMismatch(old.unit_name(), new.unit_name())),
+    }
+}
+
+/// Compare two trees of the same unit, then either print a chart or a JSON document.
+fn compare_trees<Size>(
+    old: JsonTree<Size>,
+    new: JsonTree<Size>,
+    output_encoding: Option<OutputEncoding>,
+    bytes_format: Size::DisplayFormat,
+    layout: ChartLayout<'_>,
+    shaping: InputShaping,
+) -> Result<(), RuntimeError>
+where
+    Size: size::Size + Into<u64> + Send + Sync,
+    Size::DisplayFormat: Send + Sync,
+    JsonDiffTree<Size>: Into<JsonDiffBody>,
+{
+    let into_data_tree = |tree: JsonTree<Size>| {
+        tree.tree
+            .par_try_into_tree()
+            .map_err(|error| RuntimeError::InvalidInputReflection(error.to_string()))
+    };
+    let diff = DiffTree::par_compare(into_data_tree(old)?, into_data_tree(new)?);
+
+    if let Some(output_encoding) = output_encoding {
+        let json_diff = JsonDiff {
+            schema_version: SchemaVersion,
+            binary_version: Some(BinaryVersion::current()),
+            body: JsonDiffTree::from(diff).into(),
+        };
+        return output_encoding.write(&json_diff);
+    }
+
+    let ChartLayout {
+        column_width_distribution,
+        direction,
+        bar_alignment,
+        columns,
+        quoting_style,
+        coloring,
+        ..
+    } = layout;
+    let InputShaping {
+        max_depth,
+        cull_thresholds,
+        aggregate_culled,
+        ..
+    } = shaping;
+
+    let mut data_tree = diff
+        .into_chart_tree(bytes_format)
+        .into_par_retained(|_, depth| depth + 1 < max_depth);
+    if aggregate_culled {
+        data_tree.par_cull_into_aggregates(cull_thresholds, |culled| DiffLabel {
+            name: super::aggregate_name(culled.len() as u64),
+            status: DiffStatus::Retained,
+            delta: culled.iter().map(|child| child.name().delta).sum(),
+            format: bytes_format,
+        });
+    } else {
+        data_tree.par_cull(cull_thresholds);
+    }
+    shaping.limit(&mut data_tree);
+    shaping.sort(&mut data_tree);
+
+    let visualizer = Visualizer {
+        data_tree: &data_tree,
+        bytes_format,
+        column_width_distribution,
+        direction,
+        bar_alignment,
+    }
+    .with_options(ChartOptions {
+        columns,
+        quoting_style,
+        coloring,
+    });
+
+    print!("{visualizer}"); // it already ends with "\n", println! isn't needed here.
+    Ok(())
+}
@@ -1,43 +1,78 @@
-use crate::args::{Depth, Fraction};
-use crate::data_tree::DataTree;
+use super::OutputEncoding;
+use super::history;
+use super::provenance::scan_header;
+use super::shaped_output::ShapedOutput;
+use crate::args::Depth;
+use crate::data_tree::{CullThresholds, DataTree, SortKey};
 use crate::device::DeviceBoundary;
-use crate::fs_tree_builder::FsTreeBuilder;
+use crate::fs_tree_builder::{Extras, FsTreeBuilder};
 use crate::get_size::GetSize;
+use crate::group_by::{GroupRecorder, Grouping};
 use crate::hardlink::{DeduplicateSharedSize, HardlinkIgnorant, RecordHardlinks};
 use crate::json_data::{
-    BinaryVersion, JsonData, JsonDataBody, JsonShared, JsonTree, SchemaVersion,
+    BinaryVersion, JsonData, JsonDataBody, JsonShared, JsonTree, JsonUnit, ScanSettings,
+    SchemaVersion,
 };
+use crate::largest_files::{self, LargestFiles, RankedList};
+use crate::node_metadata::MetadataFields;
 use crate::os_string_display::OsStringDisplay;
 use crate::reporter::ParallelReporter;
 use crate::runtime_error::RuntimeError;
 use crate::size;
 use crate::status_board::GLOBAL_STATUS_BOARD;
-use crate::visualizer::{BarAlignment, ColumnWidthDistribution, Direction, Visualizer};
+use crate::svg_chart::SvgLayout;
+use crate::table::Column;
+use crate::visualizer::{
+    BarAlignment, BarColoring, ChartColumn, ChartOptions, Coloring, ColumnWidthDistribution,
+    Direction, FlatList, LsColors, QuotingStyle, Visualizer,
+};
 use pipe_trait::Pipe;
 use serde::Serialize;
-use std::io::stdout;
 use std::iter::once;
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 /// The sub program of the main application.
 pub struct Sub<Size, SizeGetter, HardlinksHandler, Report>
 where
     Report: ParallelReporter<Size> + Sync,
-    Size: size::Size + Into<u64> + Serialize + Send + Sync,
+    Size: JsonUnit + Into<u64> + Serialize + Send + Sync,
     SizeGetter: GetSize<Size = Size> + Copy + Sync,
     HardlinksHandler: RecordHardlinks<Size, Report> + HardlinkSubroutines<Size> + Sync,
     JsonTree<Size>: Into<JsonDataBody>,
 {
     /// List of files and/or directories.
     pub files: Vec<PathBuf>,
-    /// Print JSON data instead of an ASCII chart.
+    /// Print JSON data (or its binary equivalent) instead of an ASCII chart.
     pub json_output: Option<JsonOutputParam>,
+    /// Whether to write the JSON Lines output while scanning instead of after shaping the tree.
+    pub stream_json_lines: bool,
+    /// Which metadata of each node to include in the JSON output.
+    pub metadata_fields: MetadataFields,
+    /// Settings to record in the header of the JSON output, if it should have one.
+    pub scan_settings: Option<ScanSettings>,
+    /// History database to append the data to.
+    pub save_to: Option<PathBuf>,
+    /// Columns of the CSV or TSV output.
+    pub table_columns: Vec<Column>,
+    /// Format to be used to display the sizes in the CSV or TSV output, or [`None`] for raw numbers.
+    pub table_bytes_format: Option<Size::DisplayFormat>,
+    /// Shape of the SVG output.
+    pub svg_layout: SvgLayout,
     /// Format to be used to [`display`](size::Size::display) the sizes returned by [`size_getter`](Self::size_getter).
     pub bytes_format: Size::DisplayFormat,
     /// The direction of the visualization.
     pub direction: Direction,
     /// The alignment of the bars.
     pub bar_alignment: BarAlignment,
+    /// Styles of the names in the colored chart, or [`None`] for a monochrome chart.
+    pub ls_colors: Option<LsColors>,
+    /// How to color the bars of the colored chart.
+    pub bar_coloring: BarColoring,
+    /// Columns of the chart besides the tree and the bars.
+    pub chart_columns: Vec<ChartColumn>,
+    /// How to write the names of the entries in the chart.
+    pub quoting_style: QuotingStyle,
     /// Distribution and number of characters/blocks can be placed in a line.
     pub column_width_distribution: ColumnWidthDistribution,
     /// Maximum number of levels that should be visualized.
@@ -50,15 +85,35 @@ where
     pub device_boundary: DeviceBoundary,
     /// Reports measurement progress.
     pub reporter: Report,
-    /// Minimal size proportion required to appear.
-    pub min_ratio: Fraction,
+    /// Sizes required to appear.
+    pub cull_thresholds: CullThresholds,
+    /// Whether to replace the culled entries of each directory with a single entry.
+    pub aggregate_culled: bool,
+    /// Maximum number of children to display for each directory.
+    pub max_children: Option<usize>,
+    /// Maximum number of nodes to display.
+    pub max_rows: Option<usize>,
+    /// Property by which the entries are sorted.
+    pub sort: SortKey,
+    /// Reverse the order of entries.
+    pub reverse: bool,
     /// Preserve order of entries.
     pub no_sort: bool,
+    /// Show the categories of the entries instead of the directory tree.
+    pub grouping: Option<Grouping>,
+    /// Number of the largest files to list, if any.
+    pub largest_files: Option<usize>,
+    /// Only list the largest files instead of showing the tree.
+    pub largest_files_only: bool,
+    /// List the entries with their full paths instead of drawing the tree.
+    pub flat: bool,
+    /// Omit the entries that contain other listed entries from the flat list.
+    pub exclude_ancestors: bool,
 }
 
 impl<Size, SizeGetter, HardlinksHandler, Report> Sub<Size, SizeGetter, HardlinksHandler, Report>
 where
-    Size: size::Size + Into<u64> + Serialize + Send + Sync,
+    Size: JsonUnit + Into<u64> + Serialize + Send + Sync,
     Report: ParallelReporter<Size> + Sync,
     SizeGetter: GetSize<Size = Size> + Copy + Sync,
     HardlinksHandler: RecordHardlinks<Size, Report> + HardlinkSubroutines<Size> + Sync,
@@ -69,22 +124,97 @@ where
         let Sub {
             files,
             json_output,
+            stream_json_lines,
+            metadata_fields,
+            scan_settings,
+            save_to,
+            table_columns,
+            table_bytes_format,
+            svg_layout,
             bytes_format,
             direction,
             bar_alignment,
+            ls_colors,
+            bar_coloring,
+            chart_columns,
+            quoting_style,
             column_width_distribution,
             max_depth,
             size_getter,
             hardlinks_handler,
             device_boundary,
             reporter,
-            min_ratio,
+            cull_thresholds,
+            aggregate_culled,
+            max_children,
+            max_rows,
+            sort,
+            reverse,
             no_sort,
+            grouping,
+            largest_files,
+            largest_files_only,
+            flat,
+            exclude_ancestors,
         } = self;
 
         let max_depth = max_depth.get();
+        // the snapshot of --save-to records the whole tree, which is shaped for the output later
+        let scan_depth = if save_to.is_some() {
+            u64::MAX
+        } else {
+            max_depth
+        };
+        let started_at = SystemTime::now();
+        let group_recorder = grouping.clone().map(|grouping| {
+            let max_paths = max_children.unwrap_or(GroupRecorder::<Size>::DEFAULT_MAX_PATHS);
+            GroupRecorder::new(grouping, max_paths)
+        });
+        let largest_files = largest_files.map(LargestFiles::new);
+        let extras = Extras {
+            metadata_fields,
+            group_recorder: group_recorder.as_ref(),
+            largest_files: largest_files.as_ref(),
+            node_receiver: None,
+        };
+
+        if stream_json_lines && let Some(json_output) = json_output {
+            let roots = if files.is_empty() {
+                vec![".".into()]
+            } else {
+                files
+            };
+            // the synthetic root of multiple roots takes up a level
+            let max_depth = if roots.len() > 1 {
+                max_depth.saturating_sub(1)
+            } else {
+                max_depth
+            };
+            let stream_result =
+                json_output
+                    .encoding
+                    .stream_json_lines(cull_thresholds.min_size, |receiver| {
+                        for root in roots {
+                            FsTreeBuilder {
+                                reporter: &reporter,
+                                root,
+                                size_getter,
+                                hardlinks_recorder: &hardlinks_handler,
+                                device_boundary,
+                                max_depth,
+                            }
+                            .build_with(extras.with_node_receiver(receiver));
+                        }
+                    });
+            if reporter.destroy().is_err() {
+                eprintln!("[warning] Failed to destroy the thread that reports progress");
+            }
+            GLOBAL_STATUS_BOARD.clear_line(0);
+            return stream_result;
+        }
 
         let mut iter = files
+            .clone()
             .into_iter()
             .map(|root| -> DataTree<OsStringDisplay, Size> {
                 FsTreeBuilder {
@@ -93,9 +223,9 @@ where
                     size_getter,
                     hardlinks_recorder: &hardlinks_handler,
                     device_boundary,
-                    max_depth,
+                    max_depth: scan_depth,
                 }
-                .into()
+                .build_with(extras)
             });
 
         let data_tree = if let Some(data_tree) = iter.next() {
@@ -103,8 +233,14 @@ where
         } else {
             return Sub {
                 files: vec![".".into()],
+                scan_settings,
+                save_to,
+                table_columns,
+                ls_colors,
+                chart_columns,
                 hardlinks_handler,
                 reporter,
+                grouping,
                 ..self
             }
             .run();
@@ -121,39 +257,104 @@ where
             let fake_root_name = OsStringDisplay::os_string_from("");
 
             DataTree::dir(fake_root_name, Size::default(), children)
-                .into_par_retained(|_, depth| depth + 1 < max_depth)
+                .into_par_retained(|_, depth| depth + 1 < scan_depth)
         };
 
         if reporter.destroy().is_err() {
             eprintln!("[warning] Failed to destroy the thread that reports progress");
         }
 
-        let min_ratio: f32 = min_ratio.into();
+        let data_tree = match group_recorder {
+            Some(group_recorder) => group_recorder.into_tree(data_tree.name().clone()),
+            None => data_tree,
+        };
+
+        let header = scan_settings.map(|settings| scan_header(&files, started_at, settings));
+
         let (data_tree, deduplication_record) = {
             let mut data_tree = data_tree;
-            data_tree.par_cull_insignificant_data(min_ratio);
-            if !no_sort {
-                data_tree.par_sort_by(|left, right| left.size().cmp(&right.size()).reverse());
-            }
-            let deduplication_record = hardlinks_handler.deduplicate(&mut data_tree);
-            if !only_one_arg {
-                assert_eq!(data_tree.name().as_os_str().to_str(), Some(""));
-                *data_tree.name_mut() = OsStringDisplay::os_string_from("(total)");
-            }
+            let shape = |data_tree: &mut DataTree<OsStringDisplay, Size>| {
+                if aggregate_culled {
+                    data_tree.par_cull_into_aggregates(cull_thresholds, |culled| {
+                        super::aggregate_name(culled.len() as u64)
+                    });
+                } else {
+                    data_tree.par_cull(cull_thresholds);
+                }
+                // the recorder has already limited the paths of each category, and the number of
+                // categories is not limited
+                if let Some(max_children) = max_children
+                    && grouping.is_none()
+                {
+                    data_tree.par_retain_largest_children(max_children);
+                }
+                if let Some(max_rows) = max_rows {
+                    data_tree.retain_largest_nodes(max_rows);
+                }
+                if !no_sort {
+                    data_tree.par_sort_by_key(sort, reverse);
+                }
+            };
+            let rename_root = |data_tree: &mut DataTree<OsStringDisplay, Size>| {
+                if !only_one_arg {
+                    assert_eq!(data_tree.name().as_os_str().to_str(), Some(""));
+                    *data_tree.name_mut() = OsStringDisplay::os_string_from("(total)");
+                }
+            };
+            let deduplication_record = if let Some(save_to) = &save_to {
+                let deduplication_record = hardlinks_handler.deduplicate(&mut data_tree);
+                rename_root(&mut data_tree);
+                history::save(save_to, header.as_ref(), &data_tree, !only_one_arg)?;
+                data_tree.par_retain(|_, depth| depth + 1 < max_depth);
+                shape(&mut data_tree);
+                deduplication_record
+            } else {
+                shape(&mut data_tree);
+                let deduplication_record = hardlinks_handler.deduplicate(&mut data_tree);
+                rename_root(&mut data_tree);
+                deduplication_record
+            };
             (data_tree, deduplication_record)
         };
 
+        let largest_files = largest_files.map(LargestFiles::into_sorted_vec);
+        let data_tree = match &largest_files {
+            Some(files) if largest_files_only => {
+                largest_files::into_flat_tree(data_tree.name().clone(), files.clone())
+            }
+            _ => data_tree,
+        };
+
+        let mut data_tree = data_tree;
+        if ls_colors.is_some() && !flat && !largest_files_only && grouping.is_none() {
+            // the colors of the names depend on the kinds of the entries
+            data_tree.par_fill_entry_types(!only_one_arg);
+        }
+
         GLOBAL_STATUS_BOARD.clear_line(0);
 
         if let Some(json_output) = json_output {
             let JsonOutputParam {
                 shared_details,
                 shared_summary,
+                encoding,
             } = json_output;
-            let tree = data_tree
-                .into_reflection() // I really want to use std::mem::transmute here but can't.
-                .par_convert_names_to_utf8() // TODO: allow non-UTF8 somehow.
-                .expect("convert all names from raw string to UTF-8");
+
+            if encoding.format.is_shaped() {
+                ShapedOutput {
+                    encoding,
+                    synthetic_root: !only_one_arg,
+                    table_columns: &table_columns,
+                    table_bytes_format,
+                    svg_layout,
+                    bytes_format,
+                }
+                .write(&data_tree)?;
+                deduplication_record.map_err(HardlinksHandler::convert_error)?;
+                return Ok(());
+            }
+
+            let tree = data_tree.into_reflection();
 
             let deduplication_result = if !shared_details && !shared_summary {
                 Ok(JsonShared::default())
@@ -184,12 +385,11 @@ where
             let json_data = JsonData {
                 schema_version: SchemaVersion,
                 binary_version: Some(BinaryVersion::current()),
+                header,
                 body: json_tree.into(),
             };
 
-            return serde_json::to_writer(stdout(), &json_data)
-                .map_err(RuntimeError::SerializationFailure)
-                .or(deduplication_result);
+            return encoding.write_snapshot(&json_data).or(deduplication_result);
         }
 
         let visualizer = Visualizer {
@@ -198,9 +398,41 @@ where
             direction,
             bar_alignment,
             column_width_distribution,
-        };
+        }
+        .with_options(ChartOptions {
+            columns: &chart_columns,
+            quoting_style,
+            coloring: ls_colors.as_ref().map(|ls_colors| Coloring {
+                bar_coloring,
+                ls_colors,
+                synthetic_root: !only_one_arg,
+            }),
+        });
+
+        if flat {
+            let flat_list = FlatList {
+                data_tree: &data_tree,
+                bytes_format,
+                quoting_style,
+                exclude_ancestors,
+                synthetic_root: !only_one_arg,
+            };
+            print!("{flat_list}");
+        } else if !largest_files_only {
+            print!("{visualizer}"); // visualizer already ends with "\n", println! isn't needed here.
+        }
 
-        print!("{visualizer}"); // visualizer already ends with "\n", println! isn't needed here.
+        if let Some(files) = &largest_files {
+            if !largest_files_only {
+                println!();
+            }
+            let ranked_list = RankedList {
+                files,
+                bytes_format,
+                quoting_style,
+            };
+            print!("{ranked_list}");
+        }
 
         let deduplication_record = deduplication_record.map_err(HardlinksHandler::convert_error)?;
         HardlinksHandler::print_report(deduplication_record, bytes_format)?;
@@ -210,25 +442,28 @@ where
 }
 
 /// Value to pass to [`Sub::json_output`] to decide how much details should be
-/// put in the output JSON object.
+/// put in the output JSON object and how it should be encoded.
 #[derive(Debug, Clone, Copy)]
 pub struct JsonOutputParam {
     /// Whether to include `.shared.details` in the JSON output.
     pub shared_details: bool,
     /// Whether to include `.shared.summary` in the JSON output.
     pub shared_summary: bool,
+    /// How to encode the JSON object.
+    pub encoding: OutputEncoding,
 }
 
 impl JsonOutputParam {
     /// Infer from the CLI flags.
     pub(super) fn from_cli_flags(
-        output_json: bool,
+        encoding: Option<OutputEncoding>,
         omit_shared_details: bool,
         omit_shared_summary: bool,
     ) -> Option<Self> {
-        output_json.then_some(JsonOutputParam {
+        encoding.map(|encoding| JsonOutputParam {
             shared_details: !omit_shared_details,
             shared_summary: !omit_shared_summary,
+            encoding,
         })
     }
 }
@@ -30,6 +30,10 @@ where
     fn json_report(report: Self::Report) -> Result<Option<JsonShared<Size>>, RuntimeError> {
         let summary = report.summarize().pipe(Some);
         let details = report.into_reflection().pipe(Some);
-        Ok(Some(JsonShared { details, summary }))
+        Ok(Some(JsonShared {
+            details,
+            summary,
+            sections: Vec::new(),
+        }))
     }
 }
@@ -1,19 +1,35 @@
+pub mod byte_size;
+pub mod color_mode;
 pub mod depth;
 pub mod fraction;
+pub mod input_format;
+pub mod json_field;
+pub mod output_format;
 pub mod quantity;
 pub mod threads;
 
+pub use byte_size::ByteSize;
+pub use color_mode::ColorMode;
 pub use depth::Depth;
 pub use fraction::Fraction;
+pub use input_format::InputFormat;
+pub use json_field::JsonField;
+pub use output_format::OutputFormat;
 pub use quantity::Quantity;
 pub use threads::Threads;
 
-use crate::bytes_format::BytesFormat;
-use crate::visualizer::ColumnWidthDistribution;
-use clap::{ColorChoice, Parser};
+use crate::bytes_format::{BytesFormat, FixedUnit, Formatter, SuffixStyle};
+use crate::data_tree::{CullThresholds, SortKey};
+use crate::group_by::GroupBy;
+use crate::node_metadata::MetadataFields;
+use crate::svg_chart::SvgLayout;
+use crate::table::Column;
+use crate::visualizer::{BarColoring, ChartColumn, ColumnWidthDistribution, QuotingStyle};
+use clap::error::ErrorKind;
+use clap::{ArgGroup, ColorChoice, CommandFactory, Parser, value_parser};
 use derive_setters::Setters;
 use smart_default::SmartDefault;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use terminal_size::{Width, terminal_size};
 use text_block_macros::text_block;
 
@@ -46,6 +62,22 @@ use text_block_macros::text_block;
         "    $ pdu --min-ratio=0.05"
         "    $ pdu --min-ratio=0 --max-depth=inf --json-output | jq"
         "    $ pdu --json-input < disk-usage.json"
+        "    $ pdu --json-input host1.json host2.json"
+        "    $ pdu --json-input disk-usage.json.zst"
+        "    $ pdu --output-format=cbor --zstd > disk-usage.cbor.zst"
+        "    $ pdu --input-format=cbor disk-usage.cbor.zst"
+        "    $ pdu --json-output --json-fields=count,mtime"
+        "    $ pdu --diff yesterday.json today.json"
+        "    $ pdu --interactive"
+        "    $ pdu --color=always --bar-coloring=gradient | less -R"
+        "    $ pdu --sort=natural-name"
+        "    $ pdu --group-by=extension"
+        "    $ pdu --category-rules=categories.toml"
+        "    $ pdu --largest-files=20 --largest-files-only"
+        "    $ pdu --flat --exclude-ancestors --max-rows=30"
+        ""
+        "    Show the number of files and the last modification time of each entry in the chart"
+        "    $ pdu --columns=size,count,mtime,percent,percent-of-parent"
     },
 
     after_long_help = text_block! {
@@ -77,37 +109,284 @@ use text_block_macros::text_block;
         "    Only show disk usage chart of entries whose size is at least 5% of total"
         "    $ pdu --min-ratio=0.05"
         ""
+        "    Only show disk usage chart of entries whose size is at least 500 megabytes"
+        "    $ pdu --min-ratio=0 --min-size=500M"
+        ""
         "    Show disk usage data as JSON instead of chart"
         "    $ pdu --min-ratio=0 --max-depth=inf --json-output | jq"
         ""
         "    Visualize existing JSON representation of disk usage data"
         "    $ pdu --json-input < disk-usage.json"
+        ""
+        "    Combine multiple JSON snapshots into one chart"
+        "    $ pdu --json-input host1.json host2.json"
+        ""
+        "    Visualize a compressed JSON snapshot"
+        "    $ pdu --json-output | zstd > disk-usage.json.zst"
+        "    $ pdu --json-input disk-usage.json.zst"
+        ""
+        "    Save disk usage data in a compact binary format, then visualize it"
+        "    $ pdu --output-format=cbor --zstd > disk-usage.cbor.zst"
+        "    $ pdu --input-format=cbor disk-usage.cbor.zst"
+        ""
+        "    Export one JSON object per file and directory as the scan goes, for loading into a database"
+        "    $ pdu --output-format=jsonl --min-ratio=0 > disk-usage.jsonl"
+        ""
+        "    Export a spreadsheet of the sizes and percentages of every file and directory"
+        "    $ pdu --output-format=csv --csv-bytes-format=metric --min-ratio=0 > disk-usage.csv"
+        ""
+        "    Create an offline HTML report with an interactive treemap to share with others"
+        "    $ pdu --output-format=html --deduplicate-hardlinks > disk-usage.html"
+        ""
+        "    Draw a static treemap image of the top three levels for a wiki page"
+        "    $ pdu --output-format=svg --svg-layout=treemap --max-depth=3 > disk-usage.svg"
+        ""
+        "    Include the number of files and the latest modification time in the JSON output"
+        "    $ pdu --json-output --json-fields=count,mtime"
+        ""
+        "    Visualize the growth and shrinkage between two JSON snapshots"
+        "    $ pdu --diff yesterday.json today.json"
+        ""
+        "    Record every scan in a history database, then visualize the changes over the last 7 scans"
+        "    $ pdu --save-to history.sqlite"
+        "    $ pdu --growth-report history.sqlite --growth-snapshots 7"
+        ""
+        "    Browse the disk usage interactively, with the option to delete files"
+        "    $ pdu --interactive --allow-delete"
+        ""
+        "    Page through a colored chart whose bars turn red as the entries grow"
+        "    $ pdu --color=always --bar-coloring=gradient | less -R"
+        ""
+        "    Sort the entries by name, treating numbers in the names as numbers"
+        "    $ pdu --sort=natural-name"
+        ""
+        "    Show how much space each file extension takes, with the largest files of each"
+        "    $ pdu --group-by=extension"
+        ""
+        "    Show how much space build artifacts, caches, and media take, as defined in a rules file"
+        "    $ pdu --category-rules=categories.toml"
+        ""
+        "    List the 20 largest files instead of showing the tree"
+        "    $ pdu --largest-files=20 --largest-files-only"
+        ""
+        "    List the full paths of the largest entries that do not contain one another"
+        "    $ pdu --flat --exclude-ancestors --max-rows=30"
     },
 
     color = ColorChoice::Never,
+
+    group = ArgGroup::new("snapshot_output").args(["json_output", "output_format"]),
+    group = ArgGroup::new("snapshot_destination")
+        .args(["json_output", "output_format", "save_to"])
+        .multiple(true),
 )]
 #[setters(prefix = "with_")]
 #[non_exhaustive]
 pub struct Args {
     /// List of files and/or directories.
+    ///
+    /// With --json-input or --input-format, list of data files to read instead.
     pub files: Vec<PathBuf>,
 
-    /// Read JSON data from stdin.
+    /// Read JSON data from the listed files, or from stdin if none is listed.
+    ///
+    /// A file named `-` refers to stdin, and it can be listed only once. Files compressed
+    /// with gzip or zstd are decompressed automatically. Multiple files are combined under a
+    /// synthetic root.
     #[clap(
         long,
-        conflicts_with_all = ["quantity", "deduplicate_hardlinks", "one_file_system"]
+        conflicts_with_all = ["input_format", "quantity", "deduplicate_hardlinks", "one_file_system"]
     )]
     pub json_input: bool,
 
+    /// Read data in the specified format from the listed files, or from stdin if none is listed.
+    ///
+    /// It behaves like --json-input, but also applies to the snapshots of --diff.
+    #[clap(
+        long,
+        value_enum,
+        conflicts_with_all = ["quantity", "deduplicate_hardlinks", "one_file_system"]
+    )]
+    pub input_format: Option<InputFormat>,
+
     /// Print JSON data instead of an ASCII chart.
     #[clap(long)]
     pub json_output: bool,
 
+    /// Print data in the specified format instead of an ASCII chart.
+    #[clap(long, value_enum)]
+    pub output_format: Option<OutputFormat>,
+
+    /// Compress the output of --json-output or --output-format with zstd.
+    #[clap(long, requires = "snapshot_output")]
+    pub zstd: bool,
+
+    /// Include additional fields of each node in the output of --json-output or --output-format.
+    ///
+    /// The fields are written in the "metadata" object of each node.
+    #[clap(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        requires = "snapshot_output",
+        conflicts_with_all = ["json_input", "input_format", "diff"]
+    )]
+    pub json_fields: Vec<JsonField>,
+
+    /// Columns of the output of --output-format=csv or --output-format=tsv.
+    #[clap(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_values_t = Column::ALL.to_vec(),
+        requires = "output_format"
+    )]
+    #[default(_code = "Column::ALL.to_vec()")]
+    pub csv_columns: Vec<Column>,
+
+    /// Format of the sizes in the output of --output-format=csv or --output-format=tsv.
+    ///
+    /// Sizes are written as raw numbers if it is not specified.
+    #[clap(long, value_enum, requires = "output_format")]
+    pub csv_bytes_format: Option<BytesFormat>,
+
+    /// Shape of the image of --output-format=svg.
+    #[clap(long, value_enum, default_value_t = SvgLayout::Treemap, requires = "output_format")]
+    pub svg_layout: SvgLayout,
+
+    /// Compare two JSON snapshots created by --json-output and visualize the changes.
+    #[clap(
+        long,
+        number_of_values = 2,
+        value_names = &["OLD", "NEW"],
+        conflicts_with_all = ["files", "json_input", "quantity", "deduplicate_hardlinks", "one_file_system"]
+    )]
+    pub diff: Option<Vec<PathBuf>>,
+
+    /// Append the data to a SQLite database as a snapshot, in addition to the usual output.
+    ///
+    /// The snapshot records the whole tree as scanned: --max-depth, --min-ratio, --min-size,
+    /// --min-ratio-of-parent, --aggregate-culled, --max-children, and --max-rows only shape the
+    /// usual output. With --json-input or --input-format, the data read from the listed files is
+    /// appended.
+    #[clap(long, value_name = "DATABASE", conflicts_with_all = ["diff", "growth_report"])]
+    pub save_to: Option<PathBuf>,
+
+    /// Record when, where, and how the scan was performed in the output of --json-output,
+    /// --output-format, or --save-to.
+    ///
+    /// The header includes the name of the host and the absolute paths of the scanned files.
+    /// It allows --diff and --growth-report to refuse snapshots of different scans.
+    #[clap(
+        long,
+        requires = "snapshot_destination",
+        conflicts_with_all = ["json_input", "input_format"]
+    )]
+    pub scan_header: bool,
+
+    /// Visualize the changes over the most recent snapshots in a SQLite database created by --save-to.
+    #[clap(
+        long,
+        value_name = "DATABASE",
+        conflicts_with_all = ["files", "json_input", "input_format", "diff", "quantity", "deduplicate_hardlinks", "one_file_system"]
+    )]
+    pub growth_report: Option<PathBuf>,
+
+    /// Number of the most recent snapshots to be spanned by --growth-report.
+    #[clap(
+        long,
+        value_name = "N",
+        default_value_t = 2,
+        value_parser = value_parser!(u64).range(2..),
+        requires = "growth_report"
+    )]
+    #[default(2)]
+    pub growth_snapshots: u64,
+
+    /// Browse the data in a full-screen terminal interface instead of printing a chart.
+    ///
+    /// It ignores --max-depth and --min-ratio. With --json-input or --input-format, the
+    /// data is read-only and the quantity cannot be changed.
+    #[clap(
+        long,
+        conflicts_with_all = ["snapshot_output", "diff", "growth_report", "save_to"]
+    )]
+    pub interactive: bool,
+
+    /// Allow deleting files and directories from the filesystem in --interactive mode.
+    ///
+    /// Every deletion must be confirmed.
+    #[clap(
+        long,
+        requires = "interactive",
+        conflicts_with_all = ["json_input", "input_format"]
+    )]
+    pub allow_delete: bool,
+
+    /// Show the total sizes of categories of entries instead of the directory tree.
+    ///
+    /// Each category lists its largest paths, up to 10 unless --max-children is given.
+    /// The size of each entry excludes its descendants.
+    #[clap(
+        long,
+        value_enum,
+        conflicts_with_all = ["json_input", "input_format", "diff", "growth_report", "save_to", "interactive", "deduplicate_hardlinks"]
+    )]
+    pub group_by: Option<GroupBy>,
+
+    /// Show the total sizes of the categories defined by a TOML file of glob patterns instead of the directory tree.
+    ///
+    /// Each category lists the matched paths that contribute the most to it, up to 10 unless
+    /// --max-children is given. The file consists of [[category]] tables, each with a name
+    /// and a list of globs, and an entry belongs to the first category with a glob that
+    /// matches its path or the path of one of its ancestors.
+    #[clap(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["group_by", "json_input", "input_format", "diff", "growth_report", "save_to", "interactive", "deduplicate_hardlinks"]
+    )]
+    pub category_rules: Option<PathBuf>,
+
+    /// List the N largest regular files after the chart, from the largest.
+    ///
+    /// The files are picked during the scan, so the memory usage does not depend on the
+    /// size of the tree. With --json-output or --output-format, it requires
+    /// --largest-files-only.
+    #[clap(
+        long,
+        value_name = "N",
+        conflicts_with_all = ["json_input", "input_format", "diff", "growth_report", "interactive"]
+    )]
+    pub largest_files: Option<usize>,
+
+    /// Only list the largest files of --largest-files instead of showing the tree.
+    ///
+    /// With --json-output or --output-format, the files are the children of the root. The
+    /// tree is not shown, so --deduplicate-hardlinks has nothing to report and is rejected.
+    #[clap(
+        long,
+        requires = "largest_files",
+        conflicts_with_all = ["group_by", "category_rules", "save_to", "deduplicate_hardlinks"]
+    )]
+    pub largest_files_only: bool,
+
     /// How to display the numbers of bytes.
     #[clap(long, short, value_enum, default_value_t = BytesFormat::MetricUnits)]
     #[default(BytesFormat::MetricUnits)]
     pub bytes_format: BytesFormat,
 
+    /// Display every number of bytes in the same unit instead of picking one for each value.
+    #[clap(long, value_enum, ignore_case = true, conflicts_with = "bytes_format")]
+    pub unit: Option<FixedUnit>,
+
+    /// Number of digits after the decimal point of the numbers of bytes [default: 1].
+    #[clap(long)]
+    pub precision: Option<usize>,
+
+    /// How to write the units of the numbers of bytes [default: short].
+    #[clap(long, value_enum)]
+    pub suffix_style: Option<SuffixStyle>,
+
     /// Detect and subtract the sizes of hardlinks from their parent directory totals.
     #[clap(long, short = 'H', visible_aliases = ["detect-links", "dedupe-links"])]
     #[cfg_attr(not(unix), clap(hide = true))]
@@ -118,6 +397,22 @@ pub struct Args {
     #[cfg_attr(not(unix), clap(hide = true))]
     pub one_file_system: bool,
 
+    /// List the entries from the largest with their full paths instead of drawing the tree.
+    ///
+    /// The list consists of the entries that would appear in the chart, so it is shaped by
+    /// --max-depth, --min-ratio, --max-rows, and similar options.
+    #[clap(
+        long,
+        conflicts_with_all = ["snapshot_output", "interactive", "diff", "growth_report", "largest_files_only"]
+    )]
+    pub flat: bool,
+
+    /// Omit the entries of --flat that contain other listed entries.
+    ///
+    /// This prevents the list from being dominated by the ancestors of the large entries.
+    #[clap(long, requires = "flat")]
+    pub exclude_ancestors: bool,
+
     /// Print the tree top-down instead of bottom-up.
     #[clap(long)]
     pub top_down: bool,
@@ -126,6 +421,37 @@ pub struct Args {
     #[clap(long)]
     pub align_right: bool,
 
+    /// Columns of the chart besides the tree and the bars.
+    ///
+    /// The percentages are placed after the bars, the other columns before the tree.
+    #[clap(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_values_t = ChartColumn::DEFAULT.to_vec()
+    )]
+    #[default(_code = "ChartColumn::DEFAULT.to_vec()")]
+    pub columns: Vec<ChartColumn>,
+
+    /// How to write the names of the entries in the chart.
+    ///
+    /// Control characters, terminal escape sequences, and bidirectional overrides in the names
+    /// are escaped by default so that they cannot corrupt or spoof the chart.
+    #[clap(long, value_enum, default_value_t = QuotingStyle::Escape)]
+    pub quoting_style: QuotingStyle,
+
+    /// When to color the chart with ANSI escape sequences.
+    ///
+    /// The names are colored by file type according to `LS_COLORS`. The largest entry amongst
+    /// its siblings is highlighted, synthetic entries and directories whose contents are not
+    /// shown are dimmed.
+    #[clap(long, value_enum, default_value_t = ColorMode::Auto)]
+    pub color: ColorMode,
+
+    /// How to color the bars of the chart when --color is in effect.
+    #[clap(long, value_enum, default_value_t = BarColoring::Depth)]
+    pub bar_coloring: BarColoring,
+
     /// Aspect of the files/directories to be measured.
     #[clap(long, short, value_enum, default_value_t = Quantity::DEFAULT)]
     #[default(Quantity::DEFAULT)]
@@ -153,6 +479,43 @@ pub struct Args {
     #[clap(long, short, default_value = "0.01")]
     pub min_ratio: Fraction,
 
+    /// Minimal size required to appear, such as `500M`, `1.5GiB`, or `4096`.
+    #[clap(long)]
+    pub min_size: Option<ByteSize>,
+
+    /// Minimal size proportion relative to the parent required to appear.
+    #[clap(long, default_value = "0")]
+    pub min_ratio_of_parent: Fraction,
+
+    /// Replace the entries hidden by the size thresholds with a single entry per directory.
+    ///
+    /// The new entry is named after the number of the entries it replaces, such as
+    /// "(1,234 smaller items)", and carries their combined size. The chart marks it with "…".
+    #[clap(long)]
+    pub aggregate_culled: bool,
+
+    /// Maximum number of children to display for each directory, the largest ones are kept.
+    ///
+    /// With --group-by or --category-rules, it limits the paths listed under each category
+    /// instead, and every category is displayed.
+    #[clap(long)]
+    pub max_children: Option<usize>,
+
+    /// Maximum number of rows of the chart, the largest entries and their ancestors are kept.
+    #[clap(long)]
+    pub max_rows: Option<usize>,
+
+    /// Property by which the branches in the tree are sorted.
+    ///
+    /// Sizes, counts, and modification times put the largest, the most, and the newest first,
+    /// while names are sorted in ascending order.
+    #[clap(long, value_enum, default_value_t = SortKey::Size, conflicts_with = "no_sort")]
+    pub sort: SortKey,
+
+    /// Reverse the order of the branches in the tree.
+    #[clap(long, conflicts_with = "no_sort")]
+    pub reverse: bool,
+
     /// Do not sort the branches in the tree.
     #[clap(long)]
     pub no_sort: bool,
@@ -170,15 +533,172 @@ pub struct Args {
     pub threads: Threads,
 
     /// Do not output `.shared.details` in the JSON output.
-    #[clap(long, requires = "json_output", requires = "deduplicate_hardlinks")]
+    #[clap(long, requires = "snapshot_output", requires = "deduplicate_hardlinks")]
     pub omit_json_shared_details: bool,
 
     /// Do not output `.shared.summary` in the JSON output.
-    #[clap(long, requires = "json_output", requires = "deduplicate_hardlinks")]
+    #[clap(long, requires = "snapshot_output", requires = "deduplicate_hardlinks")]
     pub omit_json_shared_summary: bool,
 }
 
 impl Args {
+    /// Reject the combinations of arguments that cannot be expressed by the attributes of clap.
+    ///
+    /// Clap cannot declare a conflict with a specific value of an argument, such as the
+    /// value of `--output-format` that cannot represent the data of `--diff`.
+    pub fn validate(&self) -> Result<(), clap::Error> {
+        self.validate_bytes_format()?;
+        self.validate_comparison()?;
+        self.validate_largest_files()?;
+        self.validate_stdin()
+    }
+
+    /// Reject the unit settings that `--bytes-format=plain` has no units to apply to.
+    fn validate_bytes_format(&self) -> Result<(), clap::Error> {
+        if self.unit.is_some() || self.bytes_format.formatter().is_some() {
+            return Ok(());
+        }
+        let setting = match (self.precision, self.suffix_style) {
+            (Some(_), _) => "--precision",
+            (None, Some(_)) => "--suffix-style",
+            (None, None) => return Ok(()),
+        };
+        Err(Args::command().error(
+            ErrorKind::ArgumentConflict,
+            format!("{setting} cannot be used with --bytes-format=plain"),
+        ))
+    }
+
+    /// Reject the values of `--output-format` that cannot represent a comparison.
+    fn validate_comparison(&self) -> Result<(), clap::Error> {
+        let comparison = match (&self.diff, &self.growth_report) {
+            (Some(_), _) => "--diff",
+            (None, Some(_)) => "--growth-report",
+            (None, None) => return Ok(()),
+        };
+        match self.output_format {
+            Some(format) if !format.supports_diff() => Err(Args::command().error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "--output-format={} cannot be used with {comparison}",
+                    format.name(),
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject `--largest-files` with the output formats that have no room for the list,
+    /// unless `--largest-files-only` puts the files in the tree.
+    fn validate_largest_files(&self) -> Result<(), clap::Error> {
+        if self.largest_files.is_none() || self.largest_files_only {
+            return Ok(());
+        }
+        let output = match (self.json_output, self.output_format) {
+            (true, _) => "--json-output".to_string(),
+            (false, Some(format)) => format!("--output-format={}", format.name()),
+            (false, None) => return Ok(()),
+        };
+        Err(Args::command().error(
+            ErrorKind::ArgumentConflict,
+            format!("--largest-files cannot be used with {output} without --largest-files-only"),
+        ))
+    }
+
+    /// Reject the inputs that name stdin more than once, as it can only be read once.
+    fn validate_stdin(&self) -> Result<(), clap::Error> {
+        let inputs = match (&self.diff, self.input_format()) {
+            (Some(snapshots), _) => snapshots,
+            (None, Some(_)) => &self.files,
+            (None, None) => return Ok(()),
+        };
+        let stdin = inputs.iter().filter(|path| *path == Path::new("-")).count();
+        if stdin < 2 {
+            return Ok(());
+        }
+        Err(Args::command().error(
+            ErrorKind::ArgumentConflict,
+            "- (stdin) cannot be given more than once",
+        ))
+    }
+
+    /// Deduce [`InputFormat`] from `--json-input` or `--input-format`.
+    pub(crate) fn input_format(&self) -> Option<InputFormat> {
+        if self.json_input {
+            Some(InputFormat::Json)
+        } else {
+            self.input_format
+        }
+    }
+
+    /// Deduce [`OutputFormat`] from `--json-output` or `--output-format`.
+    pub(crate) fn output_format(&self) -> Option<OutputFormat> {
+        if self.json_output {
+            Some(OutputFormat::Json)
+        } else {
+            self.output_format
+        }
+    }
+
+    /// Deduce [`BytesFormat`] from `--bytes-format`, `--unit`, `--precision`, and `--suffix-style`.
+    pub(crate) fn bytes_format(&self) -> BytesFormat {
+        let mut formatter = match (self.unit, self.bytes_format.formatter()) {
+            (Some(unit), _) => Formatter::fixed(unit),
+            (None, Some(formatter)) => formatter,
+            (None, None) => return self.bytes_format,
+        };
+        if let Some(precision) = self.precision {
+            formatter = formatter.with_precision(precision);
+        }
+        if let Some(suffix_style) = self.suffix_style {
+            formatter = formatter.with_suffix_style(suffix_style);
+        }
+        BytesFormat::Custom(formatter)
+    }
+
+    /// Deduce [`CullThresholds`] from `--min-ratio`, `--min-ratio-of-parent`, and `--min-size`.
+    pub(crate) fn cull_thresholds(&self) -> CullThresholds {
+        CullThresholds {
+            min_ratio: self.min_ratio.into(),
+            min_ratio_of_parent: self.min_ratio_of_parent.into(),
+            min_size: self.min_size.map_or(0, ByteSize::get),
+        }
+    }
+
+    /// Whether the JSON Lines output can be written while scanning, which requires that no
+    /// option depends on the whole tree.
+    pub(crate) fn streams_json_lines(&self) -> bool {
+        let CullThresholds {
+            min_ratio,
+            min_ratio_of_parent,
+            min_size: _,
+        } = self.cull_thresholds();
+        self.output_format() == Some(OutputFormat::Jsonl)
+            && min_ratio == 0.0
+            && min_ratio_of_parent == 0.0
+            && !self.aggregate_culled
+            && self.max_children.is_none()
+            && self.max_rows.is_none()
+            && !self.deduplicate_hardlinks
+            && self.save_to.is_none()
+            && self.group_by.is_none()
+            && self.category_rules.is_none()
+            && !self.largest_files_only
+    }
+
+    /// Deduce [`MetadataFields`] from `--json-fields` and `--output-format`.
+    ///
+    /// The JSON Lines output always describes the kind of each entry.
+    pub(crate) fn metadata_fields(&self) -> MetadataFields {
+        let fields = MetadataFields {
+            entry_type: self.output_format() == Some(OutputFormat::Jsonl),
+            ..MetadataFields::NONE
+        };
+        self.json_fields
+            .iter()
+            .fold(fields, |fields, field| field.select(fields))
+    }
+
     /// Deduce [`ColumnWidthDistribution`] from `--total-width` or `--column-width`.
     pub(crate) fn column_width_distribution(&self) -> ColumnWidthDistribution {
         match (self.total_width, self.column_width.as_deref()) {
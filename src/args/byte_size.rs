@@ -0,0 +1,52 @@
+use crate::bytes_format::FixedUnit;
+use clap::ValueEnum;
+use derive_more::{Display, Error, Into};
+use std::num::ParseFloatError;
+use std::str::FromStr;
+
+/// Quantity of bytes, written as a number that is optionally followed by a unit.
+///
+/// The units are the same as the ones of `--unit`, case-insensitive: `K`, `KB`, `M`, `MB`, ...
+/// are in metric scale, `KiB`, `MiB`, ... are in binary scale. A number without a unit (or
+/// followed by `B`) is a number of bytes.
+#[derive(Debug, Display, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Into)]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    /// Get the number of bytes.
+    #[inline]
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+}
+
+/// Error that occurs when parsing a string as [`ByteSize`].
+#[derive(Debug, Display, Error, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FromStrError {
+    #[display("Value is not a non-negative number followed by an optional unit: {_0}")]
+    InvalidNumber(ParseFloatError),
+    #[display("Unknown unit: {_0:?}")]
+    UnknownUnit(#[error(not(source))] String),
+}
+
+impl FromStr for ByteSize {
+    type Err = FromStrError;
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let unit_start = text
+            .find(|char: char| !char.is_ascii_digit() && char != '.')
+            .unwrap_or(text.len());
+        let (number, unit) = text.split_at(unit_start);
+        let number: f64 = number.parse().map_err(FromStrError::InvalidNumber)?;
+        let scale = match unit.trim_start() {
+            "" | "B" | "b" => 1,
+            unit => {
+                let unit = FixedUnit::from_str(unit, true)
+                    .map_err(|_| FromStrError::UnknownUnit(unit.to_string()))?;
+                unit.scale_base().pow(unit.exponent())
+            }
+        };
+        Ok(ByteSize((number * scale as f64).round() as u64))
+    }
+}
@@ -0,0 +1,32 @@
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+use std::env;
+use std::io::{IsTerminal, stdout};
+
+/// When to color the chart.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum ColorMode {
+    /// Color the chart if the output is a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    /// Always color the chart.
+    Always,
+    /// Never color the chart.
+    Never,
+}
+
+impl ColorMode {
+    /// Decide whether the chart printed to stdout should be colored.
+    pub(crate) fn enabled(self) -> bool {
+        match self {
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+            ColorMode::Auto => {
+                let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                let dumb_terminal = env::var_os("TERM").is_some_and(|term| term == "dumb");
+                !no_color && !dumb_terminal && stdout().is_terminal()
+            }
+        }
+    }
+}
@@ -0,0 +1,12 @@
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// Format of the data to be read instead of scanning the filesystem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum InputFormat {
+    /// JSON, the same as `--json-input`.
+    Json,
+    /// CBOR, a compact binary encoding of the same data as JSON.
+    Cbor,
+}
@@ -0,0 +1,48 @@
+use crate::node_metadata::MetadataFields;
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// Additional field of each node in the JSON output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum JsonField {
+    /// Kind of the entry: file, dir, symlink, or other.
+    Type,
+    /// Number of entries other than directories in the subtree.
+    Count,
+    /// Newest modification time in the subtree, in seconds since the Unix epoch.
+    Mtime,
+    /// User ID and group ID of the owner (POSIX only).
+    Owner,
+    /// Inode number and device number (POSIX only).
+    Inode,
+}
+
+impl JsonField {
+    /// Add this field to the selection.
+    pub fn select(self, fields: MetadataFields) -> MetadataFields {
+        match self {
+            JsonField::Type => MetadataFields {
+                entry_type: true,
+                ..fields
+            },
+            JsonField::Count => MetadataFields {
+                count: true,
+                ..fields
+            },
+            JsonField::Mtime => MetadataFields {
+                mtime: true,
+                ..fields
+            },
+            JsonField::Owner => MetadataFields {
+                owner: true,
+                ..fields
+            },
+            JsonField::Inode => MetadataFields {
+                inode: true,
+                ..fields
+            },
+        }
+    }
+}
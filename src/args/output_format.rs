@@ -0,0 +1,75 @@
+use crate::table::Delimiter;
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// Format of the data printed instead of the ASCII chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum OutputFormat {
+    /// JSON, the same as `--json-output`.
+    Json,
+    /// JSON Lines, one object per node with its full path, depth, size, parent, and kind.
+    ///
+    /// The children of a node are written before the node itself. The lines are written as soon
+    /// as their subtrees are scanned, unless an option needs the whole tree first, such as a
+    /// non-zero --min-ratio, --max-rows, or --deduplicate-hardlinks.
+    Jsonl,
+    /// CBOR, a compact binary encoding of the same data as JSON.
+    Cbor,
+    /// Comma-separated values, one row per node.
+    Csv,
+    /// Tab-separated values, one row per node.
+    Tsv,
+    /// A standalone HTML page with an interactive treemap, a sortable table, and the hardlinks summary.
+    Html,
+    /// A static SVG image of the tree, shaped by --svg-layout, --max-depth, and --min-ratio.
+    Svg,
+}
+
+impl OutputFormat {
+    /// Name of the format as it appears in the CLI.
+    pub const fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Jsonl => "jsonl",
+            OutputFormat::Cbor => "cbor",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Tsv => "tsv",
+            OutputFormat::Html => "html",
+            OutputFormat::Svg => "svg",
+        }
+    }
+
+    /// Whether the format describes each node separately instead of as a nested document.
+    pub const fn is_flat(self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Jsonl | OutputFormat::Csv | OutputFormat::Tsv
+        )
+    }
+
+    /// Whether the format can represent the difference of `--diff` and `--growth-report`.
+    pub const fn supports_diff(self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Cbor)
+    }
+
+    /// Whether the output is written from the tree shaped by --max-depth and --min-ratio
+    /// instead of serialized as a whole.
+    pub const fn is_shaped(self) -> bool {
+        self.is_flat() || matches!(self, OutputFormat::Svg)
+    }
+
+    /// The delimiter of the table if the format is tabular.
+    pub const fn table_delimiter(self) -> Option<Delimiter> {
+        match self {
+            OutputFormat::Csv => Some(Delimiter::Comma),
+            OutputFormat::Tsv => Some(Delimiter::Tab),
+            OutputFormat::Json
+            | OutputFormat::Jsonl
+            | OutputFormat::Cbor
+            | OutputFormat::Html
+            | OutputFormat::Svg => None,
+        }
+    }
+}
@@ -25,4 +25,15 @@ impl Quantity {
     /// Default value of the `--quantity` flag.
     #[cfg(not(unix))]
     pub(crate) const DEFAULT: Self = Quantity::ApparentSize;
+
+    /// Name of the quantity, as it appears in the `--quantity` flag.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Quantity::ApparentSize => "apparent-size",
+            #[cfg(unix)]
+            Quantity::BlockSize => "block-size",
+            #[cfg(unix)]
+            Quantity::BlockCount => "block-count",
+        }
+    }
 }
@@ -0,0 +1,208 @@
+pub mod order;
+
+pub use order::Order;
+
+use crate::data_tree::DataTree;
+use crate::hardlink::HardlinkListReflection;
+use crate::hardlink::hardlink_list::reflection::ReflectionEntry;
+use crate::os_string_display::OsStringDisplay;
+use crate::size;
+use std::path::PathBuf;
+
+/// State of an interactive navigation through a [`DataTree`].
+///
+/// It keeps track of the directory being viewed, the selected entry, and the order of
+/// the entries. It does not draw anything by itself, which is left to the user interface.
+#[derive(Debug)]
+pub struct Browser<Size: size::Size> {
+    /// The tree being navigated.
+    tree: DataTree<OsStringDisplay, Size>,
+    /// Whether the root of [`tree`](Self::tree) does not represent a real entry.
+    synthetic_root: bool,
+    /// Names of the nodes from a child of the root to the directory being viewed.
+    location: Vec<OsStringDisplay>,
+    /// Index of the selected entry in [`entries`](Self::entries).
+    cursor: usize,
+    /// Order of the entries.
+    order: Order,
+    /// Detected hardlinks.
+    hardlinks: Option<HardlinkListReflection<Size>>,
+}
+
+impl<Size: size::Size> Browser<Size> {
+    /// Start navigating from the root of `tree`.
+    ///
+    /// If `synthetic_root` is `true`, the root of `tree` does not represent a real entry,
+    /// so its children are treated as independent paths.
+    pub fn new(tree: DataTree<OsStringDisplay, Size>, synthetic_root: bool) -> Self {
+        Browser {
+            tree,
+            synthetic_root,
+            location: Vec::new(),
+            cursor: 0,
+            order: Order::default(),
+            hardlinks: None,
+        }
+    }
+
+    /// Attach the detected hardlinks, which are looked up by [`Self::selected_hardlinks`].
+    pub fn with_hardlinks(mut self, hardlinks: Option<HardlinkListReflection<Size>>) -> Self {
+        self.hardlinks = hardlinks;
+        self
+    }
+
+    /// The root of the tree.
+    pub fn root(&self) -> &DataTree<OsStringDisplay, Size> {
+        &self.tree
+    }
+
+    /// Names of the nodes from a child of the root to the directory being viewed.
+    pub fn location(&self) -> &[OsStringDisplay] {
+        &self.location
+    }
+
+    /// The current order of the entries.
+    pub fn order(&self) -> Order {
+        self.order
+    }
+
+    /// Index of the selected entry in [`Self::entries`].
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The directory being viewed.
+    pub fn directory(&self) -> &DataTree<OsStringDisplay, Size> {
+        self.location.iter().fold(&self.tree, |node, name| {
+            node.children()
+                .iter()
+                .find(|child| child.name() == name)
+                .expect("location should only lead to existing nodes")
+        })
+    }
+
+    /// Path of the directory being viewed.
+    ///
+    /// It is empty when viewing a synthetic root.
+    pub fn directory_path(&self) -> PathBuf {
+        let mut path = PathBuf::new();
+        if !self.synthetic_root {
+            path.push(self.tree.name().as_os_str());
+        }
+        for name in &self.location {
+            path.push(name.as_os_str());
+        }
+        path
+    }
+
+    /// Entries of the directory being viewed, in the current order.
+    pub fn entries(&self) -> Vec<&DataTree<OsStringDisplay, Size>> {
+        let mut entries: Vec<_> = self.directory().children().iter().collect();
+        entries.sort_by(|left, right| self.order.compare(left, right));
+        entries
+    }
+
+    /// The selected entry.
+    pub fn selected(&self) -> Option<&DataTree<OsStringDisplay, Size>> {
+        self.entries().get(self.cursor).copied()
+    }
+
+    /// Path of the selected entry.
+    pub fn selected_path(&self) -> Option<PathBuf> {
+        let name = self.selected()?.name().as_os_str();
+        Some(self.directory_path().join(name))
+    }
+
+    /// Move the selection by `offset` entries, stopping at the first and the last entry.
+    pub fn move_cursor(&mut self, offset: isize) {
+        let last = self.entries().len().saturating_sub(1);
+        self.cursor = self.cursor.saturating_add_signed(offset).min(last);
+    }
+
+    /// Select the first entry.
+    pub fn move_to_first(&mut self) {
+        self.cursor = 0;
+    }
+
+    /// Select the last entry.
+    pub fn move_to_last(&mut self) {
+        self.cursor = self.entries().len().saturating_sub(1);
+    }
+
+    /// View the selected entry if it has any children.
+    ///
+    /// Return whether the view changed.
+    pub fn enter(&mut self) -> bool {
+        let Some(selected) = self.selected() else {
+            return false;
+        };
+        if selected.children().is_empty() {
+            return false;
+        }
+        let name = selected.name().clone();
+        self.location.push(name);
+        self.cursor = 0;
+        true
+    }
+
+    /// View the parent of the directory being viewed, selecting the directory that was left.
+    ///
+    /// Return whether the view changed.
+    pub fn leave(&mut self) -> bool {
+        let Some(name) = self.location.pop() else {
+            return false;
+        };
+        self.select_by_name(&name);
+        true
+    }
+
+    /// Switch to the other [`Order`] while keeping the same entry selected.
+    pub fn toggle_order(&mut self) {
+        let selected = self.selected().map(|entry| entry.name().clone());
+        self.order = self.order.toggle();
+        if let Some(name) = selected {
+            self.select_by_name(&name);
+        }
+    }
+
+    /// Follow `location` from the root as far as it leads, such as to restore the view of
+    /// another [`Browser`] of the same paths.
+    pub fn navigate(&mut self, location: &[OsStringDisplay]) {
+        self.location.clear();
+        self.cursor = 0;
+        for name in location {
+            self.select_by_name(name);
+            if self.selected().is_none_or(|entry| entry.name() != name) || !self.enter() {
+                return;
+            }
+        }
+    }
+
+    /// Detach the selected entry from the tree and subtract its size from its ancestors.
+    ///
+    /// This only changes the tree, removing the entry from the filesystem is up to the caller.
+    pub fn remove_selected(&mut self) -> Option<DataTree<OsStringDisplay, Size>> {
+        let name = self.selected()?.name().clone();
+        let mut path = self.location.clone();
+        path.push(name);
+        let removed = self.tree.remove_descendant(&path)?;
+        self.move_cursor(0);
+        Some(removed)
+    }
+
+    /// Find the record of the hardlinks of the selected entry.
+    pub fn selected_hardlinks(&self) -> Option<&ReflectionEntry<Size>> {
+        let path = self.selected_path()?;
+        self.hardlinks
+            .as_ref()?
+            .iter()
+            .find(|entry| entry.paths.0.contains(&path))
+    }
+
+    /// Select the entry named `name` if it exists.
+    fn select_by_name(&mut self, name: &OsStringDisplay) {
+        if let Some(index) = self.entries().iter().position(|entry| entry.name() == name) {
+            self.cursor = index;
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::data_tree::DataTree;
+use crate::os_string_display::OsStringDisplay;
+use crate::size;
+use std::cmp::Ordering;
+
+/// Order of the entries of a directory in a [`Browser`](super::Browser).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    /// Largest entries first.
+    #[default]
+    Size,
+    /// Alphabetical order of the names.
+    Name,
+}
+
+impl Order {
+    /// The other order.
+    pub const fn toggle(self) -> Self {
+        match self {
+            Order::Size => Order::Name,
+            Order::Name => Order::Size,
+        }
+    }
+
+    /// Compare two entries.
+    pub fn compare<Size: size::Size>(
+        self,
+        left: &DataTree<OsStringDisplay, Size>,
+        right: &DataTree<OsStringDisplay, Size>,
+    ) -> Ordering {
+        match self {
+            Order::Size => left
+                .size()
+                .cmp(&right.size())
+                .reverse()
+                .then_with(|| left.name().cmp(right.name())),
+            Order::Name => left.name().cmp(right.name()),
+        }
+    }
+}
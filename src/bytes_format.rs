@@ -1,11 +1,15 @@
+pub mod fixed_unit;
 pub mod formatter;
 pub mod output;
 pub mod parsed_value;
 pub mod scale_base;
+pub mod suffix_style;
 
+pub use fixed_unit::FixedUnit;
 pub use formatter::Formatter;
 pub use output::Output;
 pub use parsed_value::ParsedValue;
+pub use suffix_style::SuffixStyle;
 
 use pipe_trait::Pipe;
 
@@ -15,6 +19,7 @@ use clap::ValueEnum;
 /// The [`DisplayFormat`](crate::size::Size::DisplayFormat) type of [`Bytes`](crate::size::Bytes).
 #[derive(Debug, Clone, Copy)]
 #[cfg_attr(feature = "cli", derive(ValueEnum))]
+#[non_exhaustive]
 pub enum BytesFormat {
     /// Display the value as-is.
     #[cfg_attr(
@@ -46,17 +51,29 @@ pub enum BytesFormat {
         )
     )]
     BinaryUnits,
+    /// Display the value with a unit suffix as configured by a [`Formatter`].
+    #[cfg_attr(feature = "cli", clap(skip))]
+    Custom(Formatter),
 }
 
 impl BytesFormat {
-    /// Format a quantity of bytes according to the settings.
-    pub fn format(self, bytes: u64) -> Output {
+    /// Get the [`Formatter`] behind the unit-suffixed formats.
+    pub const fn formatter(self) -> Option<Formatter> {
         use BytesFormat::*;
         use formatter::{BINARY, METRIC};
         match self {
-            PlainNumber => Output::PlainNumber(bytes),
-            MetricUnits => METRIC.parse_value(bytes).pipe(Output::Units),
-            BinaryUnits => BINARY.parse_value(bytes).pipe(Output::Units),
+            PlainNumber => None,
+            MetricUnits => Some(METRIC),
+            BinaryUnits => Some(BINARY),
+            Custom(formatter) => Some(formatter),
+        }
+    }
+
+    /// Format a quantity of bytes according to the settings.
+    pub fn format(self, bytes: u64) -> Output {
+        match self.formatter() {
+            None => Output::PlainNumber(bytes),
+            Some(formatter) => formatter.parse_value(bytes).pipe(Output::Units),
         }
     }
 }
@@ -0,0 +1,63 @@
+use super::scale_base;
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// Unit to display every quantity of bytes in, regardless of its magnitude.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum FixedUnit {
+    /// 1KB = 1000B.
+    #[cfg_attr(feature = "cli", clap(name = "KB", alias = "K"))]
+    Kilo,
+    /// 1MB = 1000KB.
+    #[cfg_attr(feature = "cli", clap(name = "MB", alias = "M"))]
+    Mega,
+    /// 1GB = 1000MB.
+    #[cfg_attr(feature = "cli", clap(name = "GB", alias = "G"))]
+    Giga,
+    /// 1TB = 1000GB.
+    #[cfg_attr(feature = "cli", clap(name = "TB", alias = "T"))]
+    Tera,
+    /// 1PB = 1000TB.
+    #[cfg_attr(feature = "cli", clap(name = "PB", alias = "P"))]
+    Peta,
+    /// 1KiB = 1024B.
+    #[cfg_attr(feature = "cli", clap(name = "KiB"))]
+    Kibi,
+    /// 1MiB = 1024KiB.
+    #[cfg_attr(feature = "cli", clap(name = "MiB"))]
+    Mebi,
+    /// 1GiB = 1024MiB.
+    #[cfg_attr(feature = "cli", clap(name = "GiB"))]
+    Gibi,
+    /// 1TiB = 1024GiB.
+    #[cfg_attr(feature = "cli", clap(name = "TiB"))]
+    Tebi,
+    /// 1PiB = 1024TiB.
+    #[cfg_attr(feature = "cli", clap(name = "PiB"))]
+    Pebi,
+}
+
+impl FixedUnit {
+    /// Multiplication factor of the system the unit belongs to.
+    pub const fn scale_base(self) -> u64 {
+        use FixedUnit::*;
+        match self {
+            Kilo | Mega | Giga | Tera | Peta => scale_base::METRIC,
+            Kibi | Mebi | Gibi | Tebi | Pebi => scale_base::BINARY,
+        }
+    }
+
+    /// Exponent of [`scale_base`](FixedUnit::scale_base) that makes up the unit.
+    pub const fn exponent(self) -> u32 {
+        use FixedUnit::*;
+        match self {
+            Kilo | Kibi => 1,
+            Mega | Mebi => 2,
+            Giga | Gibi => 3,
+            Tera | Tebi => 4,
+            Peta | Pebi => 5,
+        }
+    }
+}
@@ -1,17 +1,55 @@
-use super::{ParsedValue, scale_base};
+use super::{FixedUnit, ParsedValue, SuffixStyle, scale_base};
 use std::fmt::Debug;
 
+/// Prefix letters of the units, indexed by exponent minus 1.
+const UNITS: [char; 5] = ['K', 'M', 'G', 'T', 'P'];
+
 /// Format a quantity of bytes.
 #[derive(Debug, Clone, Copy)]
 pub struct Formatter {
     scale_base: u64,
+    exponent: Option<u32>,
+    precision: usize,
+    suffix_style: SuffixStyle,
 }
 
 impl Formatter {
     /// Create a new formatter.
+    ///
+    /// The unit is picked according to the magnitude of each value, the coefficient is written
+    /// with 1 digit after the decimal point, and the suffix is [`SuffixStyle::Short`].
     #[inline]
     pub const fn new(scale_base: u64) -> Self {
-        Formatter { scale_base }
+        Formatter {
+            scale_base,
+            exponent: None,
+            precision: 1,
+            suffix_style: SuffixStyle::Short,
+        }
+    }
+
+    /// Create a new formatter that writes every value in `unit`.
+    #[inline]
+    pub const fn fixed(unit: FixedUnit) -> Self {
+        Formatter {
+            exponent: Some(unit.exponent()),
+            ..Formatter::new(unit.scale_base())
+        }
+    }
+
+    /// Set the number of digits after the decimal point.
+    #[inline]
+    pub const fn with_precision(self, precision: usize) -> Self {
+        Formatter { precision, ..self }
+    }
+
+    /// Set how to write the unit suffixes.
+    #[inline]
+    pub const fn with_suffix_style(self, suffix_style: SuffixStyle) -> Self {
+        Formatter {
+            suffix_style,
+            ..self
+        }
     }
 
     /// Multiplication factor.
@@ -20,6 +58,24 @@ impl Formatter {
         self.scale_base
     }
 
+    /// Exponent of the unit of every value, if the formatter was created by [`Formatter::fixed`].
+    #[inline]
+    pub const fn exponent(self) -> Option<u32> {
+        self.exponent
+    }
+
+    /// Number of digits after the decimal point.
+    #[inline]
+    pub const fn precision(self) -> usize {
+        self.precision
+    }
+
+    /// How the unit suffixes are written.
+    #[inline]
+    pub const fn suffix_style(self) -> SuffixStyle {
+        self.suffix_style
+    }
+
     /// Get scale in number.
     #[inline]
     pub const fn scale(self, exp: u32) -> u64 {
@@ -27,29 +83,37 @@ impl Formatter {
     }
 
     /// Parse a value according to the prefixing rule.
+    ///
+    /// If the formatter was created by [`Formatter::fixed`], every value is parsed into
+    /// [`ParsedValue::Big`], even the ones that are smaller than the scale.
     pub fn parse_value(self, value: u64) -> ParsedValue {
-        let float_value = value as f32;
-        macro_rules! check {
-            ($exp:literal => $unit:literal) => {{
-                let scale = self.scale($exp);
-                if value >= scale {
-                    return ParsedValue::Big {
-                        coefficient: float_value / (scale as f32),
-                        unit: $unit,
-                        exponent: $exp,
-                        scale,
-                    };
-                }
-            }};
-        }
-
-        check!(5 => 'P');
-        check!(4 => 'T');
-        check!(3 => 'G');
-        check!(2 => 'M');
-        check!(1 => 'K');
-        ParsedValue::Small {
-            value: value as u16,
+        let suffix = self.suffix_style.big_suffix(self.scale_base);
+        let exponent = self.exponent.or_else(|| {
+            (1..=UNITS.len() as u32)
+                .rev()
+                .find(|&exp| value >= self.scale(exp))
+        });
+        let Some(exponent) = exponent else {
+            let small_suffix = self.suffix_style.small_suffix();
+            let fraction_width = if self.precision == 0 {
+                0
+            } else {
+                self.precision + 1
+            };
+            return ParsedValue::Small {
+                value: value as u16,
+                suffix: small_suffix,
+                padding: fraction_width + 1 + suffix.len() - small_suffix.len(),
+            };
+        };
+        let scale = self.scale(exponent);
+        ParsedValue::Big {
+            coefficient: value as f32 / scale as f32,
+            unit: UNITS[exponent as usize - 1],
+            suffix,
+            precision: self.precision,
+            scale,
+            exponent: exponent as usize,
         }
     }
 }
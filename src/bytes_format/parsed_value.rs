@@ -1,24 +1,51 @@
-use derive_more::Display;
+use std::fmt::{self, Display, Formatter};
 
 /// Return value of [`Formatter::parse_value`](super::Formatter::parse_value).
-#[derive(Debug, Display, Clone, Copy)]
+#[derive(Debug, Clone, Copy)]
 pub enum ParsedValue {
     /// When input value is less than `scale_base`.
-    #[display("{value}   ")]
+    #[non_exhaustive]
     Small {
         /// Input value that is less than `scale_base`.
         value: u16,
+        /// The unit that follows `value`, if any.
+        suffix: &'static str,
+        /// Number of spaces after `suffix` to align with [`ParsedValue::Big`].
+        padding: usize,
     },
     /// When input value is greater than `scale_base`.
-    #[display("{coefficient:.1}{unit}")]
+    #[non_exhaustive]
     Big {
         /// The visible part of the number.
         coefficient: f32,
         /// The unit that follows `coefficient`.
         unit: char,
+        /// The rest of the unit symbol that follows `unit`, such as `iB`.
+        suffix: &'static str,
+        /// Number of digits of `coefficient` after the decimal point.
+        precision: usize,
         /// The divisor that was used upon the original number to get `coefficient`.
         scale: u64,
         /// The exponent that was used upon base scale to get `scale`.
         exponent: usize,
     },
 }
+
+impl Display for ParsedValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            ParsedValue::Small {
+                value,
+                suffix,
+                padding,
+            } => write!(f, "{value}{suffix}{:padding$}", ""),
+            ParsedValue::Big {
+                coefficient,
+                unit,
+                suffix,
+                precision,
+                ..
+            } => write!(f, "{coefficient:.precision$}{unit}{suffix}"),
+        }
+    }
+}
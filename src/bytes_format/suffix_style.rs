@@ -0,0 +1,36 @@
+use super::scale_base;
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// How to write the unit suffixes of the quantities of bytes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum SuffixStyle {
+    /// Only the prefix letter, i.e. `K`, `M`, `G`, and so on.
+    #[default]
+    Short,
+    /// SI symbols in metric scale (`KB`, `MB`, ...), IEC symbols in binary scale (`KiB`, `MiB`, ...).
+    ///
+    /// Values smaller than the scale base are followed by `B`.
+    Long,
+}
+
+impl SuffixStyle {
+    /// Text that follows the prefix letter of a value that is not smaller than `scale_base`.
+    pub const fn big_suffix(self, scale_base: u64) -> &'static str {
+        match self {
+            SuffixStyle::Short => "",
+            SuffixStyle::Long if scale_base == scale_base::BINARY => "iB",
+            SuffixStyle::Long => "B",
+        }
+    }
+
+    /// Text that follows a value that is smaller than the scale base.
+    pub const fn small_suffix(self) -> &'static str {
+        match self {
+            SuffixStyle::Short => "",
+            SuffixStyle::Long => "B",
+        }
+    }
+}
@@ -1,9 +1,18 @@
+#[cfg(feature = "cli")]
+pub mod cull_thresholds;
+pub mod flatten;
 pub mod reflection;
+pub mod sort_key;
 
+#[cfg(feature = "cli")]
+pub use cull_thresholds::CullThresholds;
+pub use flatten::FlatNode;
 pub use reflection::Reflection;
+pub use sort_key::SortKey;
 
 pub use Reflection as DataTreeReflection;
 
+use super::node_metadata::NodeMetadata;
 use super::size;
 
 /// Disk usage data of a filesystem tree.
@@ -25,11 +34,17 @@ use super::size;
 pub struct DataTree<Name, Size: size::Size> {
     name: Name,
     size: Size,
+    metadata: Option<Box<NodeMetadata>>,
     children: Vec<Self>,
 }
 
+mod compare;
 mod constructors;
+#[cfg(feature = "cli")]
+mod entry_types;
 mod getters;
+mod limit;
+mod remove;
 mod retain;
 mod sort;
 
@@ -0,0 +1,100 @@
+use super::DataTree;
+use crate::size;
+use std::cmp::Ordering;
+use std::ffi::OsStr;
+use std::iter;
+use std::path::Path;
+
+impl<Name, Size: size::Size> DataTree<Name, Size> {
+    /// Compare the sizes of two nodes, smaller first.
+    pub fn compare_size(&self, other: &Self) -> Ordering {
+        self.size().cmp(&other.size())
+    }
+
+    /// Compare the [counts](crate::node_metadata::NodeMetadata::count) of two nodes, fewer first.
+    ///
+    /// A node without a count comes before any node with one.
+    pub fn compare_count(&self, other: &Self) -> Ordering {
+        let count = |node: &Self| node.metadata().and_then(|metadata| metadata.count);
+        count(self).cmp(&count(other))
+    }
+
+    /// Compare the [modification times](crate::node_metadata::NodeMetadata::mtime) of two
+    /// nodes, older first.
+    ///
+    /// A node without a modification time comes before any node with one.
+    pub fn compare_mtime(&self, other: &Self) -> Ordering {
+        let mtime = |node: &Self| node.metadata().and_then(|metadata| metadata.mtime);
+        mtime(self).cmp(&mtime(other))
+    }
+}
+
+impl<Name, Size> DataTree<Name, Size>
+where
+    Name: AsRef<OsStr>,
+    Size: size::Size,
+{
+    /// Compare the names of two nodes byte by byte.
+    pub fn compare_name(&self, other: &Self) -> Ordering {
+        self.name().as_ref().cmp(other.name().as_ref())
+    }
+
+    /// Compare the names of two nodes, treating each sequence of digits as a number,
+    /// so that `file9` comes before `file10`.
+    pub fn compare_natural_name(&self, other: &Self) -> Ordering {
+        let left = self.name().as_ref().to_string_lossy();
+        let right = other.name().as_ref().to_string_lossy();
+        compare_natural(&left, &right).then_with(|| self.compare_name(other))
+    }
+
+    /// Compare the extensions of the names of two nodes, then the names themselves.
+    ///
+    /// A name without an extension comes before any name with one.
+    pub fn compare_extension(&self, other: &Self) -> Ordering {
+        let left = Path::new(self.name().as_ref()).extension();
+        let right = Path::new(other.name().as_ref()).extension();
+        left.cmp(&right).then_with(|| self.compare_name(other))
+    }
+}
+
+/// Split `text` into alternating sequences of ASCII digits and of other characters.
+fn chunks(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    iter::from_fn(move || {
+        let is_digit = rest.chars().next()?.is_ascii_digit();
+        let end = rest
+            .find(|char: char| char.is_ascii_digit() != is_digit)
+            .unwrap_or(rest.len());
+        let (chunk, tail) = rest.split_at(end);
+        rest = tail;
+        Some(chunk)
+    })
+}
+
+/// Compare two strings, treating each sequence of digits as a number.
+fn compare_natural(left: &str, right: &str) -> Ordering {
+    let mut left = chunks(left);
+    let mut right = chunks(right);
+    loop {
+        let (left, right) = match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(left), Some(right)) => (left, right),
+        };
+        let is_number = |chunk: &str| chunk.starts_with(|char: char| char.is_ascii_digit());
+        let order = if is_number(left) && is_number(right) {
+            let left = left.trim_start_matches('0');
+            let right = right.trim_start_matches('0');
+            left.len().cmp(&right.len()).then_with(|| left.cmp(right))
+        } else {
+            left.cmp(right)
+        };
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
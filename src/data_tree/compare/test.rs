@@ -0,0 +1,94 @@
+use crate::data_tree::{DataTree, SortKey};
+use crate::node_metadata::NodeMetadata;
+use crate::size::Bytes;
+use pretty_assertions::assert_eq;
+
+type SampleTree = DataTree<String, Bytes>;
+
+fn file(name: &'static str, size: u64) -> SampleTree {
+    SampleTree::file(name.to_string(), size.into())
+}
+
+fn file_with_mtime(name: &'static str, size: u64, mtime: u64) -> SampleTree {
+    file(name, size).with_metadata(Some(Box::new(NodeMetadata {
+        mtime: Some(mtime),
+        ..NodeMetadata::default()
+    })))
+}
+
+fn sorted_names(files: Vec<SampleTree>, key: SortKey, reverse: bool) -> Vec<String> {
+    let mut tree = SampleTree::dir("root".to_string(), 0.into(), files);
+    tree.par_sort_by_key(key, reverse);
+    tree.children()
+        .iter()
+        .map(|child| child.name().clone())
+        .collect()
+}
+
+#[test]
+fn sort_by_size() {
+    let files = vec![file("a", 200), file("b", 300), file("c", 100)];
+    assert_eq!(sorted_names(files, SortKey::Size, false), ["b", "a", "c"]);
+}
+
+#[test]
+fn sort_by_size_reversed() {
+    let files = vec![file("a", 200), file("b", 300), file("c", 100)];
+    assert_eq!(sorted_names(files, SortKey::Size, true), ["c", "a", "b"]);
+}
+
+#[test]
+fn sort_by_name() {
+    let files = vec![file("file10", 1), file("File2", 1), file("file9", 1)];
+    assert_eq!(
+        sorted_names(files, SortKey::Name, false),
+        ["File2", "file10", "file9"],
+    );
+}
+
+#[test]
+fn sort_by_natural_name() {
+    let files = vec![
+        file("file10", 1),
+        file("file9", 1),
+        file("file009", 1),
+        file("file", 1),
+        file("file9a", 1),
+        file("10", 1),
+        file("9", 1),
+    ];
+    assert_eq!(
+        sorted_names(files, SortKey::NaturalName, false),
+        ["9", "10", "file", "file009", "file9", "file9a", "file10"],
+    );
+}
+
+#[test]
+fn sort_by_extension() {
+    let files = vec![
+        file("b.txt", 1),
+        file("a.rs", 1),
+        file("Makefile", 1),
+        file("a.txt", 1),
+        file(".hidden", 1),
+    ];
+    assert_eq!(
+        sorted_names(files, SortKey::Extension, false),
+        [".hidden", "Makefile", "a.rs", "a.txt", "b.txt"],
+    );
+}
+
+#[test]
+fn sort_by_mtime() {
+    let files = vec![
+        file_with_mtime("old", 300, 1000),
+        file_with_mtime("new", 100, 3000),
+        file_with_mtime("middle-small", 100, 2000),
+        file_with_mtime("middle-large", 200, 2000),
+        file("unknown", 400),
+    ];
+    assert_eq!(
+        sorted_names(files, SortKey::Mtime, false),
+        ["new", "middle-large", "middle-small", "old", "unknown"],
+    );
+}
@@ -1,4 +1,5 @@
 use super::DataTree;
+use crate::node_metadata::NodeMetadata;
 use crate::size;
 
 impl<Name, Size: size::Size> DataTree<Name, Size> {
@@ -9,6 +10,7 @@ impl<Name, Size: size::Size> DataTree<Name, Size> {
         DataTree {
             name,
             size,
+            metadata: None,
             children,
         }
     }
@@ -19,10 +21,18 @@ impl<Name, Size: size::Size> DataTree<Name, Size> {
         DataTree {
             name,
             size,
+            metadata: None,
             children: Vec::new(),
         }
     }
 
+    /// Attach [metadata](NodeMetadata) to the tree.
+    #[inline]
+    pub fn with_metadata(mut self, metadata: Option<Box<NodeMetadata>>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
     /// Create a directory constructor of fixed inode size.
     #[inline]
     pub fn fixed_size_dir_constructor(inode_size: Size) -> impl Fn(Name, Vec<Self>) -> Self
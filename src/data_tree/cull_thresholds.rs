@@ -0,0 +1,13 @@
+/// Sizes below which the descendants of a [`DataTree`](super::DataTree) are culled.
+///
+/// A descendant is kept only if it satisfies all of the thresholds. The default value culls
+/// nothing.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CullThresholds {
+    /// Minimal size proportion relative to the root.
+    pub min_ratio: f32,
+    /// Minimal size proportion relative to the parent.
+    pub min_ratio_of_parent: f32,
+    /// Minimal size.
+    pub min_size: u64,
+}
@@ -0,0 +1,49 @@
+use super::DataTree;
+use crate::size;
+use rayon::prelude::*;
+use std::ffi::OsStr;
+use std::fs::symlink_metadata;
+use std::path::Path;
+
+impl<Name, Size> DataTree<Name, Size>
+where
+    Self: Send,
+    Name: AsRef<OsStr>,
+    Size: size::Size,
+{
+    /// Look up the [kinds](crate::node_metadata::EntryType) of the nodes whose metadata lack
+    /// them, in parallel. The name of each node is a path relative to that of its parent.
+    ///
+    /// If `synthetic_root` is `true`, the root does not represent a real entry, so the names of
+    /// its children are the paths of the real roots.
+    ///
+    /// This is meant for a tree that has been shaped for a chart: looking up the kinds of its few
+    /// remaining entries is cheaper than collecting the kinds of all the scanned entries.
+    pub(crate) fn par_fill_entry_types(&mut self, synthetic_root: bool) {
+        let parent = Path::new("");
+        if synthetic_root {
+            self.children
+                .par_iter_mut()
+                .for_each(|child| child.par_fill_entry_types_under(parent));
+        } else {
+            self.par_fill_entry_types_under(parent);
+        }
+    }
+
+    /// Internal function to be used by [`Self::par_fill_entry_types`].
+    fn par_fill_entry_types_under(&mut self, parent: &Path) {
+        let path = parent.join(self.name.as_ref());
+        let metadata = self.metadata.as_deref();
+        let is_aggregate = metadata.is_some_and(|metadata| metadata.aggregated_entries.is_some());
+        let is_known = metadata.is_some_and(|metadata| metadata.entry_type.is_some());
+        if !is_aggregate
+            && !is_known
+            && let Ok(stats) = symlink_metadata(&path)
+        {
+            self.metadata.get_or_insert_default().entry_type = Some(stats.file_type().into());
+        }
+        self.children
+            .par_iter_mut()
+            .for_each(|child| child.par_fill_entry_types_under(&path));
+    }
+}
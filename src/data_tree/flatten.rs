@@ -0,0 +1,53 @@
+use super::DataTree;
+use crate::size;
+use std::cmp::Reverse;
+
+/// A node of a [`DataTree`] along with the names of its ancestors.
+///
+/// It is an item of the list returned by [`DataTree::flatten`].
+#[derive(Debug, Clone)]
+pub struct FlatNode<'a, Name, Size: size::Size> {
+    /// Names of the ancestors of the node, from the root.
+    pub ancestors: Vec<&'a Name>,
+    /// The node itself.
+    pub node: &'a DataTree<Name, Size>,
+}
+
+impl<'a, Name, Size: size::Size> FlatNode<'a, Name, Size> {
+    /// Names of the nodes from the root to this node, both inclusive.
+    pub fn path(&self) -> impl Iterator<Item = &'a Name> + '_ {
+        self.ancestors
+            .iter()
+            .copied()
+            .chain(std::iter::once(self.node.name()))
+    }
+}
+
+impl<Name, Size: size::Size> DataTree<Name, Size> {
+    /// List every node of the tree along with its ancestors, from the largest.
+    ///
+    /// Nodes of the same size are listed in depth-first order, so an ancestor always comes
+    /// before its descendants.
+    pub fn flatten(&self) -> Vec<FlatNode<'_, Name, Size>> {
+        fn collect<'a, Name, Size: size::Size>(
+            node: &'a DataTree<Name, Size>,
+            ancestors: &mut Vec<&'a Name>,
+            nodes: &mut Vec<FlatNode<'a, Name, Size>>,
+        ) {
+            nodes.push(FlatNode {
+                ancestors: ancestors.clone(),
+                node,
+            });
+            ancestors.push(node.name());
+            for child in node.children() {
+                collect(child, ancestors, nodes);
+            }
+            ancestors.pop();
+        }
+
+        let mut nodes = Vec::new();
+        collect(self, &mut Vec::new(), &mut nodes);
+        nodes.sort_by_key(|flat_node| Reverse(flat_node.node.size())); // stable
+        nodes
+    }
+}
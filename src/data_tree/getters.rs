@@ -1,4 +1,5 @@
 use super::DataTree;
+use crate::node_metadata::NodeMetadata;
 use crate::size;
 
 impl<Name, Size: size::Size> DataTree<Name, Size> {
@@ -20,6 +21,12 @@ impl<Name, Size: size::Size> DataTree<Name, Size> {
         self.size
     }
 
+    /// Extract metadata
+    #[inline]
+    pub fn metadata(&self) -> Option<&NodeMetadata> {
+        self.metadata.as_deref()
+    }
+
     /// Extract children
     #[inline]
     pub fn children(&self) -> &Vec<Self> {
@@ -0,0 +1,92 @@
+use super::DataTree;
+use crate::size;
+use rayon::prelude::*;
+use std::cmp::Reverse;
+
+impl<Name, Size> DataTree<Name, Size>
+where
+    Self: Send,
+    Size: size::Size,
+{
+    /// Recursively keep only the `max_children` largest children of every node, in parallel.
+    ///
+    /// The order of the remaining children is preserved. Amongst children of the same size,
+    /// the ones that come first are kept.
+    pub fn par_retain_largest_children(&mut self, max_children: usize) {
+        if self.children.len() > max_children {
+            let mut ranking: Vec<_> = (0..self.children.len()).collect();
+            ranking.sort_by_key(|&index| Reverse(self.children[index].size));
+            let mut kept = vec![false; self.children.len()];
+            for &index in &ranking[..max_children] {
+                kept[index] = true;
+            }
+            let mut kept = kept.into_iter();
+            self.children
+                .retain(|_| kept.next().expect("one flag per child"));
+        }
+        self.children
+            .par_iter_mut()
+            .for_each(|child| child.par_retain_largest_children(max_children));
+    }
+
+    /// Keep only the `max_nodes` largest nodes of the tree, the root included.
+    ///
+    /// Nodes are ranked by size, then by depth (shallower first), then by their order in the
+    /// tree. Since no descendant is larger than its ancestors, the ancestors of the kept nodes
+    /// are kept as well and the shape of the tree stays intact.
+    pub fn retain_largest_nodes(&mut self, max_nodes: usize) {
+        let mut ranking = Vec::new();
+        self.collect_ranking(0, &mut ranking);
+        if ranking.len() <= max_nodes {
+            return;
+        }
+        let mut kept = vec![false; ranking.len()];
+        ranking.sort_unstable();
+        for &(_, _, index) in &ranking[..max_nodes] {
+            kept[index] = true;
+        }
+        let mut kept = kept.into_iter();
+        kept.next(); // the root is never removed
+        self.retain_flagged(&mut kept);
+    }
+
+    /// Internal function to be used by [`Self::retain_largest_nodes`].
+    ///
+    /// Push the rank key of every node in pre-order.
+    fn collect_ranking(&self, depth: usize, ranking: &mut Vec<(Reverse<Size>, usize, usize)>) {
+        ranking.push((Reverse(self.size), depth, ranking.len()));
+        for child in &self.children {
+            child.collect_ranking(depth + 1, ranking);
+        }
+    }
+
+    /// Internal function to be used by [`Self::retain_largest_nodes`].
+    ///
+    /// Consume one flag per descendant in pre-order, removing the descendants whose flags are
+    /// `false` along with their subtrees.
+    fn retain_flagged(&mut self, flags: &mut impl Iterator<Item = bool>) {
+        self.children.retain_mut(|child| {
+            if flags.next().expect("one flag per node") {
+                child.retain_flagged(flags);
+                true
+            } else {
+                for _ in 1..child.node_count() {
+                    flags.next();
+                }
+                false
+            }
+        });
+    }
+
+    /// Count the nodes of the tree, the root included.
+    fn node_count(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(DataTree::node_count)
+            .sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+mod test;
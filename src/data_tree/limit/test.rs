@@ -0,0 +1,108 @@
+use crate::data_tree::DataTree;
+use crate::size::Bytes;
+use pretty_assertions::assert_eq;
+
+type SampleTree = DataTree<String, Bytes>;
+
+fn dir(name: &'static str, children: Vec<SampleTree>) -> SampleTree {
+    SampleTree::dir(name.to_string(), 0.into(), children)
+}
+
+fn culled_dir(name: &'static str, culled_size: u64, children: Vec<SampleTree>) -> SampleTree {
+    SampleTree::dir(name.to_string(), culled_size.into(), children)
+}
+
+fn file(name: &'static str, size: u64) -> SampleTree {
+    SampleTree::file(name.to_string(), size.into())
+}
+
+fn sample_tree() -> SampleTree {
+    dir(
+        "root",
+        vec![
+            dir(
+                "shards",
+                vec![
+                    file("a", 300),
+                    file("b", 500),
+                    file("c", 100),
+                    file("d", 500),
+                    file("e", 400),
+                ],
+            ),
+            dir("small", vec![file("f", 50), file("g", 20)]),
+            file("h", 1000),
+        ],
+    )
+}
+
+#[test]
+fn retain_largest_children() {
+    let mut actual = sample_tree();
+    actual.par_retain_largest_children(2);
+    let expected = culled_dir(
+        "root",
+        50 + 20,
+        vec![
+            culled_dir(
+                "shards",
+                300 + 100 + 400,
+                vec![file("b", 500), file("d", 500)],
+            ),
+            file("h", 1000),
+        ],
+    );
+    assert_eq!(actual.into_reflection(), expected.into_reflection());
+}
+
+#[test]
+fn retain_largest_children_more_than_enough() {
+    let mut actual = sample_tree();
+    actual.par_retain_largest_children(5);
+    assert_eq!(actual.into_reflection(), sample_tree().into_reflection());
+}
+
+#[test]
+fn retain_largest_nodes() {
+    let mut actual = sample_tree();
+    actual.retain_largest_nodes(5);
+    let expected = culled_dir(
+        "root",
+        50 + 20,
+        vec![
+            culled_dir(
+                "shards",
+                300 + 100 + 400,
+                vec![file("b", 500), file("d", 500)],
+            ),
+            file("h", 1000),
+        ],
+    );
+    assert_eq!(actual.into_reflection(), expected.into_reflection());
+}
+
+#[test]
+fn retain_largest_nodes_keeps_ancestors() {
+    let mut actual = dir(
+        "root",
+        vec![
+            dir("a", vec![dir("b", vec![dir("c", vec![file("d", 100)])])]),
+            file("e", 99),
+        ],
+    );
+    actual.retain_largest_nodes(4);
+    let expected = culled_dir(
+        "root",
+        99,
+        vec![dir("a", vec![dir("b", vec![culled_dir("c", 100, vec![])])])],
+    );
+    assert_eq!(actual.into_reflection(), expected.into_reflection());
+}
+
+#[test]
+fn retain_largest_nodes_only_root() {
+    let mut actual = sample_tree();
+    actual.retain_largest_nodes(1);
+    assert_eq!(actual.children().len(), 0);
+    assert_eq!(actual.size(), sample_tree().size());
+}
@@ -1,3 +1,4 @@
+use crate::node_metadata::NodeMetadata;
 use crate::size;
 use std::collections::VecDeque;
 use std::ffi::OsStr;
@@ -33,6 +34,15 @@ pub struct Reflection<Name, Size: size::Size> {
     pub name: Name,
     /// Disk usage of a file or total disk usage of a folder.
     pub size: Size,
+    /// Optional metadata of the tree.
+    ///
+    /// Use [`new`](Self::new) and [`with_metadata`](Self::with_metadata) to construct a
+    /// reflection without naming this field.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub metadata: Option<Box<NodeMetadata>>,
     /// Data of children filesystem subtrees.
     pub children: Vec<Self>,
 }
@@ -77,5 +87,6 @@ where
     }
 }
 
+mod constructors;
 mod convert;
 mod par_methods;
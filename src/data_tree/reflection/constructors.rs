@@ -0,0 +1,23 @@
+use super::Reflection;
+use crate::node_metadata::NodeMetadata;
+use crate::size;
+
+impl<Name, Size: size::Size> Reflection<Name, Size> {
+    /// Create a reflection without [metadata](NodeMetadata).
+    #[inline]
+    pub fn new(name: Name, size: Size, children: Vec<Self>) -> Self {
+        Reflection {
+            name,
+            size,
+            metadata: None,
+            children,
+        }
+    }
+
+    /// Attach [metadata](NodeMetadata) to the reflection.
+    #[inline]
+    pub fn with_metadata(mut self, metadata: Option<Box<NodeMetadata>>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+}
@@ -7,12 +7,14 @@ impl<Name, Size: size::Size> From<DataTree<Name, Size>> for Reflection<Name, Siz
         let DataTree {
             name,
             size,
+            metadata,
             children,
         } = source;
         let children: Vec<_> = children.into_iter().map(Reflection::from).collect();
         Reflection {
             name,
             size,
+            metadata,
             children,
         }
     }
@@ -15,6 +15,7 @@ where
         let Reflection {
             name,
             size,
+            metadata,
             children,
         } = self;
         let excess_child = children
@@ -44,6 +45,7 @@ where
         Ok(DataTree {
             name,
             size,
+            metadata,
             children,
         })
     }
@@ -62,6 +64,7 @@ where
         let Reflection {
             name,
             size,
+            metadata,
             children,
         } = self;
         let children = children
@@ -72,6 +75,7 @@ where
         Ok(Reflection {
             name,
             size,
+            metadata,
             children,
         })
     }
@@ -0,0 +1,29 @@
+use super::DataTree;
+use crate::size;
+
+impl<Name, Size> DataTree<Name, Size>
+where
+    Name: PartialEq,
+    Size: size::Size,
+{
+    /// Detach a descendant and subtract its size from every one of its ancestors.
+    ///
+    /// `path` lists the names of the nodes from a child of this node to the descendant.
+    /// Return [`None`] if no such descendant exists.
+    pub fn remove_descendant(&mut self, path: &[Name]) -> Option<Self> {
+        let (name, rest) = path.split_first()?;
+        let index = self.children.iter().position(|child| &child.name == name)?;
+        let removed = if rest.is_empty() {
+            self.children.remove(index)
+        } else {
+            self.children[index].remove_descendant(rest)?
+        };
+        // sizes of directories may have been reduced by hardlinks deduplication
+        self.size = if self.size > removed.size {
+            self.size - removed.size
+        } else {
+            Size::default()
+        };
+        Some(removed)
+    }
+}
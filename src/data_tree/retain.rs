@@ -1,5 +1,10 @@
 use super::DataTree;
 use crate::size;
+
+#[cfg(feature = "cli")]
+use super::CullThresholds;
+#[cfg(feature = "cli")]
+use crate::node_metadata::NodeMetadata;
 use rayon::prelude::*;
 
 impl<Name, Size> DataTree<Name, Size>
@@ -48,6 +53,102 @@ where
         self.par_retain(|descendant, _| descendant.size().into() as f32 >= minimal);
     }
 
+    /// Recursively cull all descendants whose sizes are smaller than `min_size`.
+    #[cfg(feature = "cli")]
+    pub fn par_cull_small_data(&mut self, min_size: u64)
+    where
+        Size: Into<u64>,
+    {
+        self.par_cull(CullThresholds {
+            min_size,
+            ..CullThresholds::default()
+        });
+    }
+
+    /// Recursively cull all descendants whose sizes are too small relative to their parents.
+    #[cfg(feature = "cli")]
+    pub fn par_cull_insignificant_children(&mut self, min_ratio: f32)
+    where
+        Size: Into<u64>,
+    {
+        self.par_cull(CullThresholds {
+            min_ratio_of_parent: min_ratio,
+            ..CullThresholds::default()
+        });
+    }
+
+    /// Internal function to be used by [`Self::par_cull`] and [`Self::par_cull_into_aggregates`].
+    #[cfg(feature = "cli")]
+    fn par_cull_with_minimal(
+        &mut self,
+        root_minimal: f32,
+        thresholds: CullThresholds,
+        aggregate_name: &(impl Fn(&[Self]) -> Option<Name> + Sync),
+    ) where
+        Size: Into<u64>,
+    {
+        let parent_minimal = self.size().into() as f32 * thresholds.min_ratio_of_parent.max(0.0);
+        let is_significant = |child: &Self| {
+            let size = child.size().into();
+            size >= thresholds.min_size
+                && size as f32 >= root_minimal
+                && size as f32 >= parent_minimal
+        };
+        let culled: Vec<Self> = self
+            .children
+            .extract_if(.., |child| !is_significant(child))
+            .collect();
+        self.children.par_iter_mut().for_each(|child| {
+            child.par_cull_with_minimal(root_minimal, thresholds, aggregate_name)
+        });
+        if culled.is_empty() {
+            return;
+        }
+        if let Some(name) = aggregate_name(&culled) {
+            let culled_size = culled.iter().map(DataTree::size).sum();
+            let mut metadata = NodeMetadata {
+                aggregated_entries: Some(culled.len() as u64),
+                ..NodeMetadata::default()
+            };
+            culled
+                .iter()
+                .filter_map(DataTree::metadata)
+                .for_each(|child| metadata.absorb(child));
+            let aggregate =
+                DataTree::file(name, culled_size).with_metadata(Some(Box::new(metadata)));
+            self.children.push(aggregate);
+        }
+    }
+
+    /// Recursively cull all descendants that fall below any of the `thresholds`.
+    #[cfg(feature = "cli")]
+    pub fn par_cull(&mut self, thresholds: CullThresholds)
+    where
+        Size: Into<u64>,
+    {
+        let root_minimal = self.size().into() as f32 * thresholds.min_ratio.max(0.0);
+        self.par_cull_with_minimal(root_minimal, thresholds, &|_: &[Self]| None);
+    }
+
+    /// Recursively cull all descendants that fall below any of the `thresholds`, replacing the
+    /// culled children of each node with a single node that carries their combined size.
+    ///
+    /// The name of the new node is created by `aggregate_name` from the culled children, and its
+    /// [`NodeMetadata::aggregated_entries`] is set to their number.
+    #[cfg(feature = "cli")]
+    pub fn par_cull_into_aggregates(
+        &mut self,
+        thresholds: CullThresholds,
+        aggregate_name: impl Fn(&[Self]) -> Name + Sync,
+    ) where
+        Size: Into<u64>,
+    {
+        let root_minimal = self.size().into() as f32 * thresholds.min_ratio.max(0.0);
+        self.par_cull_with_minimal(root_minimal, thresholds, &|culled: &[Self]| {
+            Some(aggregate_name(culled))
+        });
+    }
+
     /// Process the tree via [`par_cull_insignificant_data`](Self::par_cull_insignificant_data) method.
     #[cfg(test)]
     #[cfg(feature = "cli")]
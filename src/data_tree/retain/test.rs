@@ -1,4 +1,5 @@
-use crate::data_tree::DataTree;
+use crate::data_tree::{CullThresholds, DataTree};
+use crate::node_metadata::NodeMetadata;
 use crate::size::Bytes;
 use pretty_assertions::assert_eq;
 use std::cmp::Ordering;
@@ -188,3 +189,110 @@ fn edge_cases() {
     .into_reflection();
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn cull_by_min_size() {
+    let dir = dir::<1000>;
+    let culled_dir = culled_dir::<1000>;
+    let mut actual = dir(
+        "root",
+        vec![
+            dir("big", vec![file("a", 5000), file("b", 500)]),
+            dir("small", vec![file("c", 100), file("d", 200)]),
+            file("e", 3000),
+            file("f", 999),
+        ],
+    );
+    actual.par_cull_small_data(1000);
+    let expected = culled_dir(
+        "root",
+        999,
+        vec![
+            culled_dir("big", 500, vec![file("a", 5000)]),
+            file("small", 1000 + 100 + 200),
+            file("e", 3000),
+        ],
+    )
+    .into_reflection();
+    assert_eq!(actual.into_reflection(), expected);
+}
+
+#[test]
+fn cull_by_min_ratio_of_parent() {
+    let dir = dir::<0>;
+    let culled_dir = culled_dir::<0>;
+    let mut actual = dir(
+        "root",
+        vec![
+            dir(
+                "big",
+                vec![file("a", 900_000), file("b", 90_000), file("c", 10_000)],
+            ),
+            dir("small", vec![file("d", 95_000), file("e", 5_000)]),
+            file("f", 1_000),
+        ],
+    );
+    // "e" is 5% of its parent despite being less than 1% of the root, "c" is 1% of its parent
+    actual.par_cull_insignificant_children(0.05);
+    let expected = culled_dir(
+        "root",
+        1_000,
+        vec![
+            culled_dir("big", 10_000, vec![file("a", 900_000), file("b", 90_000)]),
+            dir("small", vec![file("d", 95_000), file("e", 5_000)]),
+        ],
+    )
+    .into_reflection();
+    assert_eq!(actual.into_reflection(), expected);
+}
+
+#[test]
+fn cull_into_aggregates() {
+    let dir = dir::<0>;
+    let mut actual = dir(
+        "root",
+        vec![
+            dir(
+                "big",
+                vec![file("a", 900_000), file("b", 90_000), file("c", 10_000)],
+            ),
+            dir("small", vec![file("d", 95_000), file("e", 5_000)]),
+            file("f", 1_000),
+            file("g", 2_000),
+        ],
+    );
+    actual.par_cull_into_aggregates(
+        CullThresholds {
+            min_ratio: 0.01,
+            min_ratio_of_parent: 0.05,
+            min_size: 0,
+        },
+        |culled| format!("({} smaller)", culled.len()),
+    );
+    let aggregate = |name, count, size| {
+        let metadata = NodeMetadata {
+            aggregated_entries: Some(count),
+            ..NodeMetadata::default()
+        };
+        file(name, size).with_metadata(Some(Box::new(metadata)))
+    };
+    let expected = dir(
+        "root",
+        vec![
+            dir(
+                "big",
+                vec![
+                    file("a", 900_000),
+                    file("b", 90_000),
+                    aggregate("(1 smaller)", 1, 10_000),
+                ],
+            ),
+            dir(
+                "small",
+                vec![file("d", 95_000), aggregate("(1 smaller)", 1, 5_000)],
+            ),
+            aggregate("(2 smaller)", 2, 3_000),
+        ],
+    );
+    assert_eq!(actual.into_reflection(), expected.into_reflection());
+}
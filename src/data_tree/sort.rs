@@ -1,7 +1,8 @@
-use super::DataTree;
+use super::{DataTree, SortKey};
 use crate::size;
 use rayon::prelude::*;
 use std::cmp::Ordering;
+use std::ffi::OsStr;
 
 impl<Name, Size> DataTree<Name, Size>
 where
@@ -25,4 +26,18 @@ where
         self.par_sort_by(compare);
         self
     }
+
+    /// Sort all descendants recursively by `key`, in parallel.
+    ///
+    /// If `reverse` is `true`, the order of [`SortKey::compare`] is reversed.
+    pub fn par_sort_by_key(&mut self, key: SortKey, reverse: bool)
+    where
+        Name: AsRef<OsStr>,
+    {
+        if reverse {
+            self.par_sort_by(|left, right| key.compare(left, right).reverse());
+        } else {
+            self.par_sort_by(|left, right| key.compare(left, right));
+        }
+    }
 }
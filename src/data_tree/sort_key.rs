@@ -0,0 +1,59 @@
+use super::DataTree;
+use crate::size;
+use std::cmp::Ordering;
+use std::ffi::OsStr;
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// Property by which the children of each node are sorted.
+///
+/// Sizes, counts, and modification times put the largest, the most, and the newest first,
+/// while the names are sorted in ascending order.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum SortKey {
+    /// Largest first.
+    #[default]
+    Size,
+    /// Names, byte by byte.
+    Name,
+    /// Names, treating each sequence of digits as a number.
+    NaturalName,
+    /// Most entries other than directories first.
+    Count,
+    /// Most recently modified first.
+    Mtime,
+    /// Extensions of the names, then the names.
+    Extension,
+}
+
+impl SortKey {
+    /// Compare two nodes.
+    ///
+    /// Nodes with the same count or modification time are ordered by size.
+    pub fn compare<Name, Size>(
+        self,
+        left: &DataTree<Name, Size>,
+        right: &DataTree<Name, Size>,
+    ) -> Ordering
+    where
+        Name: AsRef<OsStr>,
+        Size: size::Size,
+    {
+        match self {
+            SortKey::Size => left.compare_size(right).reverse(),
+            SortKey::Name => left.compare_name(right),
+            SortKey::NaturalName => left.compare_natural_name(right),
+            SortKey::Count => left
+                .compare_count(right)
+                .then_with(|| left.compare_size(right))
+                .reverse(),
+            SortKey::Mtime => left
+                .compare_mtime(right)
+                .then_with(|| left.compare_size(right))
+                .reverse(),
+            SortKey::Extension => left.compare_extension(right),
+        }
+    }
+}
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 
 /// Whether to cross device boundary into a different filesystem.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "kebab-case"))]
 pub enum DeviceBoundary {
     Cross,
     Stay,
@@ -0,0 +1,85 @@
+pub mod delta;
+pub mod label;
+pub mod status;
+
+pub use delta::Delta;
+pub use label::DiffLabel;
+pub use status::DiffStatus;
+
+use crate::size;
+
+#[cfg(feature = "json")]
+use crate::json_data::{BinaryVersion, SchemaVersion};
+#[cfg(feature = "json")]
+use crate::os_string_display::OsStringDisplay;
+#[cfg(feature = "json")]
+use crate::size::{Blocks, Bytes};
+#[cfg(feature = "json")]
+use derive_more::From;
+#[cfg(feature = "json")]
+use serde::Serialize;
+
+/// Difference between two snapshots of a filesystem tree, aligned by path.
+///
+/// **Construction:** Use [`DiffTree::par_compare`] to compare two
+/// [`DataTree`](crate::data_tree::DataTree)s.
+///
+/// **Visualization:** Use [`DiffTree::into_chart_tree`] to convert it into a
+/// [`DataTree`](crate::data_tree::DataTree) whose sizes are the absolute amounts of change,
+/// then render the result with [`Visualizer`](crate::visualizer::Visualizer).
+///
+/// **Serialization:** _(feature: `json`)_ [`DiffTree`] implements `Serialize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "kebab-case"))]
+#[cfg_attr(
+    feature = "json",
+    serde(bound(serialize = "Name: Serialize, Size: Serialize + Into<u64>"))
+)]
+pub struct DiffTree<Name, Size: size::Size> {
+    name: Name,
+    #[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
+    old_size: Option<Size>,
+    #[cfg_attr(feature = "json", serde(skip_serializing_if = "Option::is_none"))]
+    new_size: Option<Size>,
+    delta: Delta<Size>,
+    children: Vec<Self>,
+}
+
+/// The `"unit"` field and the `"tree"` field of [`JsonDiff`].
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, From, Serialize)]
+#[serde(tag = "unit")]
+#[serde(rename_all = "kebab-case")]
+pub enum JsonDiffBody {
+    /// Difference where size is [bytes](Bytes).
+    Bytes(JsonDiffTree<Bytes>),
+    /// Difference where size is [blocks](Blocks).
+    Blocks(JsonDiffTree<Blocks>),
+}
+
+/// The `"tree"` field of [`JsonDiff`].
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, From, Serialize)]
+pub struct JsonDiffTree<Size: size::Size + Into<u64>> {
+    /// The difference between the two trees.
+    pub tree: DiffTree<OsStringDisplay, Size>,
+}
+
+/// Output of the program with `--diff` and `--json-output` flags.
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct JsonDiff {
+    /// The `"schema-version"` field.
+    pub schema_version: SchemaVersion,
+    /// The `"pdu"` field.
+    #[serde(rename = "pdu")]
+    pub binary_version: Option<BinaryVersion>,
+    /// The `"unit"` field and the `"tree"` field.
+    #[serde(flatten)]
+    pub body: JsonDiffBody,
+}
+
+mod compare;
+mod getters;
@@ -0,0 +1,179 @@
+use super::{Delta, DiffLabel, DiffTree};
+use crate::data_tree::{DataTree, Reflection};
+use crate::size;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Pair of matching nodes from the old tree and the new tree.
+enum Counterparts<Node> {
+    /// The node exists in both trees.
+    Both(Node, Node),
+    /// The node only exists in the old tree.
+    OnlyOld(Node),
+    /// The node only exists in the new tree.
+    OnlyNew(Node),
+}
+
+impl<Name, Size> DiffTree<Name, Size>
+where
+    Name: Eq + Hash + Send,
+    Size: size::Size + Send + Sync,
+{
+    /// Compare two trees, in parallel.
+    ///
+    /// Descendants of the two roots are aligned by their names. The names of the roots
+    /// themselves are not compared, and the resulting root takes the name of `new`.
+    pub fn par_compare(old: DataTree<Name, Size>, new: DataTree<Name, Size>) -> Self {
+        Self::par_compare_reflections(old.into_reflection(), new.into_reflection())
+    }
+
+    /// Internal function to be used by [`Self::par_compare`].
+    fn par_compare_reflections(old: Reflection<Name, Size>, new: Reflection<Name, Size>) -> Self {
+        let Reflection {
+            size: old_size,
+            children: old_children,
+            ..
+        } = old;
+        let Reflection {
+            name,
+            size: new_size,
+            children: new_children,
+            ..
+        } = new;
+
+        let matching_indices: Vec<Option<usize>> = {
+            let lookup: HashMap<&Name, usize> = old_children
+                .iter()
+                .enumerate()
+                .map(|(index, child)| (&child.name, index))
+                .collect();
+            new_children
+                .iter()
+                .map(|child| lookup.get(&child.name).copied())
+                .collect()
+        };
+
+        let mut old_children: Vec<_> = old_children.into_iter().map(Some).collect();
+        let mut counterparts: Vec<_> = new_children
+            .into_iter()
+            .zip(matching_indices)
+            .map(
+                |(new_child, index)| match index.and_then(|index| old_children[index].take()) {
+                    Some(old_child) => Counterparts::Both(old_child, new_child),
+                    None => Counterparts::OnlyNew(new_child),
+                },
+            )
+            .collect();
+        counterparts.extend(
+            old_children
+                .into_iter()
+                .flatten()
+                .map(Counterparts::OnlyOld),
+        );
+
+        let children = counterparts
+            .into_par_iter()
+            .map(|counterparts| match counterparts {
+                Counterparts::Both(old, new) => Self::par_compare_reflections(old, new),
+                Counterparts::OnlyOld(old) => Self::par_one_sided(old, false),
+                Counterparts::OnlyNew(new) => Self::par_one_sided(new, true),
+            })
+            .collect();
+
+        DiffTree {
+            name,
+            old_size: Some(old_size),
+            new_size: Some(new_size),
+            delta: Delta::between(old_size, new_size),
+            children,
+        }
+    }
+
+    /// Create a tree whose nodes only exist in one of the snapshots.
+    fn par_one_sided(reflection: Reflection<Name, Size>, is_new: bool) -> Self {
+        let Reflection {
+            name,
+            size,
+            children,
+            ..
+        } = reflection;
+        let children = children
+            .into_par_iter()
+            .map(|child| Self::par_one_sided(child, is_new))
+            .collect();
+        let (old_size, new_size, delta) = if is_new {
+            (None, Some(size), Delta::Increase(size))
+        } else {
+            (Some(size), None, Delta::Decrease(size))
+        };
+        DiffTree {
+            name,
+            old_size,
+            new_size,
+            delta,
+            children,
+        }
+    }
+}
+
+impl<Name, Size> DiffTree<Name, Size>
+where
+    Name: Send,
+    Size: size::Size + Send + Sync,
+{
+    /// Convert the difference into a [`DataTree`] that can be visualized.
+    ///
+    /// The size of each node in the resulting tree is the total absolute change within
+    /// the node, so a directory whose children grew and shrank by the same amount still
+    /// shows up. The signed change is displayed alongside the name by [`DiffLabel`].
+    pub fn into_chart_tree(
+        self,
+        format: Size::DisplayFormat,
+    ) -> DataTree<DiffLabel<Name, Size>, Size>
+    where
+        Size::DisplayFormat: Send + Sync,
+    {
+        let status = self.status();
+        let DiffTree {
+            name,
+            old_size,
+            new_size,
+            delta,
+            children,
+        } = self;
+
+        let old_children_size: Size = children.iter().filter_map(DiffTree::old_size).sum();
+        let new_children_size: Size = children.iter().filter_map(DiffTree::new_size).sum();
+        let old_own_size = own_size(old_size.unwrap_or_default(), old_children_size);
+        let new_own_size = own_size(new_size.unwrap_or_default(), new_children_size);
+        let own_change = Delta::between(old_own_size, new_own_size).magnitude();
+
+        let children = children
+            .into_par_iter()
+            .map(|child| child.into_chart_tree(format))
+            .collect();
+
+        let label = DiffLabel {
+            name,
+            status,
+            delta,
+            format,
+        };
+
+        DataTree::dir(label, own_change, children)
+    }
+}
+
+/// Size of a node excluding its children.
+///
+/// Input trees are only validated to have no child greater than its parent, so the
+/// total of the children may still exceed the parent, in which case it returns zero.
+#[inline]
+fn own_size<Size: size::Size>(total: Size, children_total: Size) -> Size {
+    if total > children_total {
+        total - children_total
+    } else {
+        Size::default()
+    }
+}
@@ -0,0 +1,92 @@
+use crate::size;
+use std::fmt::{self, Display};
+use std::iter::Sum;
+
+#[cfg(feature = "json")]
+use serde::Serialize;
+
+/// Signed change of size between two snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(
+    feature = "json",
+    serde(into = "i128", bound(serialize = "Size: Copy + Into<u64>"))
+)]
+pub enum Delta<Size> {
+    /// The size grew by the contained amount (or stayed the same).
+    Increase(Size),
+    /// The size shrank by the contained amount.
+    Decrease(Size),
+}
+
+impl<Size: size::Size> Delta<Size> {
+    /// Compute the change from `old` to `new`.
+    #[inline]
+    pub fn between(old: Size, new: Size) -> Self {
+        if new >= old {
+            Delta::Increase(new - old)
+        } else {
+            Delta::Decrease(old - new)
+        }
+    }
+
+    /// Absolute amount of the change.
+    #[inline]
+    pub fn magnitude(self) -> Size {
+        match self {
+            Delta::Increase(size) | Delta::Decrease(size) => size,
+        }
+    }
+
+    /// Return a [displayable](Display) value.
+    #[inline]
+    pub fn display(self, format: Size::DisplayFormat) -> DeltaDisplay<Size> {
+        DeltaDisplay {
+            delta: self,
+            format,
+        }
+    }
+}
+
+impl<Size: size::Size> Sum for Delta<Size> {
+    /// Add up the changes, the increases and the decreases cancel each other out.
+    fn sum<Iter: Iterator<Item = Self>>(iter: Iter) -> Self {
+        let (increase, decrease) = iter.fold(
+            (Size::default(), Size::default()),
+            |(increase, decrease), delta| match delta {
+                Delta::Increase(size) => (increase + size, decrease),
+                Delta::Decrease(size) => (increase, decrease + size),
+            },
+        );
+        Delta::between(decrease, increase)
+    }
+}
+
+impl<Size: Into<u64>> From<Delta<Size>> for i128 {
+    fn from(delta: Delta<Size>) -> Self {
+        match delta {
+            Delta::Increase(size) => size.into().into(),
+            Delta::Decrease(size) => -i128::from(size.into()),
+        }
+    }
+}
+
+/// Return type of [`Delta::display`] which implements [`Display`].
+#[derive(Debug, Clone, Copy)]
+pub struct DeltaDisplay<Size: size::Size> {
+    delta: Delta<Size>,
+    format: Size::DisplayFormat,
+}
+
+impl<Size: size::Size> Display for DeltaDisplay<Size> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let DeltaDisplay { delta, format } = *self;
+        let sign = match delta {
+            Delta::Increase(size) if size == Size::default() => "",
+            Delta::Increase(_) => "+",
+            Delta::Decrease(_) => "-",
+        };
+        let magnitude = delta.magnitude().display(format).to_string();
+        write!(f, "{sign}{}", magnitude.trim_end())
+    }
+}
@@ -0,0 +1,40 @@
+use super::{Delta, DiffStatus, DiffTree};
+use crate::size;
+
+impl<Name, Size: size::Size> DiffTree<Name, Size> {
+    /// Extract name.
+    #[inline]
+    pub fn name(&self) -> &Name {
+        &self.name
+    }
+
+    /// Extract the size in the old snapshot, if the node existed there.
+    #[inline]
+    pub fn old_size(&self) -> Option<Size> {
+        self.old_size
+    }
+
+    /// Extract the size in the new snapshot, if the node exists there.
+    #[inline]
+    pub fn new_size(&self) -> Option<Size> {
+        self.new_size
+    }
+
+    /// Extract the signed change of size.
+    #[inline]
+    pub fn delta(&self) -> Delta<Size> {
+        self.delta
+    }
+
+    /// Whether the node was added, removed, or retained.
+    #[inline]
+    pub fn status(&self) -> DiffStatus {
+        DiffStatus::from_presence(self.old_size.is_some(), self.new_size.is_some())
+    }
+
+    /// Extract children.
+    #[inline]
+    pub fn children(&self) -> &Vec<Self> {
+        &self.children
+    }
+}
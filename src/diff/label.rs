@@ -0,0 +1,62 @@
+use super::{Delta, DiffStatus};
+use crate::size;
+use crate::visualizer::{QuotableName, QuotingStyle};
+use std::ffi::OsStr;
+use std::fmt::{self, Display};
+
+/// Name of a node in the chart of a [`DiffTree`](super::DiffTree).
+///
+/// It displays the [marker](DiffStatus::marker) of the status, the original name,
+/// and the signed change of size.
+#[derive(Debug, Clone, Copy)]
+pub struct DiffLabel<Name, Size: size::Size> {
+    /// The original name of the node.
+    pub name: Name,
+    /// Whether the node was added, removed, or retained.
+    pub status: DiffStatus,
+    /// The signed change of size.
+    pub delta: Delta<Size>,
+    /// Format to be used to display [`delta`](Self::delta).
+    pub format: Size::DisplayFormat,
+}
+
+impl<Name: AsRef<OsStr>, Size: size::Size> AsRef<OsStr> for DiffLabel<Name, Size> {
+    fn as_ref(&self) -> &OsStr {
+        self.name.as_ref()
+    }
+}
+
+impl<Name: Display, Size: size::Size> Display for DiffLabel<Name, Size> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let DiffLabel {
+            name,
+            status,
+            delta,
+            format,
+        } = self;
+        write!(
+            f,
+            "{marker}{name} ({delta})",
+            marker = status.marker(),
+            delta = delta.display(*format),
+        )
+    }
+}
+
+impl<Name: QuotableName, Size: size::Size> QuotableName for DiffLabel<Name, Size> {
+    /// Write the label with only the original name quoted.
+    fn quoted(&self, style: QuotingStyle) -> String {
+        let DiffLabel {
+            name,
+            status,
+            delta,
+            format,
+        } = self;
+        format!(
+            "{marker}{name} ({delta})",
+            marker = status.marker(),
+            name = name.quoted(style),
+            delta = delta.display(*format),
+        )
+    }
+}
@@ -0,0 +1,32 @@
+/// Whether a node exists in the old snapshot, the new snapshot, or both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffStatus {
+    /// The node only exists in the new snapshot.
+    Added,
+    /// The node only exists in the old snapshot.
+    Removed,
+    /// The node exists in both snapshots.
+    Retained,
+}
+
+impl DiffStatus {
+    /// Deduce the status from the presence of the node in each snapshot.
+    #[inline]
+    pub const fn from_presence(in_old: bool, in_new: bool) -> Self {
+        match (in_old, in_new) {
+            (false, _) => DiffStatus::Added,
+            (true, false) => DiffStatus::Removed,
+            (true, true) => DiffStatus::Retained,
+        }
+    }
+
+    /// Marker to be prepended to the name of the node in the chart.
+    #[inline]
+    pub const fn marker(self) -> &'static str {
+        match self {
+            DiffStatus::Added => "[+] ",
+            DiffStatus::Removed => "[-] ",
+            DiffStatus::Retained => "",
+        }
+    }
+}
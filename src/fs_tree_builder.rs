@@ -1,14 +1,23 @@
+pub mod receive_node;
+
+pub use receive_node::ReceiveNode;
+
 use super::data_tree::DataTree;
 use super::device::DeviceBoundary;
 use super::get_size::GetSize;
+use super::group_by::GroupRecorder;
 use super::hardlink::{RecordHardlinks, RecordHardlinksArgument};
+use super::largest_files::LargestFiles;
+use super::node_metadata::{MetadataFields, NodeMetadata};
 use super::os_string_display::OsStringDisplay;
 use super::reporter::error_report::Operation::{AccessEntry, ReadDirectory, SymlinkMetadata};
 use super::reporter::{ErrorReport, Event, Reporter};
 use super::size;
-use super::tree_builder::{Info, TreeBuilder};
+use super::tree_builder::{Info, build_with_metadata};
+use derive_setters::Setters;
 use device_id::get_device_id;
 use pipe_trait::Pipe;
+use smart_default::SmartDefault;
 use std::fs::{read_dir, symlink_metadata};
 use std::path::PathBuf;
 
@@ -37,6 +46,8 @@ use std::path::PathBuf;
 /// };
 /// let data_tree: DataTree<OsStringDisplay, Bytes> = builder.into();
 /// ```
+///
+/// Additional inputs can be provided by [`FsTreeBuilder::build_with`].
 #[derive(Debug)]
 pub struct FsTreeBuilder<'a, Size, SizeGetter, HardlinksRecorder, Report>
 where
@@ -59,6 +70,37 @@ where
     pub max_depth: u64,
 }
 
+/// Optional inputs of [`FsTreeBuilder::build_with`].
+///
+/// **Example:**
+///
+/// ```
+/// # use parallel_disk_usage::fs_tree_builder::Extras;
+/// # use parallel_disk_usage::node_metadata::MetadataFields;
+/// # use parallel_disk_usage::size::Bytes;
+/// let extras = Extras::<Bytes>::default().with_metadata_fields(MetadataFields {
+///     count: true,
+///     ..MetadataFields::NONE
+/// });
+/// ```
+#[derive(Debug, SmartDefault, Clone, Copy, Setters)]
+#[setters(prefix = "with_")]
+#[non_exhaustive]
+pub struct Extras<'a, Size: size::Size> {
+    /// Which [metadata](NodeMetadata) to collect for each node.
+    pub metadata_fields: MetadataFields,
+    /// Records the sizes of the entries by category, if any.
+    #[setters(strip_option)]
+    pub group_recorder: Option<&'a GroupRecorder<Size>>,
+    /// Keeps track of the largest files, if any.
+    #[setters(strip_option)]
+    pub largest_files: Option<&'a LargestFiles<Size>>,
+    /// Receives every node within [`FsTreeBuilder::max_depth`] as soon as its subtree is built,
+    /// if any. The children of the received nodes are not kept in the resulting tree.
+    #[setters(strip_option)]
+    pub node_receiver: Option<&'a dyn ReceiveNode<Size>>,
+}
+
 impl<'a, Size, SizeGetter, HardlinksRecorder, Report>
     From<FsTreeBuilder<'a, Size, SizeGetter, HardlinksRecorder, Report>>
     for DataTree<OsStringDisplay, Size>
@@ -70,6 +112,20 @@ where
 {
     /// Create a [`DataTree`] from an [`FsTreeBuilder`].
     fn from(builder: FsTreeBuilder<Size, SizeGetter, HardlinksRecorder, Report>) -> Self {
+        builder.build_with(Extras::default())
+    }
+}
+
+impl<'a, Size, SizeGetter, HardlinksRecorder, Report>
+    FsTreeBuilder<'a, Size, SizeGetter, HardlinksRecorder, Report>
+where
+    Report: Reporter<Size> + Sync + ?Sized,
+    Size: size::Size + Send + Sync,
+    SizeGetter: GetSize<Size = Size> + Sync,
+    HardlinksRecorder: RecordHardlinks<Size, Report> + Sync + ?Sized,
+{
+    /// Create a [`DataTree`] with the [additional inputs](Extras).
+    pub fn build_with(self, extras: Extras<'a, Size>) -> DataTree<OsStringDisplay, Size> {
         let FsTreeBuilder {
             root,
             size_getter,
@@ -77,7 +133,13 @@ where
             reporter,
             device_boundary,
             max_depth,
-        } = builder;
+        } = self;
+        let Extras {
+            metadata_fields,
+            group_recorder,
+            largest_files,
+            node_receiver,
+        } = extras;
 
         // `root` would be inspected multiple times, but its impact on performance is insignificant
         // before the (usually) massive fs tree `root` contains.
@@ -96,23 +158,19 @@ where
             },
         };
 
-        TreeBuilder::<PathBuf, OsStringDisplay, Size, _, _> {
-            name: OsStringDisplay::os_string_from(&root),
-
-            path: root,
-
-            get_info: |path| {
-                let (is_dir, size, same_device) = match symlink_metadata(path) {
+        let name = OsStringDisplay::os_string_from(&root);
+        build_with_metadata::<PathBuf, OsStringDisplay, Size, _, _, _>(
+            root,
+            name,
+            |path| {
+                let (is_dir, size, same_device, metadata) = match symlink_metadata(path) {
                     Err(error) => {
                         reporter.report(Event::EncounterError(ErrorReport {
                             operation: SymlinkMetadata,
                             path,
                             error,
                         }));
-                        return Info {
-                            size: Size::default(),
-                            children: Vec::new(),
-                        };
+                        return (Info::default(), None);
                     }
                     Ok(stats) => {
                         // `stats` should be dropped ASAP to avoid piling up kernel memory usage
@@ -126,7 +184,14 @@ where
                                 path, &stats, size, reporter,
                             ))
                             .ok(); // ignore the error for now
-                        (is_dir, size, same_device)
+                        if let Some(group_recorder) = group_recorder {
+                            group_recorder.record(path, &stats, size);
+                        }
+                        if let Some(largest_files) = largest_files {
+                            largest_files.record(path, &stats, size);
+                        }
+                        let metadata = NodeMetadata::collect(&stats, metadata_fields);
+                        (is_dir, size, same_device, metadata)
                     }
                 };
 
@@ -138,10 +203,13 @@ where
                                 path,
                                 error,
                             }));
-                            return Info {
-                                size,
-                                children: Vec::new(),
-                            };
+                            return (
+                                Info {
+                                    size,
+                                    children: Vec::new(),
+                                },
+                                metadata,
+                            );
                         }
                         Ok(entries) => entries,
                     }
@@ -161,14 +229,18 @@ where
                     Vec::new()
                 };
 
-                Info { size, children }
+                (Info { size, children }, metadata)
             },
-
-            join_path: |prefix, name| prefix.join(&name.0),
-
+            |prefix, name| prefix.join(&name.0),
             max_depth,
-        }
-        .into()
+            |path, parent, depth, mut tree| {
+                if let Some(node_receiver) = node_receiver {
+                    node_receiver.receive(path, parent.map(PathBuf::as_path), depth, &tree);
+                    tree.par_retain(|_, _| false);
+                }
+                tree
+            },
+        )
     }
 }
 
@@ -0,0 +1,20 @@
+use crate::data_tree::DataTree;
+use crate::os_string_display::OsStringDisplay;
+use crate::size;
+use std::fmt::Debug;
+use std::path::Path;
+
+/// Receive the nodes of a tree that is being built by [`FsTreeBuilder`](super::FsTreeBuilder).
+pub trait ReceiveNode<Size: size::Size>: Debug + Sync {
+    /// Handle a `node` whose subtree has just been built.
+    ///
+    /// `path` is the path of the node, `parent` is the path of its parent, and `depth` is its
+    /// number of ancestors. The children of the node have been received before the node itself.
+    fn receive(
+        &self,
+        path: &Path,
+        parent: Option<&Path>,
+        depth: u64,
+        node: &DataTree<OsStringDisplay, Size>,
+    );
+}
@@ -0,0 +1,126 @@
+#[cfg(feature = "category-rules")]
+pub mod category_rules;
+pub mod recorder;
+
+#[cfg(feature = "category-rules")]
+pub use category_rules::{CategoryRules, ParseCategoryRulesError, RuleMatch};
+pub use recorder::GroupRecorder;
+
+use crate::node_metadata::EntryType;
+use std::ffi::OsStr;
+use std::fmt::{self, Display, Formatter};
+use std::fs::Metadata;
+use std::path::Path;
+
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// Property by which the scanned entries are grouped into categories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum GroupBy {
+    /// Extension of the name, such as `.log`. Directories are not counted.
+    Extension,
+    /// User ID of the owner (POSIX only).
+    Owner,
+    /// Group ID of the owner (POSIX only).
+    Group,
+    /// Kind of the entry: file, dir, symlink, or other.
+    Type,
+}
+
+/// How the scanned entries are grouped into categories.
+#[derive(Debug, Clone)]
+pub enum Grouping {
+    /// Group the entries by a property, and list the largest entries of each category.
+    By(GroupBy),
+    /// Group the entries by a set of rules, and list the directories that contribute the
+    /// most to each category.
+    #[cfg(feature = "category-rules")]
+    Rules(CategoryRules),
+}
+
+impl GroupBy {
+    /// Name of the property, as it appears in the `--group-by` flag.
+    pub const fn name(self) -> &'static str {
+        match self {
+            GroupBy::Extension => "extension",
+            GroupBy::Owner => "owner",
+            GroupBy::Group => "group",
+            GroupBy::Type => "type",
+        }
+    }
+
+    /// Name of the category of an entry, or [`None`] if the entry is not counted.
+    pub fn category<'a>(self, path: &'a Path, stats: &Metadata) -> Option<CategoryName<'a>> {
+        match self {
+            GroupBy::Extension => {
+                if stats.is_dir() {
+                    return None;
+                }
+                let category = match path.extension() {
+                    Some(extension) => CategoryName::Extension(extension),
+                    None => CategoryName::Fixed("(no extension)"),
+                };
+                Some(category)
+            }
+            GroupBy::Owner => Some(owner_category(stats)),
+            GroupBy::Group => Some(group_category(stats)),
+            GroupBy::Type => {
+                let name = match EntryType::from(stats.file_type()) {
+                    EntryType::File => "file",
+                    EntryType::Dir => "dir",
+                    EntryType::Symlink => "symlink",
+                    EntryType::Other => "other",
+                };
+                Some(CategoryName::Fixed(name))
+            }
+        }
+    }
+}
+
+/// Name of a category of [`GroupBy`], which is only turned into text when it is displayed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CategoryName<'a> {
+    /// Extension of a file name, which is displayed with a leading dot.
+    Extension(&'a OsStr),
+    /// User ID of the owner.
+    Owner(u32),
+    /// Group ID of the owner.
+    Group(u32),
+    /// Name that does not depend on the entry.
+    Fixed(&'static str),
+}
+
+impl Display for CategoryName<'_> {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CategoryName::Extension(extension) => write!(formatter, ".{}", extension.display()),
+            CategoryName::Owner(uid) => write!(formatter, "uid {uid}"),
+            CategoryName::Group(gid) => write!(formatter, "gid {gid}"),
+            CategoryName::Fixed(name) => formatter.write_str(name),
+        }
+    }
+}
+
+#[cfg(unix)]
+fn owner_category(stats: &Metadata) -> CategoryName<'static> {
+    use std::os::unix::fs::MetadataExt;
+    CategoryName::Owner(stats.uid())
+}
+
+#[cfg(unix)]
+fn group_category(stats: &Metadata) -> CategoryName<'static> {
+    use std::os::unix::fs::MetadataExt;
+    CategoryName::Group(stats.gid())
+}
+
+#[cfg(not(unix))]
+fn owner_category(_: &Metadata) -> CategoryName<'static> {
+    CategoryName::Fixed("(unknown)")
+}
+
+#[cfg(not(unix))]
+fn group_category(_: &Metadata) -> CategoryName<'static> {
+    CategoryName::Fixed("(unknown)")
+}
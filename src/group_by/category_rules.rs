@@ -0,0 +1,150 @@
+use derive_more::{Display, Error};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Rules that assign named categories to the scanned entries by glob patterns.
+///
+/// **Format:** The rules are written in TOML as an array of tables named `category`,
+/// each of which has a `name` and a list of `globs`:
+///
+/// ```toml
+/// [[category]]
+/// name = "build artifacts"
+/// globs = ["**/target", "**/*.o"]
+///
+/// [[category]]
+/// name = "VCS metadata"
+/// globs = ["**/.git"]
+/// ```
+///
+/// **Matching:** The patterns are matched against the whole path of an entry and of each of
+/// its ancestors, so a pattern that matches a directory also covers everything inside it.
+/// `*` also matches `/`. An entry belongs to the first category that matches it, or to
+/// [`UNCATEGORIZED`](Self::UNCATEGORIZED) if none does. The outermost path that matches is
+/// the one the entry is attributed to.
+#[derive(Debug, Clone)]
+pub struct CategoryRules {
+    /// Name and patterns of each category, in the order of precedence.
+    categories: Vec<(String, GlobSet)>,
+}
+
+/// Error that occurs when [`CategoryRules::from_toml`] fails.
+#[derive(Debug, Display, Error)]
+#[non_exhaustive]
+pub enum ParseCategoryRulesError {
+    /// The text is not valid TOML or does not describe a list of categories.
+    #[display("{_0}")]
+    Toml(toml::de::Error),
+    /// A pattern is not a valid glob.
+    #[display("{_0}")]
+    Glob(globset::Error),
+}
+
+/// Content of a rules file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    category: Vec<Rule>,
+}
+
+/// A `[[category]]` table of a rules file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Rule {
+    name: String,
+    globs: Vec<String>,
+}
+
+impl CategoryRules {
+    /// Name of the category of the entries that match none of the rules.
+    pub const UNCATEGORIZED: &str = "(uncategorized)";
+
+    /// Parse the rules from TOML text.
+    pub fn from_toml(text: &str) -> Result<Self, ParseCategoryRulesError> {
+        let RulesFile { category } = toml::from_str(text).map_err(ParseCategoryRulesError::Toml)?;
+        let categories = category
+            .into_iter()
+            .map(|Rule { name, globs }| {
+                let mut builder = GlobSetBuilder::new();
+                for glob in globs {
+                    builder.add(Glob::new(&glob).map_err(ParseCategoryRulesError::Glob)?);
+                }
+                let globs = builder.build().map_err(ParseCategoryRulesError::Glob)?;
+                Ok((name, globs))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(CategoryRules { categories })
+    }
+
+    /// Name of the category of the entry at `path`, and the outermost of `path` and its
+    /// ancestors that matches it, or [`None`] if the entry is uncategorized.
+    ///
+    /// Every ancestor of `path` is matched against the rules. When the entries of a tree are
+    /// categorized from the top down, [`locate`](Self::locate) is cheaper.
+    pub fn category<'a>(&'a self, path: &'a Path) -> (&'a str, Option<&'a Path>) {
+        self.resolve(path, self.locate_through_ancestors(path))
+    }
+
+    /// Find the rule that matches the entry at `path` by [locating](Self::locate) each of its
+    /// ancestors from the outermost.
+    pub fn locate_through_ancestors(&self, path: &Path) -> Option<RuleMatch> {
+        let ancestors: Vec<_> = path.ancestors().collect();
+        ancestors
+            .into_iter()
+            .rev()
+            .fold(None, |parent, ancestor| self.locate(ancestor, parent))
+    }
+
+    /// Find the rule that matches the entry at `path`, given the match of its parent.
+    ///
+    /// Only the categories that take precedence over that of the parent are matched against
+    /// `path`, so each entry is matched at most once against each category.
+    pub fn locate(&self, path: &Path, parent: Option<RuleMatch>) -> Option<RuleMatch> {
+        let inherited = parent.map(
+            |RuleMatch {
+                 category,
+                 levels_up,
+             }| RuleMatch {
+                category,
+                levels_up: levels_up + 1,
+            },
+        );
+        let candidates = inherited.map_or(self.categories.len(), |found| found.category);
+        self.categories[..candidates]
+            .iter()
+            .position(|(_, globs)| globs.is_match(path))
+            .map(|category| RuleMatch {
+                category,
+                levels_up: 0,
+            })
+            .or(inherited)
+    }
+
+    /// Name of the category of the entry at `path` and the path that matches it, from the
+    /// result of [`locate`](Self::locate).
+    pub fn resolve<'a>(
+        &'a self,
+        path: &'a Path,
+        found: Option<RuleMatch>,
+    ) -> (&'a str, Option<&'a Path>) {
+        let Some(RuleMatch {
+            category,
+            levels_up,
+        }) = found
+        else {
+            return (CategoryRules::UNCATEGORIZED, None);
+        };
+        let (name, _) = &self.categories[category];
+        (name.as_str(), path.ancestors().nth(levels_up))
+    }
+}
+
+/// Rule that matches an entry, as found by [`CategoryRules::locate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuleMatch {
+    /// Index of the matching category in the order of precedence.
+    category: usize,
+    /// Number of levels between the entry and the outermost path that matches the category.
+    levels_up: usize,
+}
@@ -0,0 +1,288 @@
+use super::Grouping;
+use crate::data_tree::DataTree;
+use crate::os_string_display::OsStringDisplay;
+use crate::size;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt::Write;
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+#[cfg(feature = "category-rules")]
+use super::{CategoryRules, RuleMatch};
+#[cfg(feature = "category-rules")]
+use dashmap::DashMap;
+
+/// Total size of a category and the paths that contribute to it.
+#[derive(Debug)]
+struct Category<Size> {
+    /// Combined size of all the entries in the category.
+    total: Size,
+    /// The largest entries of the category with [`Grouping::By`], the smallest on top.
+    largest: BinaryHeap<Reverse<(Size, PathBuf)>>,
+    /// Map each contributing path to the size it contributes with `Grouping::Rules`.
+    ///
+    /// It is pruned to the largest contributors whenever it grows too big, so it never holds
+    /// more than twice `CONTRIBUTORS_PER_PATH` paths for each path that is listed.
+    contributors: HashMap<PathBuf, Size>,
+}
+
+impl<Size: size::Size> Default for Category<Size> {
+    fn default() -> Self {
+        Category {
+            total: Size::default(),
+            largest: BinaryHeap::new(),
+            contributors: HashMap::new(),
+        }
+    }
+}
+
+impl<Size: size::Size> Category<Size> {
+    /// Keep `path` amongst the `max_paths` largest entries if it is large enough.
+    ///
+    /// The size is compared against the smallest kept entry first, so that the paths of the
+    /// entries that would be dropped right away are never copied.
+    fn offer(&mut self, path: &Path, size: Size, max_paths: usize) {
+        if self.largest.len() >= max_paths {
+            match self.largest.peek() {
+                Some(Reverse((smallest, _))) if size > *smallest => {
+                    self.largest.pop();
+                }
+                _ => return,
+            }
+        }
+        self.largest.push(Reverse((size, path.to_path_buf())));
+    }
+
+    /// Add `size` to the contribution of `path`, and drop the smallest contributors if there
+    /// are too many of them.
+    ///
+    /// The sizes of the dropped contributors stay in the total of the category. A dropped
+    /// contributor that contributes again starts over from zero, so the listed contributions
+    /// are a lower bound when the category has many more contributors than are listed.
+    #[cfg(feature = "category-rules")]
+    fn contribute(&mut self, path: &Path, size: Size, max_paths: usize) {
+        if let Some(contribution) = self.contributors.get_mut(path) {
+            *contribution += size;
+            return;
+        }
+        self.contributors.insert(path.to_path_buf(), size);
+        let capacity = max_paths.saturating_mul(CONTRIBUTORS_PER_PATH);
+        if self.contributors.len() > capacity.saturating_mul(2) {
+            let contributors = std::mem::take(&mut self.contributors);
+            self.contributors = largest(contributors, capacity).into_iter().collect();
+        }
+    }
+
+    /// Add the sizes and the paths that were recorded by another thread.
+    fn merge(&mut self, other: Self, max_paths: usize) {
+        self.total += other.total;
+        for Reverse((size, path)) in other.largest {
+            self.offer(&path, size, max_paths);
+        }
+        for (path, size) in other.contributors {
+            *self.contributors.entry(path).or_default() += size;
+        }
+    }
+}
+
+/// Categories recorded by a single thread.
+#[derive(Debug, Default)]
+struct Shard<Size> {
+    /// Map each category name to its total size and its contributors.
+    categories: HashMap<String, Category<Size>>,
+    /// Buffer to write the name of a category of [`Grouping::By`] into, so that looking up an
+    /// existing category does not allocate.
+    name: String,
+}
+
+/// Number of contributors to keep track of for each path that is listed under a category,
+/// so that a contributor that has not reached the top yet is not dropped too early.
+#[cfg(feature = "category-rules")]
+const CONTRIBUTORS_PER_PATH: usize = 16;
+
+/// Keep only the `max_paths` largest `contributors`, from the largest.
+fn largest<Size: size::Size>(
+    contributors: HashMap<PathBuf, Size>,
+    max_paths: usize,
+) -> Vec<(PathBuf, Size)> {
+    let mut contributors: Vec<_> = contributors.into_iter().collect();
+    contributors.sort_by_key(|(_, size)| Reverse(*size));
+    contributors.truncate(max_paths);
+    contributors
+}
+
+/// Aggregate the sizes of the scanned entries into categories.
+///
+/// It is fed by [`FsTreeBuilder`](crate::fs_tree_builder::FsTreeBuilder) during the scan,
+/// then turned into a two-level [`DataTree`] by [`into_tree`](Self::into_tree).
+#[derive(Debug)]
+pub struct GroupRecorder<Size> {
+    /// How the entries are grouped.
+    grouping: Grouping,
+    /// Maximum number of paths to list under each category.
+    max_paths: usize,
+    /// The categories recorded by each thread, which are combined by [`into_tree`](Self::into_tree).
+    ///
+    /// Each thread of the pool records into the shard of its own index, so the locks are
+    /// hardly ever contended.
+    shards: Vec<Mutex<Shard<Size>>>,
+    /// The rules that match each directory with `Grouping::Rules`, so that the entries inside
+    /// it only have to be matched against the rules that take precedence.
+    #[cfg(feature = "category-rules")]
+    rule_matches: DashMap<PathBuf, Option<RuleMatch>>,
+}
+
+impl<Size: size::Size> GroupRecorder<Size> {
+    /// Number of paths to list under each category when not specified.
+    pub const DEFAULT_MAX_PATHS: usize = 10;
+
+    /// Create an empty recorder.
+    pub fn new(grouping: Grouping, max_paths: usize) -> Self {
+        let shards = (0..rayon::current_num_threads())
+            .map(|_| Mutex::default())
+            .collect();
+        GroupRecorder {
+            grouping,
+            max_paths,
+            shards,
+            #[cfg(feature = "category-rules")]
+            rule_matches: DashMap::new(),
+        }
+    }
+
+    /// Add the size of an entry, excluding its descendants, to its category.
+    ///
+    /// With [`Grouping::By`], the entry itself is a contributor. With `Grouping::Rules`,
+    /// the contributor is the path that matched the rules, which is the entry or one of its
+    /// ancestors. An uncategorized entry contributes through itself if it is a directory,
+    /// or through its parent otherwise.
+    pub fn record(&self, path: &Path, stats: &Metadata, size: Size) {
+        // threads outside the pool share the shards, which are still guarded by the locks
+        let index = rayon::current_thread_index().unwrap_or(0) % self.shards.len();
+        let mut shard = self.shards[index]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let Shard { categories, name } = &mut *shard;
+        let (name, contributor) = match &self.grouping {
+            Grouping::By(group_by) => {
+                let Some(category) = group_by.category(path, stats) else {
+                    return;
+                };
+                name.clear();
+                write!(name, "{category}").expect("write to a string");
+                (name.as_str(), path)
+            }
+            #[cfg(feature = "category-rules")]
+            Grouping::Rules(rules) => {
+                let found = self.locate(rules, path, stats);
+                let (name, matched) = rules.resolve(path, found);
+                let contributor = match (matched, path.parent()) {
+                    (Some(matched), _) => matched,
+                    (None, Some(parent)) if !stats.is_dir() && parent != Path::new("") => parent,
+                    (None, _) => path,
+                };
+                (name, contributor)
+            }
+        };
+        if let Some(category) = categories.get_mut(name) {
+            self.add(category, contributor, size);
+        } else {
+            let mut category = Category::default();
+            self.add(&mut category, contributor, size);
+            categories.insert(name.to_string(), category);
+        }
+    }
+
+    /// Find the rule that matches the entry at `path` from the match of its parent, and keep
+    /// the match of the entry if it is a directory.
+    #[cfg(feature = "category-rules")]
+    fn locate(&self, rules: &CategoryRules, path: &Path, stats: &Metadata) -> Option<RuleMatch> {
+        let parent = path
+            .parent()
+            .and_then(|parent| self.rule_matches.get(parent))
+            .map(|found| *found);
+        let found = match parent {
+            Some(parent) => rules.locate(path, parent),
+            // the roots of the scan have no recorded parent
+            None => rules.locate_through_ancestors(path),
+        };
+        if stats.is_dir() {
+            self.rule_matches.insert(path.to_path_buf(), found);
+        }
+        found
+    }
+
+    /// Add the size of an entry to its `category` through its `contributor`.
+    fn add(&self, category: &mut Category<Size>, contributor: &Path, size: Size) {
+        category.total += size;
+        if self.max_paths == 0 {
+            return;
+        }
+        match &self.grouping {
+            // every entry is its own contributor, so only the largest ones need to be kept
+            Grouping::By(_) => category.offer(contributor, size, self.max_paths),
+            #[cfg(feature = "category-rules")]
+            Grouping::Rules(_) => category.contribute(contributor, size, self.max_paths),
+        }
+    }
+
+    /// Create a tree whose root is named `root_name`, whose children are the categories,
+    /// and whose grandchildren are the largest contributors of each category.
+    ///
+    /// The sizes of the contributors that are not listed remain in their categories.
+    pub fn into_tree(self, root_name: OsStringDisplay) -> DataTree<OsStringDisplay, Size> {
+        let GroupRecorder {
+            max_paths, shards, ..
+        } = self;
+        let mut categories = HashMap::<String, Category<Size>>::new();
+        for shard in shards {
+            let shard = shard.into_inner().unwrap_or_else(PoisonError::into_inner);
+            for (name, category) in shard.categories {
+                match categories.get_mut(&name) {
+                    Some(existing) => existing.merge(category, max_paths),
+                    None => {
+                        categories.insert(name, category);
+                    }
+                }
+            }
+        }
+        let categories = categories
+            .into_iter()
+            .map(
+                |(
+                    name,
+                    Category {
+                        total,
+                        largest: largest_entries,
+                        contributors,
+                    },
+                )| {
+                    let contributors = if contributors.is_empty() {
+                        largest_entries
+                            .into_sorted_vec()
+                            .into_iter()
+                            .map(|Reverse((size, path))| (path, size))
+                            .collect()
+                    } else {
+                        largest(contributors, max_paths)
+                    };
+                    let listed = contributors.iter().map(|(_, size)| *size).sum();
+                    let contributors = contributors
+                        .into_iter()
+                        .map(|(path, size)| {
+                            DataTree::file(OsStringDisplay::os_string_from(path), size)
+                        })
+                        .collect();
+                    DataTree::dir(
+                        OsStringDisplay::os_string_from(name),
+                        total - listed,
+                        contributors,
+                    )
+                },
+            )
+            .collect();
+        DataTree::dir(root_name, Size::default(), categories)
+    }
+}
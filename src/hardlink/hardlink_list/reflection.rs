@@ -6,6 +6,8 @@ use dashmap::DashMap;
 use derive_more::{Display, Error, Into, IntoIterator};
 use into_sorted::IntoSortedUnstable;
 use pipe_trait::Pipe;
+use std::collections::HashSet;
+use std::path::Path;
 
 #[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
@@ -46,6 +48,30 @@ impl<Size> Reflection<Size> {
     }
 }
 
+impl<Size> Reflection<Size> {
+    /// Move the paths of the links from under `old_root` to under `new_root`.
+    ///
+    /// Paths that are not under `old_root` are moved under `new_root` as a whole.
+    pub fn rebase(self, old_root: &Path, new_root: &Path) -> Self {
+        self.0
+            .into_iter()
+            .map(|entry| ReflectionEntry {
+                paths: entry
+                    .paths
+                    .into_iter()
+                    .map(|path| match path.strip_prefix(old_root) {
+                        Ok(suffix) => new_root.join(suffix),
+                        Err(_) => new_root.join(path),
+                    })
+                    .collect::<HashSet<_>>()
+                    .into(),
+                ..entry
+            })
+            .collect::<Vec<_>>()
+            .pipe(Reflection)
+    }
+}
+
 /// An entry in [`Reflection`].
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
@@ -2,6 +2,8 @@ use super::{AddError, HardlinkList, NumberOfLinksConflictError, SizeConflictErro
 use crate::size::Bytes;
 use pipe_trait::Pipe;
 use pretty_assertions::{assert_eq, assert_ne};
+use std::collections::HashSet;
+use std::path::PathBuf;
 
 const TABLE: &[(u64, u64, u64, u64, &str)] = &[
     // ino, dev, size, links, path
@@ -182,3 +184,24 @@ fn same_ino_on_different_devices_are_treated_separately() {
     assert_eq!(entries[1].ino, 100.into());
     assert_eq!(entries[1].paths.len(), 2);
 }
+
+#[test]
+fn rebase_reflection() {
+    let list = HardlinkList::<Bytes>::new();
+    list.add(123.into(), 0.into(), 100.into(), 2, "root/a".as_ref())
+        .expect("add a");
+    list.add(123.into(), 0.into(), 100.into(), 2, "other/b".as_ref())
+        .expect("add b");
+    let actual = list
+        .into_reflection()
+        .rebase("root".as_ref(), "label".as_ref());
+    let paths: HashSet<_> = actual
+        .iter()
+        .flat_map(|entry| entry.paths.0.iter().cloned())
+        .collect();
+    let expected: HashSet<PathBuf> = ["label/a", "label/other/b"]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    assert_eq!(paths, expected);
+}
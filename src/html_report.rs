@@ -0,0 +1,84 @@
+use crate::bytes_format::BytesFormat;
+use std::fmt::{self, Display, Formatter};
+
+/// Page that visualizes the embedded data with a zoomable treemap, an icicle chart,
+/// a sortable table, and a summary of the detected hardlinks.
+const TEMPLATE: &str = include_str!("html_report/template.html");
+
+/// Placeholder of [`TEMPLATE`] to be replaced by the data.
+const DATA_PLACEHOLDER: &str = "{{DATA}}";
+
+/// Placeholder of [`TEMPLATE`] to be replaced by the description of the format of the sizes.
+const BYTES_FORMAT_PLACEHOLDER: &str = "{{BYTES_FORMAT}}";
+
+/// Standalone HTML page which visualizes a JSON document created by `--json-output`.
+///
+/// The [`Display`] trait can be used to create the page. The styles, the scripts, and
+/// the data are all inlined, so the page works without network access.
+///
+/// **Example:**
+///
+/// ```no_run
+/// # use parallel_disk_usage::bytes_format::BytesFormat;
+/// # use parallel_disk_usage::html_report::HtmlReport;
+/// # fn _wrapper(json: String) {
+/// let report = HtmlReport {
+///     json: &json,
+///     bytes_format: BytesFormat::MetricUnits,
+/// };
+/// print!("{report}");
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct HtmlReport<'a> {
+    /// The serialized [`JsonData`](crate::json_data::JsonData).
+    pub json: &'a str,
+    /// Format to be used to display the sizes of the data whose unit is bytes.
+    pub bytes_format: BytesFormat,
+}
+
+impl HtmlReport<'_> {
+    /// Describe [`bytes_format`](Self::bytes_format) as a JSON value for the scripts of the page.
+    fn fmt_bytes_format(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Some(formatter) = self.bytes_format.formatter() else {
+            return write!(f, "null");
+        };
+        let scale_base = formatter.scale_base();
+        let suffix_style = formatter.suffix_style();
+        write!(f, "{{\"scale-base\":{scale_base},\"exponent\":")?;
+        match formatter.exponent() {
+            Some(exponent) => write!(f, "{exponent}")?,
+            None => write!(f, "null")?,
+        }
+        write!(
+            f,
+            ",\"precision\":{},\"big-suffix\":\"{}\",\"small-suffix\":\"{}\"}}",
+            formatter.precision(),
+            suffix_style.big_suffix(scale_base),
+            suffix_style.small_suffix(),
+        )
+    }
+}
+
+impl Display for HtmlReport<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (head, rest) = TEMPLATE
+            .split_once(DATA_PLACEHOLDER)
+            .expect("template should contain the placeholder of the data");
+        let (middle, tail) = rest
+            .split_once(BYTES_FORMAT_PLACEHOLDER)
+            .expect("template should contain the placeholder of the bytes format");
+        write!(f, "{head}")?;
+        // `<` only appears inside JSON strings where it can be escaped, which prevents
+        // names such as `</script>` from ending the element that embeds the data.
+        for (index, chunk) in self.json.split('<').enumerate() {
+            if index > 0 {
+                write!(f, "\\u003c")?;
+            }
+            write!(f, "{chunk}")?;
+        }
+        write!(f, "{middle}")?;
+        self.fmt_bytes_format(f)?;
+        write!(f, "{tail}")
+    }
+}
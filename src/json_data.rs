@@ -1,14 +1,19 @@
 pub mod binary_version;
+pub mod scan_header;
 pub mod schema_version;
 
 pub use binary_version::BinaryVersion;
+pub use scan_header::{ScanHeader, ScanQuantity, ScanRoot, ScanSettings};
 pub use schema_version::SchemaVersion;
 
 use crate::data_tree::DataTreeReflection;
 use crate::hardlink::{HardlinkListReflection, SharedLinkSummary};
+use crate::os_string_display::OsStringDisplay;
 use crate::size::{self, Blocks, Bytes};
 use derive_more::{Deref, DerefMut, From, TryInto};
 use smart_default::SmartDefault;
+use std::mem;
+use std::path::Path;
 
 #[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
@@ -30,6 +35,75 @@ pub struct JsonShared<Size: size::Size> {
         serde(skip_serializing_if = "JsonShared::skip_summary")
     )]
     pub summary: Option<SharedLinkSummary<Size>>,
+    /// Detailed lists of the hardlinks of the combined snapshots, one for each snapshot.
+    ///
+    /// They are kept apart because snapshots of different hosts may reuse the same inode
+    /// numbers for unrelated files.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub sections: Vec<JsonSharedSection<Size>>,
+}
+
+/// Hardlinks of one of the snapshots combined by [`JsonTree::merge`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "kebab-case"))]
+pub struct JsonSharedSection<Size: size::Size> {
+    /// Name of the root of the snapshot in the combined tree.
+    pub root: OsStringDisplay,
+    /// Detailed list of the hardlinks detected in the snapshot.
+    pub details: HardlinkListReflection<Size>,
+}
+
+impl<Size: size::Size> JsonShared<Size> {
+    /// Summary of the hardlinks, calculated from the details if it is absent.
+    pub fn summarize(&self) -> Option<SharedLinkSummary<Size>> {
+        if let Some(summary) = self.summary {
+            return Some(summary);
+        }
+        self.details
+            .iter()
+            .chain(self.sections.iter().map(|section| &section.details))
+            .map(HardlinkListReflection::summarize)
+            .reduce(|left, right| left + right)
+    }
+
+    /// Combine the `"shared"` fields of independent scans, each of which is labeled by the
+    /// name of its root.
+    ///
+    /// The details of each scan become a [section](JsonSharedSection) of its own, so the
+    /// entries of different scans are never united. The summaries are added together, each
+    /// of which is calculated from the details of its scan if it is absent.
+    pub fn merge(labeled: impl IntoIterator<Item = (OsStringDisplay, Self)>) -> Self {
+        let labeled: Vec<_> = labeled.into_iter().collect();
+        let has_summary = labeled.iter().any(|(_, shared)| shared.summary.is_some());
+        let summary = labeled
+            .iter()
+            .filter_map(|(_, shared)| shared.summarize())
+            .reduce(|left, right| left + right)
+            .filter(|_| has_summary);
+
+        let sections = labeled
+            .into_iter()
+            .flat_map(|(root, shared)| {
+                let JsonShared {
+                    details, sections, ..
+                } = shared;
+                details
+                    .map(|details| JsonSharedSection { root, details })
+                    .into_iter()
+                    .chain(sections)
+            })
+            .collect();
+
+        JsonShared {
+            details: None,
+            summary,
+            sections,
+        }
+    }
 }
 
 #[cfg(feature = "json")]
@@ -50,7 +124,9 @@ impl<Size: size::Size> JsonShared<Size> {
 
     /// Decide whether to skip serializing.
     fn skip(&self) -> bool {
-        JsonShared::skip_details(&self.details) && JsonShared::skip_summary(&self.summary)
+        JsonShared::skip_details(&self.details)
+            && JsonShared::skip_summary(&self.summary)
+            && self.sections.is_empty()
     }
 }
 
@@ -62,7 +138,7 @@ pub struct JsonTree<Size: size::Size> {
     /// The main data of the tree.
     #[deref]
     #[deref_mut]
-    pub tree: DataTreeReflection<String, Size>,
+    pub tree: DataTreeReflection<OsStringDisplay, Size>,
     /// Optional list of shared inodes, their sizes, and their many links.
     #[cfg_attr(
         feature = "json",
@@ -71,6 +147,54 @@ pub struct JsonTree<Size: size::Size> {
     pub shared: JsonShared<Size>,
 }
 
+impl<Size: size::Size> JsonTree<Size> {
+    /// Rename the root of the tree, and move the paths of the shared links along with it.
+    pub fn rename_root(&mut self, name: OsStringDisplay) {
+        let old_root = mem::replace(&mut self.tree.name, name);
+        let old_root: &Path = old_root.as_os_str().as_ref();
+        let new_root: &Path = self.tree.name.as_os_str().as_ref();
+        self.shared.details = self
+            .shared
+            .details
+            .take()
+            .map(|details| details.rebase(old_root, new_root));
+        self.shared.sections = mem::take(&mut self.shared.sections)
+            .into_iter()
+            .map(|JsonSharedSection { root, details }| {
+                let root: &Path = root.as_os_str().as_ref();
+                let root = match root.strip_prefix(old_root) {
+                    Ok(suffix) => new_root.join(suffix),
+                    Err(_) => new_root.join(root),
+                };
+                JsonSharedSection {
+                    root: OsStringDisplay::os_string_from(root),
+                    details: details.rebase(old_root, new_root),
+                }
+            })
+            .collect();
+    }
+
+    /// Graft the trees of independent scans under a synthetic root and combine their
+    /// `"shared"` fields.
+    ///
+    /// The size of the root is the total size of the grafted trees. The roots of the trees
+    /// should have been [renamed](JsonTree::rename_root) to distinct names beforehand, so
+    /// that the paths of the shared links point into the combined tree.
+    pub fn merge(root_name: OsStringDisplay, trees: impl IntoIterator<Item = Self>) -> Self {
+        let (children, sections): (Vec<_>, Vec<_>) = trees
+            .into_iter()
+            .map(|JsonTree { tree, shared }| {
+                let label = tree.name.clone();
+                (tree, (label, shared))
+            })
+            .unzip();
+        let size = children.iter().map(|child| child.size).sum();
+        let tree = DataTreeReflection::new(root_name, size, children);
+        let shared = JsonShared::merge(sections);
+        JsonTree { tree, shared }
+    }
+}
+
 /// The `"unit"` field, the `"tree"` field, and the `"shared-inodes"` field of [`JsonData`].
 #[derive(Debug, Clone, From, TryInto)]
 #[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
@@ -83,6 +207,30 @@ pub enum JsonDataBody {
     Blocks(JsonTree<Blocks>),
 }
 
+impl JsonDataBody {
+    /// Name of the unit, as it appears in the `"unit"` field.
+    pub fn unit_name(&self) -> &'static str {
+        match self {
+            JsonDataBody::Bytes(_) => Bytes::UNIT_NAME,
+            JsonDataBody::Blocks(_) => Blocks::UNIT_NAME,
+        }
+    }
+}
+
+/// [Size](size::Size) of the trees of a variant of [`JsonDataBody`].
+pub trait JsonUnit: size::Size {
+    /// Name of the unit, as it appears in the `"unit"` field.
+    const UNIT_NAME: &'static str;
+}
+
+impl JsonUnit for Bytes {
+    const UNIT_NAME: &'static str = "bytes";
+}
+
+impl JsonUnit for Blocks {
+    const UNIT_NAME: &'static str = "blocks";
+}
+
 /// Output of the program with `--json-output` flag as well as
 /// input of the program with `--json-input` flag.
 #[derive(Debug, Clone)]
@@ -94,6 +242,12 @@ pub struct JsonData {
     /// The `"pdu"` field.
     #[cfg_attr(feature = "json", serde(rename = "pdu"))]
     pub binary_version: Option<BinaryVersion>,
+    /// The `"header"` field.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub header: Option<ScanHeader>,
     /// The `"unit"` field, the `"tree"` field, and the `"shared"` field.
     #[cfg_attr(feature = "json", serde(flatten))]
     pub body: JsonDataBody,
@@ -0,0 +1,210 @@
+use crate::device::{DeviceBoundary, DeviceNumber};
+use crate::os_string_display::OsStringDisplay;
+
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+/// The `"header"` field of [`JsonData`](super::JsonData), which records when, where, and how
+/// the data was collected.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "kebab-case"))]
+pub struct ScanHeader {
+    /// Time at which the scan started, in seconds since the Unix epoch.
+    pub started_at: u64,
+    /// Time at which the scan finished, in seconds since the Unix epoch.
+    pub finished_at: u64,
+    /// Name of the host that performed the scan.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub hostname: Option<String>,
+    /// The scanned paths.
+    pub roots: Vec<ScanRoot>,
+    /// Settings of the scan.
+    pub settings: ScanSettings,
+}
+
+/// A scanned path in [`ScanHeader`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "kebab-case"))]
+pub struct ScanRoot {
+    /// Absolute and canonical path.
+    pub path: OsStringDisplay,
+    /// Type of the filesystem that contains the path, such as `ext4`.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub filesystem: Option<String>,
+    /// Device number of the filesystem that contains the path.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub device: Option<DeviceNumber>,
+}
+
+/// Settings of the scan in [`ScanHeader`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "kebab-case"))]
+pub struct ScanSettings {
+    /// Aspect of the files and directories that was measured, as in `--quantity`.
+    pub quantity: ScanQuantity,
+    /// Number of threads that performed the scan.
+    pub threads: usize,
+    /// Whether the scan crossed device boundaries.
+    pub device_boundary: DeviceBoundary,
+    /// Whether the sizes of hardlinks were deduplicated.
+    pub deduplicate_hardlinks: bool,
+    /// Deepest level of descendants that was recorded, or [`None`] if unlimited.
+    pub max_depth: Option<u64>,
+    /// Minimal size proportion required for a descendant to be recorded.
+    pub min_ratio: f32,
+    /// Minimal size proportion relative to the parent required for a descendant to be recorded.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub min_ratio_of_parent: Option<f32>,
+    /// Minimal size required for a descendant to be recorded.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub min_size: Option<u64>,
+    /// Maximum number of children that was recorded for each directory, if limited.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max_children: Option<usize>,
+    /// Maximum number of nodes that was recorded, if limited.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub max_rows: Option<usize>,
+    /// Property by which the entries were grouped into categories, if they were.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub group_by: Option<String>,
+    /// Path to the file of the rules by which the entries were categorized, if they were.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub category_rules: Option<OsStringDisplay>,
+    /// Number of the largest files that were listed, if they were.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub largest_files: Option<usize>,
+}
+
+/// Aspect of the files and directories that was measured, as in `--quantity`.
+///
+/// Unlike the `--quantity` flag, every variant exists on every platform so that a snapshot
+/// can be read on a platform other than the one that created it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "kebab-case"))]
+pub enum ScanQuantity {
+    /// Apparent sizes.
+    ApparentSize,
+    /// Block sizes (block-count * 512B).
+    BlockSize,
+    /// Numbers of blocks.
+    BlockCount,
+}
+
+#[cfg(feature = "cli")]
+impl From<crate::args::Quantity> for ScanQuantity {
+    fn from(quantity: crate::args::Quantity) -> Self {
+        use crate::args::Quantity;
+        match quantity {
+            Quantity::ApparentSize => ScanQuantity::ApparentSize,
+            #[cfg(unix)]
+            Quantity::BlockSize => ScanQuantity::BlockSize,
+            #[cfg(unix)]
+            Quantity::BlockCount => ScanQuantity::BlockCount,
+        }
+    }
+}
+
+impl ScanHeader {
+    /// Find a difference that makes two scans unfit to be compared with each other.
+    ///
+    /// Besides the settings that affect the sizes, the scanned paths and the settings that
+    /// decide which descendants are recorded must be the same, otherwise the entries missing
+    /// from one of the scans would appear as changes.
+    pub fn difference(&self, other: &Self) -> Option<&'static str> {
+        if let Some(setting) = self.settings.incompatibility(&other.settings) {
+            return Some(setting);
+        }
+        let same_path = |(left, right): (&ScanRoot, &ScanRoot)| left.path == right.path;
+        if self.roots.len() != other.roots.len()
+            || !self.roots.iter().zip(&other.roots).all(same_path)
+        {
+            return Some("roots");
+        }
+        self.settings.culling_difference(&other.settings)
+    }
+}
+
+impl ScanSettings {
+    /// Find a setting whose difference makes the sizes of two scans incomparable.
+    ///
+    /// Settings that only decide which descendants are recorded, such as
+    /// [`max_depth`](Self::max_depth), do not affect the sizes and are ignored.
+    pub fn incompatibility(&self, other: &Self) -> Option<&'static str> {
+        if self.quantity != other.quantity {
+            return Some("quantity");
+        }
+        if self.device_boundary != other.device_boundary {
+            return Some("device-boundary");
+        }
+        if self.deduplicate_hardlinks != other.deduplicate_hardlinks {
+            return Some("deduplicate-hardlinks");
+        }
+        None
+    }
+
+    /// Find a setting whose difference changes which descendants of two scans are recorded.
+    pub fn culling_difference(&self, other: &Self) -> Option<&'static str> {
+        if self.max_depth != other.max_depth {
+            return Some("max-depth");
+        }
+        if self.min_ratio != other.min_ratio {
+            return Some("min-ratio");
+        }
+        if self.min_ratio_of_parent != other.min_ratio_of_parent {
+            return Some("min-ratio-of-parent");
+        }
+        if self.min_size != other.min_size {
+            return Some("min-size");
+        }
+        if self.max_children != other.max_children {
+            return Some("max-children");
+        }
+        if self.max_rows != other.max_rows {
+            return Some("max-rows");
+        }
+        if self.group_by != other.group_by {
+            return Some("group-by");
+        }
+        if self.category_rules != other.category_rules {
+            return Some("category-rules");
+        }
+        if self.largest_files != other.largest_files {
+            return Some("largest-files");
+        }
+        None
+    }
+}
@@ -6,7 +6,12 @@ use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
 
 /// Content of [`SchemaVersion`].
-pub const SCHEMA_VERSION: &str = "2026-04-02";
+pub const SCHEMA_VERSION: &str = "2026-10-18";
+
+/// Schema versions of the documents that can still be read.
+///
+/// Documents of `2026-04-02` always represent names as strings, which remains valid.
+pub const READABLE_SCHEMA_VERSIONS: &[&str] = &[SCHEMA_VERSION, "2026-04-02"];
 
 /// Verifying schema version.
 #[derive(Debug, Clone, Copy)]
@@ -17,7 +22,7 @@ pub struct SchemaVersion;
 /// Error when trying to parse [`SchemaVersion`].
 #[cfg(feature = "json")]
 #[derive(Debug, Display, Error)]
-#[display("InvalidSchema: {input:?}: input schema is not {SCHEMA_VERSION:?}")]
+#[display("InvalidSchema: {input:?}: input schema is not one of {READABLE_SCHEMA_VERSIONS:?}")]
 pub struct InvalidSchema {
     /// The input string.
     pub input: String,
@@ -27,7 +32,7 @@ pub struct InvalidSchema {
 impl TryFrom<String> for SchemaVersion {
     type Error = InvalidSchema;
     fn try_from(input: String) -> Result<Self, Self::Error> {
-        if input == SCHEMA_VERSION {
+        if READABLE_SCHEMA_VERSIONS.contains(&input.as_str()) {
             Ok(SchemaVersion)
         } else {
             Err(InvalidSchema { input })
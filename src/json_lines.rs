@@ -0,0 +1,89 @@
+use crate::data_tree::DataTree;
+use crate::node_metadata::{EntryType, NodeMetadata};
+use crate::os_string_display::OsStringDisplay;
+use crate::size;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+/// A single line of JSON Lines output, which describes a single node of a [`DataTree`].
+///
+/// Unlike [`JsonData`](crate::json_data::JsonData), the nodes are not nested. Each node
+/// refers to its parent by [path](Self::parent) instead.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "kebab-case"))]
+pub struct JsonLine<Size: size::Size> {
+    /// Full path of the node.
+    pub path: OsStringDisplay,
+    /// Full path of the parent of the node, or [`None`] if the node is a root.
+    pub parent: Option<OsStringDisplay>,
+    /// Number of ancestors of the node.
+    pub depth: u64,
+    /// Disk usage of a file or total disk usage of a folder.
+    pub size: Size,
+    /// Kind of the entry, if known.
+    #[cfg_attr(feature = "json", serde(default))]
+    pub kind: Option<EntryType>,
+    /// The remaining metadata of the node.
+    #[cfg_attr(feature = "json", serde(flatten))]
+    pub metadata: NodeMetadata,
+}
+
+impl<Size: size::Size> JsonLine<Size> {
+    /// Describe a `node` located at `path`, whose parent is located at `parent`.
+    pub fn new(
+        node: &DataTree<OsStringDisplay, Size>,
+        path: &Path,
+        parent: Option<&Path>,
+        depth: u64,
+    ) -> Self {
+        let metadata = node.metadata().copied().unwrap_or_default();
+        JsonLine {
+            path: OsStringDisplay::os_string_from(path),
+            parent: parent.map(OsStringDisplay::os_string_from),
+            depth,
+            size: node.size(),
+            kind: metadata.entry_type,
+            metadata: NodeMetadata {
+                entry_type: None,
+                ..metadata
+            },
+        }
+    }
+
+    /// Describe every node of `tree`, passing each line to `emit`.
+    ///
+    /// The children of a node are emitted before the node itself, so a reader that processes
+    /// the lines in order has seen the whole subtree of a node by the time it reaches the node.
+    /// If `synthetic_root` is `true`, the root does not represent a real entry, so it is
+    /// skipped and its children are treated as roots.
+    pub fn visit<Error>(
+        tree: &DataTree<OsStringDisplay, Size>,
+        synthetic_root: bool,
+        emit: &mut impl FnMut(Self) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        if synthetic_root {
+            return tree.children().iter().try_for_each(|root| {
+                JsonLine::visit_node(root, PathBuf::from(root.name().as_os_str()), None, 0, emit)
+            });
+        }
+        JsonLine::visit_node(tree, PathBuf::from(tree.name().as_os_str()), None, 0, emit)
+    }
+
+    /// Internal function to be used by [`Self::visit`].
+    fn visit_node<Error>(
+        node: &DataTree<OsStringDisplay, Size>,
+        path: PathBuf,
+        parent: Option<&Path>,
+        depth: u64,
+        emit: &mut impl FnMut(Self) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        for child in node.children() {
+            let child_path = path.join(child.name().as_os_str());
+            JsonLine::visit_node(child, child_path, Some(&path), depth + 1, emit)?;
+        }
+        emit(JsonLine::new(node, &path, parent, depth))
+    }
+}
@@ -0,0 +1,140 @@
+use crate::data_tree::DataTree;
+use crate::os_string_display::OsStringDisplay;
+use crate::size;
+use crate::visualizer::QuotingStyle;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::{self, Display, Formatter};
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Keep track of the largest regular files seen during a scan.
+///
+/// It is fed by [`FsTreeBuilder`](crate::fs_tree_builder::FsTreeBuilder) during the scan.
+/// The files are kept in a min-heap bounded by [`capacity`](Self::capacity), so the memory
+/// usage does not depend on the size of the scanned tree.
+#[derive(Debug)]
+pub struct LargestFiles<Size> {
+    /// Maximum number of files to keep.
+    capacity: usize,
+    /// The largest files seen so far, the smallest of which is on top.
+    heap: Mutex<BinaryHeap<Reverse<(Size, PathBuf)>>>,
+    /// Minimum size of a file to be kept, so that the smaller files are rejected without
+    /// locking the heap. It stays at 0 until the heap is full.
+    min_size: AtomicU64,
+    /// Convert a size to be compared with [`min_size`](Self::min_size).
+    size_to_u64: fn(Size) -> u64,
+}
+
+impl<Size: size::Size> LargestFiles<Size> {
+    /// Create an empty record that keeps at most `capacity` files.
+    pub fn new(capacity: usize) -> Self
+    where
+        Size: Into<u64>,
+    {
+        LargestFiles {
+            capacity,
+            heap: Mutex::new(BinaryHeap::with_capacity(capacity)),
+            min_size: AtomicU64::new(0),
+            size_to_u64: Size::into,
+        }
+    }
+
+    /// Maximum number of files to keep.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Consider an entry, which is kept only if it is a regular file among the largest.
+    pub fn record(&self, path: &Path, stats: &Metadata, size: Size) {
+        if !stats.is_file() || self.capacity == 0 {
+            return;
+        }
+        // the minimum only grows, so an outdated value never rejects a file that should be kept
+        if (self.size_to_u64)(size) < self.min_size.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut heap = self
+            .heap
+            .lock()
+            .expect("lock the heap of the largest files");
+        if heap.len() < self.capacity {
+            heap.push(Reverse((size, path.to_path_buf())));
+        } else if let Some(mut smallest) = heap.peek_mut()
+            && size > smallest.0.0
+        {
+            *smallest = Reverse((size, path.to_path_buf()));
+        } else {
+            return;
+        }
+        if heap.len() == self.capacity
+            && let Some(Reverse((smallest, _))) = heap.peek()
+        {
+            let min_size = (self.size_to_u64)(*smallest).saturating_add(1);
+            self.min_size.store(min_size, Ordering::Relaxed);
+        }
+    }
+
+    /// List the recorded files from the largest.
+    pub fn into_sorted_vec(self) -> Vec<(PathBuf, Size)> {
+        self.heap
+            .into_inner()
+            .expect("take the heap of the largest files")
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((size, path))| (path, size))
+            .collect()
+    }
+}
+
+/// Create a flat tree whose root is named `root_name` and whose children are `files`,
+/// named after their full paths.
+pub fn into_flat_tree<Size: size::Size>(
+    root_name: OsStringDisplay,
+    files: Vec<(PathBuf, Size)>,
+) -> DataTree<OsStringDisplay, Size> {
+    let children = files
+        .into_iter()
+        .map(|(path, size)| DataTree::file(OsStringDisplay::os_string_from(path), size))
+        .collect();
+    DataTree::dir(root_name, Size::default(), children)
+}
+
+/// Display a list of files as ranked lines of sizes and paths, such as ` 1. 20.0G ./big.iso`.
+#[derive(Debug, Clone, Copy)]
+pub struct RankedList<'a, Size: size::Size> {
+    /// The files, from the largest.
+    pub files: &'a [(PathBuf, Size)],
+    /// Format to be used to display the sizes.
+    pub bytes_format: Size::DisplayFormat,
+    /// How to quote the paths.
+    pub quoting_style: QuotingStyle,
+}
+
+impl<Size: size::Size> Display for RankedList<'_, Size> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let RankedList {
+            files,
+            bytes_format,
+            quoting_style,
+        } = *self;
+        let sizes: Vec<_> = files
+            .iter()
+            .map(|(_, size)| size.display(bytes_format).to_string())
+            .collect();
+        let rank_width = files.len().to_string().len();
+        let size_width = sizes.iter().map(String::len).max().unwrap_or(0);
+        for (rank, ((path, _), size)) in files.iter().zip(sizes).enumerate() {
+            let path = quoting_style.quote_os_str(path.as_os_str());
+            writeln!(
+                f,
+                "{rank:>rank_width$}. {size:>size_width$} {path}",
+                rank = rank + 1,
+            )?;
+        }
+        Ok(())
+    }
+}
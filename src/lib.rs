@@ -40,18 +40,27 @@ pub use clap_complete;
 #[cfg(feature = "cli")]
 pub use clap_utilities;
 
+pub mod browser;
 pub mod bytes_format;
 pub mod data_tree;
 pub mod device;
+pub mod diff;
 pub mod fs_tree_builder;
 pub mod get_size;
+pub mod group_by;
 pub mod hardlink;
+pub mod html_report;
 pub mod inode;
 pub mod json_data;
+pub mod json_lines;
+pub mod largest_files;
+pub mod node_metadata;
 pub mod os_string_display;
 pub mod reporter;
 pub mod size;
 pub mod status_board;
+pub mod svg_chart;
+pub mod table;
 pub mod tree_builder;
 pub mod visualizer;
 
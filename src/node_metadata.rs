@@ -0,0 +1,176 @@
+use crate::device::DeviceNumber;
+use crate::inode::InodeNumber;
+use std::fs::{FileType, Metadata};
+use std::time::UNIX_EPOCH;
+
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+/// Kind of a filesystem entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "kebab-case"))]
+pub enum EntryType {
+    /// Regular file.
+    File,
+    /// Directory.
+    Dir,
+    /// Symbolic link.
+    Symlink,
+    /// Anything else, such as a socket, a FIFO, or a device file.
+    Other,
+}
+
+impl From<FileType> for EntryType {
+    fn from(file_type: FileType) -> Self {
+        if file_type.is_dir() {
+            EntryType::Dir
+        } else if file_type.is_symlink() {
+            EntryType::Symlink
+        } else if file_type.is_file() {
+            EntryType::File
+        } else {
+            EntryType::Other
+        }
+    }
+}
+
+/// Selection of the fields of [`NodeMetadata`] to be collected.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MetadataFields {
+    /// Whether to collect [`NodeMetadata::entry_type`].
+    pub entry_type: bool,
+    /// Whether to collect [`NodeMetadata::count`].
+    pub count: bool,
+    /// Whether to collect [`NodeMetadata::mtime`].
+    pub mtime: bool,
+    /// Whether to collect [`NodeMetadata::uid`] and [`NodeMetadata::gid`].
+    pub owner: bool,
+    /// Whether to collect [`NodeMetadata::inode`] and [`NodeMetadata::device`].
+    pub inode: bool,
+}
+
+impl MetadataFields {
+    /// Selection of no fields.
+    pub const NONE: Self = MetadataFields {
+        entry_type: false,
+        count: false,
+        mtime: false,
+        owner: false,
+        inode: false,
+    };
+
+    /// Check whether no fields are selected.
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self == MetadataFields::NONE
+    }
+}
+
+/// Additional information of a node in a [`DataTree`](crate::data_tree::DataTree).
+///
+/// Fields that were not selected by [`MetadataFields`] or that are not supported by the
+/// platform are left as [`None`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "json", serde(rename_all = "kebab-case"))]
+pub struct NodeMetadata {
+    /// Kind of the entry.
+    #[cfg_attr(
+        feature = "json",
+        serde(rename = "type", default, skip_serializing_if = "Option::is_none")
+    )]
+    pub entry_type: Option<EntryType>,
+    /// Number of entries other than directories in the subtree.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub count: Option<u64>,
+    /// Newest modification time in the subtree, in seconds since the Unix epoch.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub mtime: Option<u64>,
+    /// User ID of the owner of the entry.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub uid: Option<u32>,
+    /// Group ID of the owner of the entry.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub gid: Option<u32>,
+    /// Inode number of the entry.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub inode: Option<InodeNumber>,
+    /// Device number of the filesystem of the entry.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub device: Option<DeviceNumber>,
+    /// Number of culled entries that this synthetic node stands for, if it is one.
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub aggregated_entries: Option<u64>,
+}
+
+impl NodeMetadata {
+    /// Collect the selected `fields` from the [`Metadata`] of a single entry.
+    ///
+    /// Return [`None`] if no fields are selected.
+    pub fn collect(stats: &Metadata, fields: MetadataFields) -> Option<Box<Self>> {
+        if fields.is_empty() {
+            return None;
+        }
+        let mut metadata = NodeMetadata::default();
+        if fields.entry_type {
+            metadata.entry_type = Some(stats.file_type().into());
+        }
+        if fields.count {
+            metadata.count = Some(if stats.is_dir() { 0 } else { 1 });
+        }
+        if fields.mtime {
+            metadata.mtime = stats
+                .modified()
+                .ok()
+                .and_then(|mtime| mtime.duration_since(UNIX_EPOCH).ok())
+                .map(|mtime| mtime.as_secs());
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            if fields.owner {
+                metadata.uid = Some(stats.uid());
+                metadata.gid = Some(stats.gid());
+            }
+            if fields.inode {
+                metadata.inode = Some(InodeNumber::get(stats));
+                metadata.device = Some(DeviceNumber::get(stats));
+            }
+        }
+        Some(Box::new(metadata))
+    }
+
+    /// Fold the aggregated fields of a child node into this node.
+    ///
+    /// The [`count`](Self::count) is summed and the newest [`mtime`](Self::mtime) is kept.
+    /// The remaining fields only describe the entry itself, so they are left untouched.
+    pub fn absorb(&mut self, child: &NodeMetadata) {
+        self.count = match (self.count, child.count) {
+            (Some(count), Some(child_count)) => Some(count + child_count),
+            (count, child_count) => count.or(child_count),
+        };
+        self.mtime = self.mtime.max(child.mtime);
+    }
+}
@@ -3,12 +3,21 @@ use std::ffi::{OsStr, OsString};
 use std::fmt::{Debug, Display, Error, Formatter};
 
 #[cfg(feature = "json")]
-use serde::{Deserialize, Serialize};
+use serde::de::{self, Deserialize, Deserializer, MapAccess, Visitor};
+#[cfg(feature = "json")]
+use serde::ser::{Serialize, SerializeMap, Serializer};
+#[cfg(feature = "json")]
+use std::marker::PhantomData;
 
 /// [`Display`] inner [`OsStr`] or [`OsString`].
 ///
 /// If the inner string can be converted to UTF-8, displays the UTF-8.
 /// Otherwise, displays its [`Debug`] form.
+///
+/// **Serialization and deserialization:** _(feature: `json`)_ A UTF-8 string is represented
+/// as is. Otherwise, it is represented as `{"bytes": [...]}` on POSIX or as `{"wide": [...]}`
+/// on Windows, which preserves the raw name. A raw name from a different platform is
+/// deserialized lossily.
 #[derive(
     Debug,
     Default,
@@ -18,6 +27,7 @@ use serde::{Deserialize, Serialize};
     Eq,
     PartialOrd,
     Ord,
+    Hash,
     AsRef,
     AsMut,
     Deref,
@@ -25,7 +35,6 @@ use serde::{Deserialize, Serialize};
     From,
     FromStr,
 )]
-#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
 pub struct OsStringDisplay<Inner = OsString>(pub Inner)
 where
     Inner: AsRef<OsStr> + Debug;
@@ -77,3 +86,126 @@ where
         }
     }
 }
+
+/// Key of the raw representation of a non-UTF-8 name on POSIX.
+#[cfg(feature = "json")]
+const BYTES_KEY: &str = "bytes";
+
+/// Key of the raw representation of a non-UTF-8 name on Windows.
+#[cfg(feature = "json")]
+const WIDE_KEY: &str = "wide";
+
+#[cfg(feature = "json")]
+impl<Inner> Serialize for OsStringDisplay<Inner>
+where
+    Inner: AsRef<OsStr> + Debug,
+{
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        let inner = self.as_os_str();
+        if let Some(utf8) = inner.to_str() {
+            return serializer.serialize_str(utf8);
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::ffi::OsStrExt;
+            let mut map = serializer.serialize_map(Some(1))?;
+            map.serialize_entry(BYTES_KEY, inner.as_bytes())?;
+            map.end()
+        }
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::ffi::OsStrExt;
+            let wide: Vec<u16> = inner.encode_wide().collect();
+            let mut map = serializer.serialize_map(Some(1))?;
+            map.serialize_entry(WIDE_KEY, &wide)?;
+            map.end()
+        }
+
+        #[cfg(not(any(unix, windows)))]
+        serializer.serialize_str(&inner.to_string_lossy())
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'de, Inner> Deserialize<'de> for OsStringDisplay<Inner>
+where
+    Inner: AsRef<OsStr> + Debug + From<OsString>,
+{
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        deserializer.deserialize_any(OsStringVisitor(PhantomData))
+    }
+}
+
+/// [`Visitor`] to deserialize an [`OsStringDisplay`].
+#[cfg(feature = "json")]
+struct OsStringVisitor<Inner>(PhantomData<Inner>);
+
+#[cfg(feature = "json")]
+impl<'de, Inner> Visitor<'de> for OsStringVisitor<Inner>
+where
+    Inner: AsRef<OsStr> + Debug + From<OsString>,
+{
+    type Value = OsStringDisplay<Inner>;
+
+    fn expecting(&self, formatter: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            formatter,
+            "a string, or a map with a single key of either {BYTES_KEY:?} or {WIDE_KEY:?}",
+        )
+    }
+
+    fn visit_str<Error: de::Error>(self, value: &str) -> Result<Self::Value, Error> {
+        Ok(OsStringDisplay(OsString::from(value).into()))
+    }
+
+    fn visit_string<Error: de::Error>(self, value: String) -> Result<Self::Value, Error> {
+        Ok(OsStringDisplay(OsString::from(value).into()))
+    }
+
+    fn visit_map<Map: MapAccess<'de>>(self, mut map: Map) -> Result<Self::Value, Map::Error> {
+        let key: String = map
+            .next_key()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let name = match key.as_str() {
+            BYTES_KEY => os_string_from_bytes(map.next_value()?),
+            WIDE_KEY => os_string_from_wide(map.next_value()?),
+            _ => return Err(de::Error::unknown_field(&key, &[BYTES_KEY, WIDE_KEY])),
+        };
+        if map.next_key::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+        Ok(OsStringDisplay(name.into()))
+    }
+}
+
+/// Convert raw bytes of a POSIX name into an [`OsString`].
+#[cfg(feature = "json")]
+fn os_string_from_bytes(bytes: Vec<u8>) -> OsString {
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStringExt;
+        OsString::from_vec(bytes)
+    }
+
+    #[cfg(not(unix))]
+    {
+        String::from_utf8_lossy(&bytes).into_owned().into()
+    }
+}
+
+/// Convert raw UTF-16 code units of a Windows name into an [`OsString`].
+#[cfg(feature = "json")]
+fn os_string_from_wide(wide: Vec<u16>) -> OsString {
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStringExt;
+        OsString::from_wide(&wide)
+    }
+
+    #[cfg(not(windows))]
+    {
+        String::from_utf16_lossy(&wide).into()
+    }
+}
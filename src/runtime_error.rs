@@ -1,5 +1,8 @@
+use crate::group_by::ParseCategoryRulesError;
 use derive_more::{Display, Error};
 use std::convert::Infallible;
+use std::io;
+use std::path::PathBuf;
 use std::process::ExitCode;
 
 /// Error caused by the CLI program.
@@ -15,6 +18,7 @@ pub enum RuntimeError {
     #[display("DeserializationFailure: {_0}")]
     DeserializationFailure(serde_json::Error),
     /// When `--json-input` and file names are both specified.
+    #[deprecated(note = "--json-input reads the listed files, so they no longer conflict")]
     #[display("JsonInputArgConflict: Arguments exist alongside --json-input")]
     JsonInputArgConflict,
     /// When input JSON data is not a valid tree.
@@ -23,6 +27,62 @@ pub enum RuntimeError {
     /// When the user attempts to use unavailable platform-specific features.
     #[display("UnsupportedFeature: {_0}")]
     UnsupportedFeature(UnsupportedFeature),
+    /// When it fails to read an input file.
+    #[display("ReadInputFailure: {path:?}: {error}")]
+    ReadInputFailure {
+        /// Path to the input file.
+        path: PathBuf,
+        /// The error that occurred.
+        #[error(source)]
+        error: io::Error,
+    },
+    /// When input data measured in different units are combined.
+    #[display("UnitMismatch: Data in {_0} cannot be combined with data in {_1}")]
+    UnitMismatch(#[error(not(source))] &'static str, &'static str),
+    /// When the inputs to be combined cannot be told apart.
+    #[display("MergeConflict: {_0}")]
+    MergeConflict(#[error(not(source))] String),
+    /// When it fails to write CBOR representation of
+    /// [DataTreeReflection](crate::data_tree::Reflection) to stdout.
+    #[display("CborSerializationFailure: {_0}")]
+    CborSerializationFailure(ciborium::ser::Error<io::Error>),
+    /// When it fails to read CBOR representation of
+    /// [DataTreeReflection](crate::data_tree::Reflection) from the input.
+    #[display("CborDeserializationFailure: {_0}")]
+    CborDeserializationFailure(ciborium::de::Error<io::Error>),
+    /// When it fails to write the output.
+    #[display("WriteOutputFailure: {_0}")]
+    WriteOutputFailure(io::Error),
+    /// When the inputs were scanned with settings that make their sizes incomparable.
+    #[display("IncomparableSnapshots: {_0}")]
+    IncomparableSnapshots(#[error(not(source))] String),
+    /// When the data cannot be represented in the requested output format.
+    #[display("UnsupportedOutputFormat: This data cannot be written as {_0}")]
+    UnsupportedOutputFormat(#[error(not(source))] &'static str),
+    /// When it fails to read from or write to a history database.
+    #[display("DatabaseFailure: {path:?}: {error}")]
+    DatabaseFailure {
+        /// Path to the database.
+        path: PathBuf,
+        /// The error that occurred.
+        #[error(source)]
+        error: rusqlite::Error,
+    },
+    /// When a history database has too few snapshots to be compared.
+    #[display("InsufficientSnapshots: At least 2 snapshots are required, but only {_0} found")]
+    InsufficientSnapshots(#[error(not(source))] usize),
+    /// When it fails to control the terminal of the interactive mode.
+    #[display("TerminalFailure: {_0}")]
+    TerminalFailure(io::Error),
+    /// When the file of category rules is invalid.
+    #[display("InvalidCategoryRules: {path:?}: {error}")]
+    InvalidCategoryRules {
+        /// Path to the file.
+        path: PathBuf,
+        /// The error that occurred.
+        #[error(source)]
+        error: ParseCategoryRulesError,
+    },
 }
 
 /// Error caused by the user attempting to use unavailable platform-specific features.
@@ -51,9 +111,22 @@ impl RuntimeError {
         ExitCode::from(match self {
             RuntimeError::SerializationFailure(_) => 2,
             RuntimeError::DeserializationFailure(_) => 3,
+            #[allow(deprecated)]
             RuntimeError::JsonInputArgConflict => 4,
             RuntimeError::InvalidInputReflection(_) => 5,
             RuntimeError::UnsupportedFeature(_) => 6,
+            RuntimeError::ReadInputFailure { .. } => 7,
+            RuntimeError::UnitMismatch(..) => 8,
+            RuntimeError::MergeConflict(_) => 9,
+            RuntimeError::CborSerializationFailure(_) => 10,
+            RuntimeError::CborDeserializationFailure(_) => 11,
+            RuntimeError::WriteOutputFailure(_) => 12,
+            RuntimeError::IncomparableSnapshots(_) => 13,
+            RuntimeError::UnsupportedOutputFormat(_) => 14,
+            RuntimeError::DatabaseFailure { .. } => 15,
+            RuntimeError::InsufficientSnapshots(_) => 16,
+            RuntimeError::TerminalFailure(_) => 17,
+            RuntimeError::InvalidCategoryRules { .. } => 18,
         })
     }
 }
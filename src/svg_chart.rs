@@ -0,0 +1,235 @@
+pub mod layout;
+
+pub use layout::SvgLayout;
+
+use crate::data_tree::DataTree;
+use crate::size;
+use std::ffi::OsStr;
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// Render a [`DataTree`] as a static SVG image.
+///
+/// The [`Display`] trait can be used to create the image. Every node is labeled with its
+/// name and its size when there is enough room, and its full path can be read from its
+/// tooltip. The tree is drawn as is, so it should be culled and sorted beforehand.
+///
+/// **Example:**
+///
+/// ```no_run
+/// # use parallel_disk_usage::bytes_format::BytesFormat;
+/// # use parallel_disk_usage::data_tree::DataTree;
+/// # use parallel_disk_usage::os_string_display::OsStringDisplay;
+/// # use parallel_disk_usage::size::Bytes;
+/// # use parallel_disk_usage::svg_chart::{SvgChart, SvgLayout};
+/// # fn _wrapper(create_data_tree: fn() -> DataTree<OsStringDisplay, Bytes>) {
+/// let data_tree: DataTree<OsStringDisplay, Bytes> = create_data_tree();
+/// let (width, height) = SvgLayout::Treemap.dimensions();
+/// let chart = SvgChart {
+///     data_tree: &data_tree,
+///     layout: SvgLayout::Treemap,
+///     bytes_format: BytesFormat::MetricUnits,
+///     width,
+///     height,
+/// };
+/// print!("{chart}");
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SvgChart<'a, Name, Size>
+where
+    Size: size::Size,
+{
+    /// The tree to draw.
+    pub data_tree: &'a DataTree<Name, Size>,
+    /// The shape of the chart.
+    pub layout: SvgLayout,
+    /// Format to be used to [`display`](size::Size::display) the sizes.
+    pub bytes_format: Size::DisplayFormat,
+    /// Width of the image in pixels.
+    pub width: f64,
+    /// Height of the image in pixels.
+    pub height: f64,
+}
+
+/// Height of the text of the labels in pixels.
+const FONT_SIZE: f64 = 12.0;
+
+/// Estimated width of a character of the labels in pixels.
+const CHAR_WIDTH: f64 = FONT_SIZE * 0.6;
+
+/// A node to be drawn along with its location within the tree.
+struct Node<'a, Name, Size: size::Size> {
+    /// The node itself.
+    tree: &'a DataTree<Name, Size>,
+    /// Full path of the node.
+    path: PathBuf,
+    /// Number of ancestors of the node.
+    depth: usize,
+    /// Index of the child of the root which the node descends from, if the node is not the root.
+    branch: Option<usize>,
+}
+
+impl<'a, Name, Size> Node<'a, Name, Size>
+where
+    Name: AsRef<OsStr>,
+    Size: size::Size,
+{
+    /// The root of the tree.
+    fn root(tree: &'a DataTree<Name, Size>) -> Self {
+        Node {
+            tree,
+            path: PathBuf::from(tree.name()),
+            depth: 0,
+            branch: None,
+        }
+    }
+
+    /// The children of the node.
+    fn children(&self) -> impl Iterator<Item = Node<'a, Name, Size>> + '_ {
+        self.tree
+            .children()
+            .iter()
+            .enumerate()
+            .map(|(index, child)| Node {
+                tree: child,
+                path: self.path.join(child.name().as_ref()),
+                depth: self.depth + 1,
+                branch: Some(self.branch.unwrap_or(index)),
+            })
+    }
+
+    /// Name of the node as it appears in the labels.
+    fn name(&self) -> String {
+        Path::new(self.tree.name()).to_string_lossy().into_owned()
+    }
+
+    /// Fill color of the node, which is shared by its branch and lightened by its depth.
+    fn color(&self) -> String {
+        match self.branch {
+            None => "hsl(0, 0%, 70%)".to_string(),
+            Some(branch) => {
+                let hue = (branch as f64 * 137.508) % 360.0;
+                let lightness = (45 + 10 * self.depth).min(85);
+                format!("hsl({hue:.0}, 55%, {lightness}%)")
+            }
+        }
+    }
+}
+
+impl<Name, Size> SvgChart<'_, Name, Size>
+where
+    Name: AsRef<OsStr>,
+    Size: size::Size + Into<u64>,
+{
+    /// Open the group of a node, which shows its path and size when hovered.
+    fn open_group(&self, f: &mut Formatter<'_>, node: &Node<'_, Name, Size>) -> fmt::Result {
+        write!(
+            f,
+            "<g><title>{path} ({size})</title>",
+            path = Escape(&node.path.to_string_lossy()),
+            size = Escape(
+                node.tree
+                    .size()
+                    .display(self.bytes_format)
+                    .to_string()
+                    .trim()
+            ),
+        )
+    }
+
+    /// Write a label at (`x`, `y`) if it fits in `room` pixels, preferring the one with the size.
+    fn label(
+        &self,
+        f: &mut Formatter<'_>,
+        node: &Node<'_, Name, Size>,
+        (x, y): (f64, f64),
+        room: f64,
+        anchor: &str,
+    ) -> fmt::Result {
+        let name = node.name();
+        let size = node.tree.size().display(self.bytes_format).to_string();
+        let full = format!("{name} {size}", size = size.trim());
+        let fits = |text: &str| text.chars().count() as f64 * CHAR_WIDTH <= room;
+        let text = if fits(&full) {
+            full
+        } else if fits(&name) {
+            name
+        } else {
+            let chars = (room / CHAR_WIDTH) as usize;
+            if chars < 4 {
+                return Ok(());
+            }
+            let mut text: String = name.chars().take(chars - 3).collect();
+            text.push_str("...");
+            text
+        };
+        write!(
+            f,
+            r#"<text x="{x:.2}" y="{y:.2}" text-anchor="{anchor}">{text}</text>"#,
+            text = Escape(&text),
+        )
+    }
+
+    /// Ratio of the size of `node` to the size of `total`.
+    fn ratio(node: &DataTree<Name, Size>, total: &DataTree<Name, Size>) -> f64 {
+        let total: u64 = total.size().into();
+        if total == 0 {
+            return 0.0;
+        }
+        let size: u64 = node.size().into();
+        size as f64 / total as f64
+    }
+}
+
+impl<Name, Size> Display for SvgChart<'_, Name, Size>
+where
+    Name: AsRef<OsStr>,
+    Size: size::Size + Into<u64>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let SvgChart { width, height, .. } = *self;
+        writeln!(
+            f,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="{FONT_SIZE}">"#,
+        )?;
+        writeln!(
+            f,
+            r##"<style>rect, path, circle {{ stroke: #ffffff; stroke-width: 1; }} text {{ fill: #1f2328; pointer-events: none; }}</style>"##,
+        )?;
+        let root = Node::root(self.data_tree);
+        match self.layout {
+            SvgLayout::Treemap => self.fmt_treemap(f, root)?,
+            SvgLayout::Icicle => self.fmt_icicle(f, root)?,
+            SvgLayout::Sunburst => self.fmt_sunburst(f, root)?,
+        }
+        writeln!(f, "</svg>")
+    }
+}
+
+/// Escape the characters of a text that are special to XML.
+struct Escape<'a>(&'a str);
+
+impl Display for Escape<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for char in self.0.chars() {
+            match char {
+                '&' => write!(f, "&amp;")?,
+                '<' => write!(f, "&lt;")?,
+                '>' => write!(f, "&gt;")?,
+                '"' => write!(f, "&quot;")?,
+                '\'' => write!(f, "&apos;")?,
+                // these are not allowed in XML 1.0 at all
+                char if char.is_control() && !matches!(char, '\t' | '\n' | '\r') => {
+                    write!(f, "\u{FFFD}")?
+                }
+                char => write!(f, "{char}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+mod icicle;
+mod sunburst;
+mod treemap;
@@ -0,0 +1,60 @@
+use super::{FONT_SIZE, Node, SvgChart};
+use crate::data_tree::DataTree;
+use crate::size;
+use std::ffi::OsStr;
+use std::fmt::{self, Formatter};
+
+impl<Name, Size> SvgChart<'_, Name, Size>
+where
+    Name: AsRef<OsStr>,
+    Size: size::Size + Into<u64>,
+{
+    /// Draw rows of rectangles, one row per level, whose widths are proportional to the sizes.
+    pub(super) fn fmt_icicle(
+        &self,
+        f: &mut Formatter<'_>,
+        root: Node<'_, Name, Size>,
+    ) -> fmt::Result {
+        let row_height = self.height / levels(root.tree) as f64;
+        self.fmt_icicle_node(f, root, 0.0, self.width, row_height)
+    }
+
+    /// Draw a node, then its children below it.
+    fn fmt_icicle_node(
+        &self,
+        f: &mut Formatter<'_>,
+        node: Node<'_, Name, Size>,
+        x: f64,
+        width: f64,
+        row_height: f64,
+    ) -> fmt::Result {
+        if width < 1.0 {
+            return Ok(());
+        }
+        let y = node.depth as f64 * row_height;
+        self.open_group(f, &node)?;
+        write!(
+            f,
+            r#"<rect x="{x:.2}" y="{y:.2}" width="{width:.2}" height="{row_height:.2}" fill="{color}"/>"#,
+            color = node.color(),
+        )?;
+        if row_height >= FONT_SIZE + 2.0 {
+            let baseline = y + (row_height + FONT_SIZE) / 2.0 - 2.0;
+            self.label(f, &node, (x + 4.0, baseline), width - 8.0, "start")?;
+        }
+        writeln!(f, "</g>")?;
+
+        let mut offset = x;
+        for child in node.children() {
+            let child_width = Self::ratio(child.tree, node.tree) * width;
+            self.fmt_icicle_node(f, child, offset, child_width, row_height)?;
+            offset += child_width;
+        }
+        Ok(())
+    }
+}
+
+/// Number of levels of a tree.
+pub(super) fn levels<Name, Size: size::Size>(tree: &DataTree<Name, Size>) -> usize {
+    1 + tree.children().iter().map(levels).max().unwrap_or(0)
+}
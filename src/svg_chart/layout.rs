@@ -0,0 +1,25 @@
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// Shape of an [`SvgChart`](super::SvgChart).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum SvgLayout {
+    /// Nested rectangles whose areas are proportional to the sizes.
+    #[default]
+    Treemap,
+    /// Rows of rectangles, one row per level, whose widths are proportional to the sizes.
+    Icicle,
+    /// Rings of arcs, one ring per level, whose angles are proportional to the sizes.
+    Sunburst,
+}
+
+impl SvgLayout {
+    /// Width and height of the image in pixels.
+    pub const fn dimensions(self) -> (f64, f64) {
+        match self {
+            SvgLayout::Treemap | SvgLayout::Icicle => (1200.0, 800.0),
+            SvgLayout::Sunburst => (900.0, 900.0),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use super::icicle::levels;
+use super::{FONT_SIZE, Node, SvgChart};
+use crate::size;
+use std::f64::consts::TAU;
+use std::ffi::OsStr;
+use std::fmt::{self, Formatter};
+
+/// Center and ring width of a sunburst.
+#[derive(Debug, Clone, Copy)]
+struct Rings {
+    center_x: f64,
+    center_y: f64,
+    width: f64,
+}
+
+impl Rings {
+    /// The point at `radius` and `angle`, where angle 0 points upward and increases clockwise.
+    fn point(self, radius: f64, angle: f64) -> (f64, f64) {
+        (
+            self.center_x + radius * angle.sin(),
+            self.center_y - radius * angle.cos(),
+        )
+    }
+}
+
+impl<Name, Size> SvgChart<'_, Name, Size>
+where
+    Name: AsRef<OsStr>,
+    Size: size::Size + Into<u64>,
+{
+    /// Draw rings of arcs, one ring per level, whose angles are proportional to the sizes.
+    pub(super) fn fmt_sunburst(
+        &self,
+        f: &mut Formatter<'_>,
+        root: Node<'_, Name, Size>,
+    ) -> fmt::Result {
+        let radius = self.width.min(self.height) / 2.0 - 2.0;
+        let rings = Rings {
+            center_x: self.width / 2.0,
+            center_y: self.height / 2.0,
+            width: radius / levels(root.tree) as f64,
+        };
+
+        self.open_group(f, &root)?;
+        write!(
+            f,
+            r#"<circle cx="{x:.2}" cy="{y:.2}" r="{r:.2}" fill="{color}"/>"#,
+            x = rings.center_x,
+            y = rings.center_y,
+            r = rings.width,
+            color = root.color(),
+        )?;
+        let center = (rings.center_x, rings.center_y + FONT_SIZE / 2.0 - 2.0);
+        self.label(f, &root, center, rings.width * 2.0 - 8.0, "middle")?;
+        writeln!(f, "</g>")?;
+
+        self.fmt_sunburst_children(f, &root, rings, 0.0, TAU)
+    }
+
+    /// Draw the arcs of the children of `node` between `start` and `end` angles.
+    fn fmt_sunburst_children(
+        &self,
+        f: &mut Formatter<'_>,
+        node: &Node<'_, Name, Size>,
+        rings: Rings,
+        start: f64,
+        end: f64,
+    ) -> fmt::Result {
+        let mut offset = start;
+        for child in node.children() {
+            let angle = Self::ratio(child.tree, node.tree) * (end - start);
+            let inner = child.depth as f64 * rings.width;
+            let outer = inner + rings.width;
+            // arcs too thin to be seen are skipped along with their descendants
+            if angle * outer >= 0.5 {
+                self.open_group(f, &child)?;
+                write!(
+                    f,
+                    r#"<path d="{path}" fill="{color}" fill-rule="evenodd"/>"#,
+                    path = arc(rings, inner, outer, offset, offset + angle),
+                    color = child.color(),
+                )?;
+                let middle = (inner + outer) / 2.0;
+                if rings.width >= FONT_SIZE + 2.0 {
+                    let (x, y) = rings.point(middle, offset + angle / 2.0);
+                    let room = (angle * middle).min(rings.width * 2.0) - 4.0;
+                    self.label(f, &child, (x, y + FONT_SIZE / 2.0 - 2.0), room, "middle")?;
+                }
+                writeln!(f, "</g>")?;
+                self.fmt_sunburst_children(f, &child, rings, offset, offset + angle)?;
+            }
+            offset += angle;
+        }
+        Ok(())
+    }
+}
+
+/// Outline of the part of the ring between `inner` and `outer` radii from `start` to `end` angles.
+fn arc(rings: Rings, inner: f64, outer: f64, start: f64, end: f64) -> String {
+    if end - start >= TAU - 1e-9 {
+        // a full ring cannot be drawn by a single arc since its ends coincide
+        let (top_x, top_y) = rings.point(outer, 0.0);
+        let (bottom_x, bottom_y) = rings.point(outer, TAU / 2.0);
+        let (inner_top_x, inner_top_y) = rings.point(inner, 0.0);
+        let (inner_bottom_x, inner_bottom_y) = rings.point(inner, TAU / 2.0);
+        return format!(
+            "M {top_x:.2} {top_y:.2} A {outer:.2} {outer:.2} 0 1 1 {bottom_x:.2} {bottom_y:.2} A {outer:.2} {outer:.2} 0 1 1 {top_x:.2} {top_y:.2} Z \
+             M {inner_top_x:.2} {inner_top_y:.2} A {inner:.2} {inner:.2} 0 1 0 {inner_bottom_x:.2} {inner_bottom_y:.2} A {inner:.2} {inner:.2} 0 1 0 {inner_top_x:.2} {inner_top_y:.2} Z",
+        );
+    }
+    let large_arc = u8::from(end - start > TAU / 2.0);
+    let (outer_start_x, outer_start_y) = rings.point(outer, start);
+    let (outer_end_x, outer_end_y) = rings.point(outer, end);
+    let (inner_end_x, inner_end_y) = rings.point(inner, end);
+    let (inner_start_x, inner_start_y) = rings.point(inner, start);
+    format!(
+        "M {outer_start_x:.2} {outer_start_y:.2} A {outer:.2} {outer:.2} 0 {large_arc} 1 {outer_end_x:.2} {outer_end_y:.2} \
+         L {inner_end_x:.2} {inner_end_y:.2} A {inner:.2} {inner:.2} 0 {large_arc} 0 {inner_start_x:.2} {inner_start_y:.2} Z",
+    )
+}
@@ -0,0 +1,174 @@
+use super::{FONT_SIZE, Node, SvgChart};
+use crate::size;
+use std::ffi::OsStr;
+use std::fmt::{self, Formatter};
+
+/// Height of the band which labels a directory above its children.
+const HEADER: f64 = FONT_SIZE + 4.0;
+
+/// Gap between the border of a directory and its children.
+const PADDING: f64 = 2.0;
+
+/// Smallest width or height of a rectangle whose children are drawn.
+const MIN_PARENT_SIDE: f64 = 24.0;
+
+/// Position and dimensions of a rectangle.
+#[derive(Debug, Clone, Copy)]
+struct Rect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl<Name, Size> SvgChart<'_, Name, Size>
+where
+    Name: AsRef<OsStr>,
+    Size: size::Size + Into<u64>,
+{
+    /// Draw nested rectangles whose areas are proportional to the sizes.
+    pub(super) fn fmt_treemap(
+        &self,
+        f: &mut Formatter<'_>,
+        root: Node<'_, Name, Size>,
+    ) -> fmt::Result {
+        let rect = Rect {
+            x: 0.0,
+            y: 0.0,
+            width: self.width,
+            height: self.height,
+        };
+        self.fmt_treemap_node(f, root, rect)
+    }
+
+    /// Draw a node, then its children inside it.
+    fn fmt_treemap_node(
+        &self,
+        f: &mut Formatter<'_>,
+        node: Node<'_, Name, Size>,
+        rect: Rect,
+    ) -> fmt::Result {
+        let Rect {
+            x,
+            y,
+            width,
+            height,
+        } = rect;
+        self.open_group(f, &node)?;
+        write!(
+            f,
+            r#"<rect x="{x:.2}" y="{y:.2}" width="{width:.2}" height="{height:.2}" fill="{color}"/>"#,
+            color = node.color(),
+        )?;
+        if height >= FONT_SIZE + PADDING {
+            self.label(
+                f,
+                &node,
+                (x + PADDING * 2.0, y + FONT_SIZE + 1.0),
+                width - PADDING * 4.0,
+                "start",
+            )?;
+        }
+        writeln!(f, "</g>")?;
+
+        let inner = Rect {
+            x: x + PADDING,
+            y: y + HEADER,
+            width: width - PADDING * 2.0,
+            height: height - HEADER - PADDING,
+        };
+        if inner.width < MIN_PARENT_SIDE || inner.height < MIN_PARENT_SIDE {
+            return Ok(());
+        }
+        let children: Vec<_> = node.children().collect();
+        let areas: Vec<f64> = children
+            .iter()
+            .map(|child| Self::ratio(child.tree, node.tree) * inner.width * inner.height)
+            .collect();
+        for (child, rect) in children.into_iter().zip(squarify(&areas, inner)) {
+            if rect.width >= 1.0 && rect.height >= 1.0 {
+                self.fmt_treemap_node(f, child, rect)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Lay out rectangles of the given `areas` in `rect`, keeping their aspect ratios close to 1.
+///
+/// This is the squarified treemap algorithm of Bruls, Huizing, and van Wijk. Empty areas
+/// are given empty rectangles.
+fn squarify(areas: &[f64], mut rect: Rect) -> Vec<Rect> {
+    /// The worst aspect ratio among a row of positive `areas` laid along a side of `length`.
+    fn worst(areas: &[(usize, f64)], length: f64) -> f64 {
+        let sum: f64 = areas.iter().map(|(_, area)| area).sum();
+        let max = areas.iter().map(|(_, area)| *area).fold(f64::MIN, f64::max);
+        let min = areas.iter().map(|(_, area)| *area).fold(f64::MAX, f64::min);
+        f64::max(
+            length * length * max / (sum * sum),
+            sum * sum / (length * length * min),
+        )
+    }
+
+    /// Lay out a row of positive `areas` along the shorter side of `rect`, then shrink `rect`.
+    fn lay_row(areas: &[(usize, f64)], rect: &mut Rect, result: &mut [Rect]) {
+        let sum: f64 = areas.iter().map(|(_, area)| area).sum();
+        if rect.width >= rect.height {
+            let width = sum / rect.height;
+            let mut y = rect.y;
+            for (index, area) in areas {
+                let height = area / width;
+                result[*index] = Rect {
+                    x: rect.x,
+                    y,
+                    width,
+                    height,
+                };
+                y += height;
+            }
+            rect.x += width;
+            rect.width -= width;
+        } else {
+            let height = sum / rect.width;
+            let mut x = rect.x;
+            for (index, area) in areas {
+                let width = area / height;
+                result[*index] = Rect {
+                    x,
+                    y: rect.y,
+                    width,
+                    height,
+                };
+                x += width;
+            }
+            rect.y += height;
+            rect.height -= height;
+        }
+    }
+
+    let empty = Rect {
+        width: 0.0,
+        height: 0.0,
+        ..rect
+    };
+    let mut result = vec![empty; areas.len()];
+    let positive: Vec<(usize, f64)> = areas
+        .iter()
+        .copied()
+        .enumerate()
+        .filter(|(_, area)| *area > 0.0)
+        .collect();
+    let mut row_start = 0;
+    for row_end in 1..positive.len() {
+        let row = &positive[row_start..row_end];
+        let length = rect.width.min(rect.height);
+        if worst(&positive[row_start..=row_end], length) > worst(row, length) {
+            lay_row(row, &mut rect, &mut result);
+            row_start = row_end;
+        }
+    }
+    if row_start < positive.len() {
+        lay_row(&positive[row_start..], &mut rect, &mut result);
+    }
+    result
+}
@@ -0,0 +1,183 @@
+pub mod column;
+pub mod delimiter;
+
+pub use column::Column;
+pub use delimiter::Delimiter;
+
+use crate::data_tree::DataTree;
+use crate::size;
+use std::ffi::OsStr;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Flatten a [`DataTree`] into rows of delimiter-separated values.
+///
+/// The table can be written by [`write_to`](Self::write_to), which preserves names that are
+/// not valid UTF-8, or created by the [`Display`] trait, which replaces them lossily. The first
+/// row names the [columns](Self::columns). Every other row describes a single node, and every
+/// node precedes its children.
+///
+/// **Example:**
+///
+/// ```no_run
+/// # use parallel_disk_usage::data_tree::DataTree;
+/// # use parallel_disk_usage::os_string_display::OsStringDisplay;
+/// # use parallel_disk_usage::size::Bytes;
+/// # use parallel_disk_usage::table::{Column, Delimiter, Table};
+/// # fn _wrapper(create_data_tree: fn() -> DataTree<OsStringDisplay, Bytes>) {
+/// let data_tree: DataTree<OsStringDisplay, Bytes> = create_data_tree();
+/// let table = Table {
+///     data_tree: &data_tree,
+///     columns: &[Column::Path, Column::Size, Column::PercentOfRoot],
+///     delimiter: Delimiter::Comma,
+///     bytes_format: None,
+///     synthetic_root: false,
+/// };
+/// table.write_to(&mut std::io::stdout()).unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Table<'a, Name, Size>
+where
+    Size: size::Size,
+{
+    /// The tree to flatten.
+    pub data_tree: &'a DataTree<Name, Size>,
+    /// The columns of each row.
+    pub columns: &'a [Column],
+    /// The character that separates the values of a row.
+    pub delimiter: Delimiter,
+    /// Format to be used to [`display`](size::Size::display) the sizes, or [`None`] to write raw numbers.
+    pub bytes_format: Option<Size::DisplayFormat>,
+    /// Whether the root of [`data_tree`](Self::data_tree) does not represent a real entry,
+    /// in which case it is omitted and its children are treated as roots.
+    pub synthetic_root: bool,
+}
+
+/// Location of a node within the tree.
+struct Position<'a> {
+    /// Full path of the node.
+    path: &'a Path,
+    /// Number of ancestors of the node.
+    depth: u64,
+    /// Size of the parent of the node, or of the node itself if it is a root.
+    parent_size: u64,
+    /// Size of the root of the node.
+    root_size: u64,
+}
+
+impl<Name, Size> Table<'_, Name, Size>
+where
+    Name: AsRef<OsStr>,
+    Size: size::Size + Into<u64>,
+{
+    /// Write the header row then the rows of every node.
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let delimiter = self.delimiter.char();
+        for (index, column) in self.columns.iter().enumerate() {
+            if index != 0 {
+                write!(writer, "{delimiter}")?;
+            }
+            write!(writer, "{}", column.name())?;
+        }
+        writeln!(writer)?;
+
+        if self.synthetic_root {
+            return self
+                .data_tree
+                .children()
+                .iter()
+                .try_for_each(|root| self.write_root(writer, root));
+        }
+        self.write_root(writer, self.data_tree)
+    }
+
+    /// Write the row of `node` then the rows of its descendants.
+    fn write_node(
+        &self,
+        writer: &mut impl Write,
+        node: &DataTree<Name, Size>,
+        position: Position<'_>,
+    ) -> io::Result<()> {
+        let size: u64 = node.size().into();
+        let delimiter = self.delimiter.char();
+        for (index, column) in self.columns.iter().enumerate() {
+            if index != 0 {
+                write!(writer, "{delimiter}")?;
+            }
+            let value = match column {
+                Column::Path => {
+                    let path = position.path.as_os_str().as_encoded_bytes();
+                    self.delimiter.write_value(writer, path)?;
+                    continue;
+                }
+                Column::Depth => position.depth.to_string(),
+                Column::Size => match self.bytes_format {
+                    // human-readable sizes are padded for alignment in the chart
+                    Some(bytes_format) => node
+                        .size()
+                        .display(bytes_format)
+                        .to_string()
+                        .trim()
+                        .to_string(),
+                    None => size.to_string(),
+                },
+                Column::PercentOfParent => percentage(size, position.parent_size),
+                Column::PercentOfRoot => percentage(size, position.root_size),
+            };
+            self.delimiter.write_value(writer, value.as_bytes())?;
+        }
+        writeln!(writer)?;
+
+        for child in node.children() {
+            let name: &OsStr = child.name().as_ref();
+            let path = position.path.join(name);
+            self.write_node(
+                writer,
+                child,
+                Position {
+                    path: &path,
+                    depth: position.depth + 1,
+                    parent_size: size,
+                    root_size: position.root_size,
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the rows of a root and its descendants.
+    fn write_root(&self, writer: &mut impl Write, root: &DataTree<Name, Size>) -> io::Result<()> {
+        let size = root.size().into();
+        let path = PathBuf::from(root.name());
+        let position = Position {
+            path: &path,
+            depth: 0,
+            parent_size: size,
+            root_size: size,
+        };
+        self.write_node(writer, root, position)
+    }
+}
+
+impl<Name, Size> Display for Table<'_, Name, Size>
+where
+    Name: AsRef<OsStr>,
+    Size: size::Size + Into<u64>,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        let mut buffer = Vec::new();
+        self.write_to(&mut buffer).map_err(|_| fmt::Error)?;
+        formatter.write_str(&String::from_utf8_lossy(&buffer))
+    }
+}
+
+/// Express `part` as a percentage of `whole` with 2 decimal places.
+fn percentage(part: u64, whole: u64) -> String {
+    if whole == 0 {
+        return "0.00".to_string();
+    }
+    format!("{:.2}", part as f64 * 100.0 / whole as f64)
+}
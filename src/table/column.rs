@@ -0,0 +1,40 @@
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// Column of a [`Table`](super::Table).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum Column {
+    /// Full path of the entry.
+    Path,
+    /// Number of ancestors of the entry.
+    Depth,
+    /// Disk usage of a file or total disk usage of a folder.
+    Size,
+    /// Percentage of the size of the parent.
+    PercentOfParent,
+    /// Percentage of the size of the root.
+    PercentOfRoot,
+}
+
+impl Column {
+    /// All columns in their default order.
+    pub const ALL: &[Column] = &[
+        Column::Path,
+        Column::Depth,
+        Column::Size,
+        Column::PercentOfParent,
+        Column::PercentOfRoot,
+    ];
+
+    /// Name of the column in the header row.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Column::Path => "path",
+            Column::Depth => "depth",
+            Column::Size => "size",
+            Column::PercentOfParent => "percent-of-parent",
+            Column::PercentOfRoot => "percent-of-root",
+        }
+    }
+}
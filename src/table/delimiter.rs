@@ -0,0 +1,56 @@
+use std::io::{self, Write};
+
+/// Character that separates the values of a row of a [`Table`](super::Table).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// Comma-separated values as described by RFC 4180.
+    ///
+    /// Values that contain a comma, a double quote, or a line break are enclosed in
+    /// double quotes, and their double quotes are doubled.
+    Comma,
+    /// Tab-separated values.
+    ///
+    /// Backslashes, tabs, and line breaks within values are escaped as `\\`, `\t`, `\n`, and `\r`.
+    Tab,
+}
+
+impl Delimiter {
+    /// The separating character.
+    pub const fn char(self) -> char {
+        match self {
+            Delimiter::Comma => ',',
+            Delimiter::Tab => '\t',
+        }
+    }
+
+    /// Write a single value, escaped as necessary.
+    ///
+    /// The value is written byte for byte, so names that are not valid UTF-8 are preserved.
+    pub fn write_value(self, writer: &mut impl Write, value: &[u8]) -> io::Result<()> {
+        match self {
+            Delimiter::Comma => {
+                if !value
+                    .iter()
+                    .any(|byte| matches!(byte, b',' | b'"' | b'\n' | b'\r'))
+                {
+                    return writer.write_all(value);
+                }
+                writer.write_all(b"\"")?;
+                for chunk in value.split_inclusive(|byte| *byte == b'"') {
+                    writer.write_all(chunk)?;
+                    if chunk.ends_with(b"\"") {
+                        writer.write_all(b"\"")?;
+                    }
+                }
+                writer.write_all(b"\"")
+            }
+            Delimiter::Tab => value.iter().try_for_each(|byte| match byte {
+                b'\\' => writer.write_all(b"\\\\"),
+                b'\t' => writer.write_all(b"\\t"),
+                b'\n' => writer.write_all(b"\\n"),
+                b'\r' => writer.write_all(b"\\r"),
+                _ => writer.write_all(&[*byte]),
+            }),
+        }
+    }
+}
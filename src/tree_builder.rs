@@ -3,6 +3,7 @@ pub mod info;
 pub use info::Info;
 
 use super::data_tree::DataTree;
+use super::node_metadata::NodeMetadata;
 use super::size;
 use rayon::prelude::*;
 
@@ -46,26 +47,120 @@ where
             join_path,
             max_depth,
         } = builder;
+        build_with_metadata(
+            path,
+            name,
+            |path| (get_info(path), None),
+            join_path,
+            max_depth,
+            |_, _, _, tree| tree,
+        )
+    }
+}
+
+/// Build a [`DataTree`] like [`TreeBuilder`] does, except that `get_info` also returns the
+/// [metadata](NodeMetadata) of each path, which is [aggregated](NodeMetadata::absorb) with
+/// that of the descendants.
+///
+/// Every node within `max_depth` is passed to `complete` along with its path, the path of its
+/// parent, and its depth as soon as its subtree is built, and is replaced by what it returns.
+pub(crate) fn build_with_metadata<Path, Name, Size, GetInfo, JoinPath, Complete>(
+    path: Path,
+    name: Name,
+    get_info: GetInfo,
+    join_path: JoinPath,
+    max_depth: u64,
+    complete: Complete,
+) -> DataTree<Name, Size>
+where
+    Path: Send + Sync,
+    Name: Send + Sync,
+    GetInfo: Fn(&Path) -> (Info<Name, Size>, Option<Box<NodeMetadata>>) + Copy + Send + Sync,
+    JoinPath: Fn(&Path, &Name) -> Path + Copy + Send + Sync,
+    Complete: Fn(&Path, Option<&Path>, u64, DataTree<Name, Size>) -> DataTree<Name, Size>
+        + Copy
+        + Send
+        + Sync,
+    Size: size::Size + Send,
+{
+    let functions = Functions {
+        get_info,
+        join_path,
+        complete,
+    };
+    build_node(path, None, name, 0, max_depth, functions)
+}
+
+/// Functions passed down by [`build_node`].
+#[derive(Clone, Copy)]
+struct Functions<GetInfo, JoinPath, Complete> {
+    get_info: GetInfo,
+    join_path: JoinPath,
+    complete: Complete,
+}
 
-        let Info { size, children } = get_info(&path);
-        let max_depth = max_depth.saturating_sub(1);
+/// Internal function to be used by [`build_with_metadata`].
+fn build_node<Path, Name, Size, GetInfo, JoinPath, Complete>(
+    path: Path,
+    parent: Option<&Path>,
+    name: Name,
+    depth: u64,
+    max_depth: u64,
+    functions: Functions<GetInfo, JoinPath, Complete>,
+) -> DataTree<Name, Size>
+where
+    Path: Send + Sync,
+    Name: Send + Sync,
+    GetInfo: Fn(&Path) -> (Info<Name, Size>, Option<Box<NodeMetadata>>) + Copy + Send + Sync,
+    JoinPath: Fn(&Path, &Name) -> Path + Copy + Send + Sync,
+    Complete: Fn(&Path, Option<&Path>, u64, DataTree<Name, Size>) -> DataTree<Name, Size>
+        + Copy
+        + Send
+        + Sync,
+    Size: size::Size + Send,
+{
+    let Functions {
+        get_info,
+        join_path,
+        complete,
+    } = functions;
+    let (Info { size, children }, metadata) = get_info(&path);
+    let within_max_depth = max_depth > 0;
+    let max_depth = max_depth.saturating_sub(1);
 
-        let children = children
-            .into_par_iter()
-            .map(|name| TreeBuilder {
-                path: join_path(&path, &name),
+    let children: Vec<_> = children
+        .into_par_iter()
+        .map(|name| {
+            let child_path = join_path(&path, &name);
+            build_node(
+                child_path,
+                Some(&path),
                 name,
-                get_info,
-                join_path,
+                depth + 1,
                 max_depth,
-            })
-            .map(Self::from);
+                functions,
+            )
+        })
+        .collect();
+
+    let metadata = metadata.map(|mut metadata| {
+        children
+            .iter()
+            .filter_map(DataTree::metadata)
+            .for_each(|child| metadata.absorb(child));
+        metadata
+    });
+
+    let tree = if max_depth > 0 {
+        DataTree::dir(name, size, children).with_metadata(metadata)
+    } else {
+        let size = size + children.iter().map(DataTree::size).sum();
+        DataTree::dir(name, size, Vec::new()).with_metadata(metadata)
+    };
 
-        if max_depth > 0 {
-            DataTree::dir(name, size, children.collect())
-        } else {
-            let size = size + children.map(|child| child.size()).sum();
-            DataTree::dir(name, size, Vec::new())
-        }
+    if within_max_depth {
+        complete(&path, parent, depth, tree)
+    } else {
+        tree
     }
 }
@@ -1,17 +1,27 @@
 pub mod bar_alignment;
+pub mod chart_column;
+pub mod chart_options;
 pub mod child_position;
+pub mod coloring;
 pub mod column_width_distribution;
 pub mod direction;
+pub mod flat_list;
 pub mod parenthood;
 pub mod proportion_bar;
+pub mod quoting_style;
 pub mod tree;
 
 pub use bar_alignment::BarAlignment;
+pub use chart_column::ChartColumn;
+pub use chart_options::ChartOptions;
 pub use child_position::ChildPosition;
+pub use coloring::{BarColoring, Coloring, LsColors};
 pub use column_width_distribution::ColumnWidthDistribution;
 pub use direction::Direction;
+pub use flat_list::FlatList;
 pub use parenthood::Parenthood;
 pub use proportion_bar::{ProportionBar, ProportionBarBlock};
+pub use quoting_style::{QuotableName, QuotingStyle};
 pub use tree::{TreeHorizontalSlice, TreeSkeletalComponent};
 
 use super::data_tree::DataTree;
@@ -61,6 +71,37 @@ where
     pub column_width_distribution: ColumnWidthDistribution,
 }
 
+/// A [`Visualizer`] combined with [`ChartOptions`].
+///
+/// It is created by [`Visualizer::with_options`], and the [`Display`] trait can be used to create
+/// the ASCII chart.
+pub struct Chart<'a, Name, Size>
+where
+    Name: Display,
+    Size: size::Size,
+{
+    /// The basic construction parameters of the chart.
+    pub visualizer: Visualizer<'a, Name, Size>,
+    /// The optional settings of the chart.
+    pub options: ChartOptions<'a>,
+}
+
+impl<'a, Name, Size> Visualizer<'a, Name, Size>
+where
+    Name: Display,
+    Size: size::Size,
+{
+    /// Combine the visualizer with [`ChartOptions`].
+    #[inline]
+    pub fn with_options(self, options: ChartOptions<'a>) -> Chart<'a, Name, Size> {
+        Chart {
+            visualizer: self,
+            options,
+        }
+    }
+}
+
 mod copy;
+mod debug;
 mod display;
 mod methods;
@@ -0,0 +1,44 @@
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// Column of the chart created by [`Visualizer`](super::Visualizer), other than the tree and the bars.
+///
+/// The percentage columns are placed after the bars, the other columns are placed before the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum ChartColumn {
+    /// Disk usage of a file or total disk usage of a folder.
+    Size,
+    /// Percentage of the size of the root.
+    Percent,
+    /// Percentage of the size of the parent.
+    PercentOfParent,
+    /// Number of entries other than directories in the subtree.
+    ///
+    /// It requires [`NodeMetadata::count`](crate::node_metadata::NodeMetadata::count).
+    Count,
+    /// Newest modification time in the subtree, in UTC.
+    ///
+    /// It requires [`NodeMetadata::mtime`](crate::node_metadata::NodeMetadata::mtime).
+    Mtime,
+}
+
+impl ChartColumn {
+    /// Columns of the chart when none is specified.
+    pub const DEFAULT: &[ChartColumn] = &[ChartColumn::Size, ChartColumn::Percent];
+
+    /// Number of variants.
+    pub(super) const COUNT: usize = 5;
+
+    /// Position of the variant in the declaration.
+    #[inline]
+    pub(super) const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Whether the column is placed after the bars instead of before the tree.
+    #[inline]
+    pub const fn follows_bar(self) -> bool {
+        matches!(self, ChartColumn::Percent | ChartColumn::PercentOfParent)
+    }
+}
@@ -0,0 +1,28 @@
+use super::{ChartColumn, Coloring, QuotingStyle};
+use derive_setters::Setters;
+use smart_default::SmartDefault;
+
+/// Optional settings of a [`Chart`](super::Chart) on top of those of a
+/// [`Visualizer`](super::Visualizer).
+///
+/// **Example:**
+///
+/// ```
+/// # use parallel_disk_usage::visualizer::{ChartColumn, ChartOptions, QuotingStyle};
+/// let options = ChartOptions::default()
+///     .with_columns(&[ChartColumn::Size, ChartColumn::Count])
+///     .with_quoting_style(QuotingStyle::Shell);
+/// ```
+#[derive(Debug, SmartDefault, Clone, Copy, Setters)]
+#[setters(prefix = "with_")]
+#[non_exhaustive]
+pub struct ChartOptions<'a> {
+    /// Columns to display besides the tree and the bars.
+    #[default(ChartColumn::DEFAULT)]
+    pub columns: &'a [ChartColumn],
+    /// How to write the names of the entries.
+    pub quoting_style: QuotingStyle,
+    /// How to color the chart, or [`None`] for a monochrome chart.
+    #[setters(strip_option)]
+    pub coloring: Option<Coloring<'a>>,
+}
@@ -0,0 +1,125 @@
+pub mod bar_coloring;
+pub mod ls_colors;
+
+pub use bar_coloring::BarColoring;
+pub use ls_colors::LsColors;
+
+use super::{BarAlignment, ProportionBar, proportion_bar};
+use crate::node_metadata::EntryType;
+use std::fmt::{Display, Write};
+
+/// Settings to color the chart with ANSI escape sequences.
+///
+/// The escape sequences are added after the columns are padded, so they never affect the
+/// width calculations of [`Visualizer`](super::Visualizer).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Coloring<'a> {
+    /// How to color the bars.
+    pub bar_coloring: BarColoring,
+    /// Styles of the names by file type.
+    pub ls_colors: &'a LsColors,
+    /// Whether the root is a synthetic node (such as `(total)`) which should be dimmed.
+    pub synthetic_root: bool,
+}
+
+/// SGR parameter that makes the text bold.
+const BOLD: &str = "1";
+/// SGR parameter that makes the text dim.
+const DIM: &str = "2";
+/// Foreground colors of the bars at each depth, cycled.
+const DEPTH_PALETTE: [&str; 6] = ["36", "32", "33", "35", "34", "31"];
+
+/// Wrap `content` in the escape sequences of the SGR parameters in `codes`.
+///
+/// Empty parameters are skipped, and `content` is returned as is if none are left.
+pub(super) fn paint<'a>(codes: impl IntoIterator<Item = &'a str>, content: impl Display) -> String {
+    let codes: Vec<&str> = codes.into_iter().filter(|code| !code.is_empty()).collect();
+    if codes.is_empty() {
+        return content.to_string();
+    }
+    format!("\x1b[{}m{content}\x1b[0m", codes.join(";"))
+}
+
+/// Emphasis of a row as a whole.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Emphasis {
+    /// Nothing special about the row.
+    Normal,
+    /// The row is the largest amongst its siblings.
+    Highlighted,
+    /// The row is synthetic or its children are not shown.
+    Dimmed,
+}
+
+impl Emphasis {
+    /// SGR parameter of the emphasis.
+    pub(super) const fn code(self) -> &'static str {
+        match self {
+            Emphasis::Normal => "",
+            Emphasis::Highlighted => BOLD,
+            Emphasis::Dimmed => DIM,
+        }
+    }
+}
+
+impl Coloring<'_> {
+    /// SGR parameters of the name of a node.
+    pub(super) fn name_style(&self, name: &str, entry_type: Option<EntryType>) -> &str {
+        self.ls_colors.style(name, entry_type).unwrap_or_default()
+    }
+
+    /// SGR parameter of the bar block that represents a node at `depth` whose size takes
+    /// up `ratio` of the total.
+    pub(super) fn bar_style(&self, depth: usize, ratio: f64) -> String {
+        match self.bar_coloring {
+            BarColoring::Depth => DEPTH_PALETTE[depth % DEPTH_PALETTE.len()].to_string(),
+            BarColoring::Gradient => {
+                // walk the 6x6x6 cube of the 256-color palette from green through yellow to red
+                let ratio = ratio.clamp(0.0, 1.0);
+                let (red, green) = if ratio < 0.5 {
+                    ((ratio * 10.0).round() as u8, 5)
+                } else {
+                    (5, ((1.0 - ratio) * 10.0).round() as u8)
+                };
+                format!("38;5;{}", 16 + 36 * red + 6 * green)
+            }
+        }
+    }
+
+    /// Render a proportion bar whose blocks are colored by their levels.
+    ///
+    /// `styles` contains the SGR parameters of the blocks of level 0 to level 3.
+    pub(super) fn paint_bar(
+        &self,
+        bar: ProportionBar,
+        align: BarAlignment,
+        styles: [&str; 4],
+    ) -> String {
+        let ProportionBar {
+            level0,
+            level1,
+            level2,
+            level3,
+            level4,
+        } = bar;
+        let mut segments = [
+            (proportion_bar::LEVEL0_BLOCK, level0, styles[0]),
+            (proportion_bar::LEVEL1_BLOCK, level1, styles[1]),
+            (proportion_bar::LEVEL2_BLOCK, level2, styles[2]),
+            (proportion_bar::LEVEL3_BLOCK, level3, styles[3]),
+            (proportion_bar::LEVEL4_BLOCK, level4, ""),
+        ];
+        if align == BarAlignment::Right {
+            segments.reverse();
+        }
+        let mut result = String::new();
+        for (block, count, style) in segments {
+            if count == 0 {
+                continue;
+            }
+            let blocks = fmt_iter::repeat(block, count);
+            write!(result, "{}", paint([style], blocks)).expect("write to a string");
+        }
+        result
+    }
+}
@@ -0,0 +1,13 @@
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+
+/// How to color the bars of the chart.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum BarColoring {
+    /// Give each level of the tree a different color.
+    #[default]
+    Depth,
+    /// Shift from green to red as the proportion of the total grows.
+    Gradient,
+}
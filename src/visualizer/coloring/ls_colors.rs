@@ -0,0 +1,77 @@
+use crate::node_metadata::EntryType;
+use std::env;
+
+/// Styles of the names by file type, in the format of the `LS_COLORS` environment variable.
+///
+/// Only the keys which are relevant to the chart are recognized: `di` (directory), `ln`
+/// (symbolic link), `fi` (regular file), and `*<suffix>` patterns (regular files whose names
+/// end with `<suffix>`, case-insensitive). The other keys are ignored.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LsColors {
+    directory: Option<String>,
+    symlink: Option<String>,
+    file: Option<String>,
+    suffixes: Vec<(String, String)>,
+}
+
+impl LsColors {
+    /// Styles to use when `LS_COLORS` is not set, the same as the defaults of GNU `ls`.
+    pub const DEFAULT: &str = "di=01;34:ln=01;36";
+
+    /// Parse the content of `LS_COLORS`.
+    pub fn parse(text: &str) -> Self {
+        let mut ls_colors = LsColors::default();
+        for (key, style) in text.split(':').filter_map(|entry| entry.split_once('=')) {
+            if style.is_empty() || style == "target" {
+                continue;
+            }
+            let style = style.to_string();
+            match key {
+                "di" => ls_colors.directory = Some(style),
+                "ln" => ls_colors.symlink = Some(style),
+                "fi" => ls_colors.file = Some(style),
+                _ => {
+                    if let Some(suffix) = key.strip_prefix('*') {
+                        ls_colors.suffixes.push((suffix.to_lowercase(), style));
+                    }
+                }
+            }
+        }
+        ls_colors
+    }
+
+    /// Read the styles from the `LS_COLORS` environment variable, or use [`LsColors::DEFAULT`]
+    /// if it is not set.
+    pub fn from_env() -> Self {
+        match env::var("LS_COLORS") {
+            Ok(text) => LsColors::parse(&text),
+            Err(_) => LsColors::parse(LsColors::DEFAULT),
+        }
+    }
+
+    /// Get the SGR parameters of a name.
+    ///
+    /// Regular files are matched against the suffix patterns, the latest matching pattern wins.
+    /// The names of other kinds of entries (such as sockets and device files) are not styled.
+    pub fn style(&self, name: &str, entry_type: Option<EntryType>) -> Option<&str> {
+        let style = match entry_type {
+            Some(EntryType::Dir) => &self.directory,
+            Some(EntryType::Symlink) => &self.symlink,
+            Some(EntryType::Other) => return None,
+            Some(EntryType::File) | None => {
+                let name = name.to_lowercase();
+                let suffix_style = self
+                    .suffixes
+                    .iter()
+                    .rev()
+                    .find(|(suffix, _)| name.ends_with(suffix.as_str()))
+                    .map(|(_, style)| style);
+                if suffix_style.is_some() {
+                    return suffix_style.map(String::as_str);
+                }
+                &self.file
+            }
+        };
+        style.as_deref()
+    }
+}
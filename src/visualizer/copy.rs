@@ -1,4 +1,4 @@
-use super::Visualizer;
+use super::{Chart, Visualizer};
 use crate::size;
 use std::fmt::Display;
 
@@ -18,3 +18,20 @@ where
     Size: size::Size,
 {
 }
+
+impl<'a, Name, Size> Clone for Chart<'a, Name, Size>
+where
+    Name: Display,
+    Size: size::Size,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, Name, Size> Copy for Chart<'a, Name, Size>
+where
+    Name: Display,
+    Size: size::Size,
+{
+}
@@ -0,0 +1,18 @@
+use super::{Chart, Visualizer};
+use crate::size;
+use std::fmt::{self, Debug, Display, Formatter};
+
+impl<'a, Name, Size> Debug for Chart<'a, Name, Size>
+where
+    Name: Display,
+    Size: size::Size,
+    Visualizer<'a, Name, Size>: Debug,
+{
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("Chart")
+            .field("visualizer", &self.visualizer)
+            .field("options", &self.options)
+            .finish()
+    }
+}
@@ -1,18 +1,29 @@
-use super::{Direction, Visualizer};
+use super::{Chart, ChartOptions, Direction, QuotableName, Visualizer};
 use crate::size;
 use std::fmt::{Display, Error, Formatter};
 
-impl<'a, Name, Size> Display for Visualizer<'a, Name, Size>
+impl<'a, Name, Size> Display for Chart<'a, Name, Size>
 where
-    Name: Display,
+    Name: Display + QuotableName,
     Size: size::Size + Into<u64>,
 {
     /// Create the ASCII chart.
     fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), Error> {
         let write = |line: &String| writeln!(formatter, "{line}");
-        match self.direction {
+        match self.visualizer.direction {
             Direction::BottomUp => self.rows().iter().rev().try_for_each(write),
             Direction::TopDown => self.rows().iter().try_for_each(write),
         }
     }
 }
+
+impl<'a, Name, Size> Display for Visualizer<'a, Name, Size>
+where
+    Name: Display + QuotableName,
+    Size: size::Size + Into<u64>,
+{
+    /// Create the ASCII chart with the default [`ChartOptions`].
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result<(), Error> {
+        self.with_options(ChartOptions::default()).fmt(formatter)
+    }
+}
@@ -0,0 +1,65 @@
+use super::QuotingStyle;
+use crate::data_tree::DataTree;
+use crate::size;
+use std::ffi::OsStr;
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+
+/// List the nodes of a [`DataTree`] from the largest, each with its size, its percentage of
+/// the total, and its full path.
+///
+/// The [`Display`] trait can be used to create the list.
+#[derive(Debug)]
+pub struct FlatList<'a, Name, Size: size::Size> {
+    /// The tree to list.
+    pub data_tree: &'a DataTree<Name, Size>,
+    /// Format to be used to display the sizes.
+    pub bytes_format: Size::DisplayFormat,
+    /// How to quote the paths.
+    pub quoting_style: QuotingStyle,
+    /// Whether to omit the nodes that have descendants in the tree, which are always listed.
+    pub exclude_ancestors: bool,
+    /// Whether the root does not represent a real entry, so its name is not part of the paths.
+    pub synthetic_root: bool,
+}
+
+impl<Name, Size> Display for FlatList<'_, Name, Size>
+where
+    Name: AsRef<OsStr>,
+    Size: size::Size + Into<u64>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let FlatList {
+            data_tree,
+            bytes_format,
+            quoting_style,
+            exclude_ancestors,
+            synthetic_root,
+        } = *self;
+        let total: u64 = data_tree.size().into();
+        let rows: Vec<_> = data_tree
+            .flatten()
+            .into_iter()
+            .filter(|flat_node| !exclude_ancestors || flat_node.node.children().is_empty())
+            .map(|flat_node| {
+                let size = flat_node.node.size();
+                let path: PathBuf = if synthetic_root && !flat_node.ancestors.is_empty() {
+                    flat_node.path().skip(1).map(AsRef::as_ref).collect()
+                } else {
+                    flat_node.path().map(AsRef::as_ref).collect()
+                };
+                let percentage = match total {
+                    0 => 0,
+                    total => rounded_div::u64(size.into() * 100, total),
+                };
+                (size.display(bytes_format).to_string(), percentage, path)
+            })
+            .collect();
+        let size_width = rows.iter().map(|(size, ..)| size.len()).max().unwrap_or(0);
+        for (size, percentage, path) in rows {
+            let path = quoting_style.quote_os_str(path.as_os_str());
+            writeln!(f, "{size:>size_width$} {percentage:>3}% {path}")?;
+        }
+        Ok(())
+    }
+}
@@ -4,20 +4,35 @@ mod initial_table;
 mod node_info;
 mod table;
 mod tree_table;
+mod utc_time;
 
-use super::{ColumnWidthDistribution, Visualizer};
+use super::coloring::{Emphasis, paint};
+use super::{Chart, ChartOptions, ColumnWidthDistribution, QuotableName, Visualizer};
+use crate::node_metadata::EntryType;
 use crate::size;
 use bar_table::render_bars;
-use constants::{MIN_OVERALL_WIDTH, PERCENTAGE_COLUMN_MAX_WIDTH};
+use constants::MIN_OVERALL_WIDTH;
 use initial_table::render_initial;
 use std::cmp::min;
-use std::fmt::Display;
+use std::fmt::{Display, Write};
 use tree_table::render_tree;
-use zero_copy_pads::{align_left, align_right};
+use zero_copy_pads::{Width, align_left, align_right};
 
 impl<'a, Name, Size> Visualizer<'a, Name, Size>
 where
-    Name: Display,
+    Name: Display + QuotableName,
+    Size: size::Size + Into<u64>,
+{
+    /// Create ASCII rows that visualize the [tree](crate::data_tree::DataTree) with the default
+    /// [`ChartOptions`], such rows are meant to be printed to a terminal screen.
+    pub fn rows(self) -> Vec<String> {
+        self.with_options(ChartOptions::default()).rows()
+    }
+}
+
+impl<'a, Name, Size> Chart<'a, Name, Size>
+where
+    Name: Display + QuotableName,
     Size: size::Size + Into<u64>,
 {
     /// Create ASCII rows that visualize the [tree](crate::data_tree::DataTree), such rows
@@ -26,18 +41,20 @@ where
         let initial_table = render_initial(self);
         let min_width = initial_table.column_width.total_max_width();
 
-        let (tree_table, bar_width) = match self.column_width_distribution {
+        let (tree_table, bar_width) = match self.visualizer.column_width_distribution {
             ColumnWidthDistribution::Total { width } => {
                 let extra_cols = 3; // make space for tree_column to minimize second-time re-rendering.
 
                 if width <= min_width {
-                    self.column_width_distribution
+                    self.visualizer
+                        .column_width_distribution
                         .set_components(min_width, extra_cols);
                     return self.rows();
                 }
 
                 if width <= MIN_OVERALL_WIDTH {
-                    self.column_width_distribution
+                    self.visualizer
+                        .column_width_distribution
                         .set_components(min_width, MIN_OVERALL_WIDTH + extra_cols);
                     return self.rows();
                 }
@@ -47,7 +64,9 @@ where
 
                 let min_width = tree_table.column_width.total_max_width();
                 if width <= min_width {
-                    self.column_width_distribution.set_components(min_width, 1);
+                    self.visualizer
+                        .column_width_distribution
+                        .set_components(min_width, 1);
                     return self.rows();
                 }
 
@@ -61,7 +80,8 @@ where
                 bar_column_width,
             } => {
                 if bar_column_width < 1 {
-                    self.column_width_distribution
+                    self.visualizer
+                        .column_width_distribution
                         .set_components(tree_column_max_width, 1);
                     return self.rows();
                 }
@@ -72,20 +92,91 @@ where
             }
         };
 
-        let size_width = tree_table.column_width.size_column_width;
+        let cell_widths = tree_table.column_width.cell_widths;
         let tree_width = tree_table.column_width.tree_column_width;
 
-        let bar_table = render_bars(tree_table, self.data_tree.size().into(), bar_width);
+        let total = self.visualizer.data_tree.size().into();
+        let bar_table = render_bars(tree_table, total, bar_width);
 
         bar_table
             .into_iter()
             .map(|row| {
+                let mut preceding_cells = String::new();
+                let mut following_cells = String::new();
+                for column in self.options.columns.iter().copied() {
+                    let index = column.index();
+                    let cell = align_right(&row.cells[index], cell_widths[index]);
+                    if !column.follows_bar() {
+                        write!(preceding_cells, "{cell} ").expect("write to a string");
+                    } else if following_cells.is_empty() {
+                        write!(following_cells, "{cell}").expect("write to a string");
+                    } else {
+                        write!(following_cells, " {cell}").expect("write to a string");
+                    }
+                }
+
+                let Some(coloring) = self.options.coloring else {
+                    return format!(
+                        "{preceding_cells}{tree}│{bar}│{following_cells}",
+                        tree = align_left(&row.tree_horizontal_slice, tree_width),
+                        bar = row.proportion_bar.display(self.visualizer.bar_alignment),
+                    );
+                };
+
+                // the escape sequences are only added to the padded cells to keep the columns aligned
+                let is_synthetic = coloring.synthetic_root && row.row_index == 0;
+                let contents_hidden =
+                    row.entry_type == Some(EntryType::Dir) && row.children_count == 0;
+                let emphasis = if is_synthetic || contents_hidden || row.is_aggregate {
+                    Emphasis::Dimmed
+                } else if row.is_largest_child {
+                    Emphasis::Highlighted
+                } else {
+                    Emphasis::Normal
+                };
+
+                let slice = &row.tree_horizontal_slice;
+                let name_style = if row.is_aggregate {
+                    ""
+                } else {
+                    coloring.name_style(&slice.name, row.entry_type)
+                };
+                let tree = format!(
+                    "{skeleton}{name}{padding}",
+                    skeleton = slice.skeleton(),
+                    name = paint([name_style, emphasis.code()], &slice.name),
+                    padding = " ".repeat(tree_width.saturating_sub(slice.width())),
+                );
+
+                let ratio = |size: Size| {
+                    if total == 0 {
+                        0.0
+                    } else {
+                        size.into() as f64 / total as f64
+                    }
+                };
+                let depth = row.ancestors.len();
+                // level N of the bar (for N from 1 to 3) represents the ancestor at depth 4-N
+                let level_style = |level: usize| {
+                    let ancestor_depth = 4 - level;
+                    row.ancestors
+                        .get(ancestor_depth)
+                        .map(|ancestor| {
+                            coloring.bar_style(ancestor_depth, ratio(ancestor.node_data))
+                        })
+                        .unwrap_or_default()
+                };
+                let own_style = coloring.bar_style(depth, ratio(row.node_data));
+                let (level1, level2, level3) = (level_style(1), level_style(2), level_style(3));
+                let bar = coloring.paint_bar(
+                    row.proportion_bar,
+                    self.visualizer.bar_alignment,
+                    [&own_style, &level1, &level2, &level3],
+                );
+
                 format!(
-                    "{size} {tree}│{bar}│{ratio}",
-                    size = align_right(&row.size, size_width),
-                    tree = align_left(&row.tree_horizontal_slice, tree_width),
-                    bar = row.proportion_bar.display(self.bar_alignment),
-                    ratio = align_right(&row.percentage, PERCENTAGE_COLUMN_MAX_WIDTH),
+                    "{preceding_cells}{tree}│{bar}│{following_cells}",
+                    preceding_cells = paint([emphasis.code()], preceding_cells),
                 )
             })
             .collect()
@@ -1,4 +1,6 @@
 pub const PERCENTAGE_COLUMN_MAX_WIDTH: usize = "100%".len();
 pub const BORDER_COLUMNS: usize = 3; // 4 columns, 3 borders, each border has a width of 1.
+pub const BAR_BORDER_COLUMNS: usize = 2; // 1 border on each side of the bars.
+pub const AGGREGATE_MARKER: &str = "… "; // marks the synthetic entries of --aggregate-culled without colors.
 pub const MIN_BAR_WIDTH: usize = 10;
 pub const MIN_OVERALL_WIDTH: usize = PERCENTAGE_COLUMN_MAX_WIDTH + BORDER_COLUMNS + MIN_BAR_WIDTH;
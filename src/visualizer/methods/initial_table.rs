@@ -1,9 +1,11 @@
-use super::constants::{BORDER_COLUMNS, PERCENTAGE_COLUMN_MAX_WIDTH};
+use super::constants::{BAR_BORDER_COLUMNS, PERCENTAGE_COLUMN_MAX_WIDTH};
 use super::node_info::NodeInfo;
 use super::table::Table;
+use super::utc_time::format_utc_minutes;
 use crate::data_tree::DataTree;
+use crate::node_metadata::EntryType;
 use crate::size;
-use crate::visualizer::Visualizer;
+use crate::visualizer::{Chart, ChartColumn};
 use assert_cmp::debug_assert_op;
 use derive_more::{Deref, DerefMut};
 use std::cmp::max;
@@ -17,8 +19,12 @@ pub(super) struct InitialRow<Name, NodeData> {
     pub(super) node_info: NodeInfo<Name, NodeData>,
     pub(super) ancestors: Vec<NodeInfo<Name, NodeData>>,
     pub(super) preceding_sibling: Option<NodeInfo<Name, NodeData>>,
-    pub(super) size: String,
-    pub(super) percentage: String,
+    /// Content of each column, indexed by [`ChartColumn::index`].
+    pub(super) cells: [String; ChartColumn::COUNT],
+    pub(super) entry_type: Option<EntryType>,
+    pub(super) is_largest_child: bool,
+    /// Whether the node stands for culled entries.
+    pub(super) is_aggregate: bool,
 }
 
 impl<Name, NodeData> InitialRow<Name, NodeData> {
@@ -30,21 +36,33 @@ impl<Name, NodeData> InitialRow<Name, NodeData> {
 
 #[derive(Default, Clone, Copy)]
 pub(super) struct InitialColumnWidth {
-    pub(super) size_column_width: usize,
+    /// Width of each column, indexed by [`ChartColumn::index`].
+    pub(super) cell_widths: [usize; ChartColumn::COUNT],
+    /// Total width of the selected columns, the spaces between them, and the borders of the bars.
+    pub(super) fixed_width: usize,
 }
 
 impl InitialColumnWidth {
     #[inline]
     pub(super) const fn total_max_width(self) -> usize {
-        self.size_column_width + PERCENTAGE_COLUMN_MAX_WIDTH + BORDER_COLUMNS
+        self.fixed_width
     }
 }
 
+/// Express `part` as a rounded percentage of `whole`.
+fn percentage(part: u64, whole: u64) -> String {
+    if whole == 0 {
+        return "0%".to_string();
+    }
+    let percentage = rounded_div::u64(part * 100, whole);
+    format!("{percentage}%")
+}
+
 pub(super) type InitialTable<Name, NodeData> =
     Table<InitialRow<Name, NodeData>, InitialColumnWidth>;
 
 pub(super) fn render_initial<Name, Size>(
-    visualizer: Visualizer<'_, Name, Size>,
+    chart: Chart<'_, Name, Size>,
 ) -> InitialTable<&'_ Name, Size>
 where
     Name: Display,
@@ -53,6 +71,7 @@ where
     #[derive(Clone)]
     struct Param<Name, NodeData> {
         index_as_child: usize,
+        is_largest_child: bool,
         ancestors: Vec<NodeInfo<Name, NodeData>>,
         preceding_sibling: Option<NodeInfo<Name, NodeData>>,
     }
@@ -76,6 +95,16 @@ where
     {
         let ActResult { node_info } = act(tree, param.clone());
         let mut preceding_sibling = None;
+        let largest_child_index = (tree.children().len() > 1)
+            .then(|| {
+                tree.children()
+                    .iter()
+                    .enumerate()
+                    .rev() // the first one wins ties
+                    .max_by_key(|(_, child)| child.size())
+                    .map(|(index, _)| index)
+            })
+            .flatten();
         for (index_as_child, child) in tree.children().iter().enumerate() {
             let mut ancestors = Vec::with_capacity(param.ancestors.len() + 1);
             ancestors.clone_from(&param.ancestors);
@@ -85,6 +114,7 @@ where
                 act,
                 Param {
                     index_as_child,
+                    is_largest_child: largest_child_index == Some(index_as_child),
                     ancestors,
                     preceding_sibling,
                 },
@@ -95,13 +125,14 @@ where
     }
 
     let mut initial_table = InitialTable::default();
-    let total_fs_size = visualizer.data_tree.size().into();
+    let total_fs_size = chart.visualizer.data_tree.size().into();
 
     traverse(
-        visualizer.data_tree,
+        chart.visualizer.data_tree,
         &mut |node, param| {
             let Param {
                 index_as_child,
+                is_largest_child,
                 ancestors,
                 preceding_sibling,
             } = param;
@@ -110,13 +141,35 @@ where
             let row_index = initial_table.len();
             let children_count = node.children().len();
             let fs_size = node.size().into();
-            let percentage = if total_fs_size == 0 {
-                "0%".to_string()
-            } else {
-                let percentage = rounded_div::u64(fs_size * 100, total_fs_size);
-                format!("{percentage}%")
-            };
-            let size = node.size().display(visualizer.bytes_format).to_string();
+            let parent_fs_size = ancestors
+                .last()
+                .map_or(fs_size, |parent| parent.node_data.into());
+            let metadata = node.metadata();
+            let mut cells: [String; ChartColumn::COUNT] = Default::default();
+            for column in chart.options.columns.iter().copied() {
+                let cell = match column {
+                    ChartColumn::Size => node
+                        .size()
+                        .display(chart.visualizer.bytes_format)
+                        .to_string(),
+                    ChartColumn::Percent => percentage(fs_size, total_fs_size),
+                    ChartColumn::PercentOfParent => percentage(fs_size, parent_fs_size),
+                    ChartColumn::Count => metadata
+                        .and_then(|metadata| metadata.count)
+                        .map_or_else(|| "-".to_string(), |count| count.to_string()),
+                    ChartColumn::Mtime => metadata
+                        .and_then(|metadata| metadata.mtime)
+                        .map_or_else(|| "-".to_string(), format_utc_minutes),
+                };
+                let cell_width = &mut initial_table.column_width.cell_widths[column.index()];
+                *cell_width = max(*cell_width, cell.len());
+                cells[column.index()] = cell;
+            }
+            let entry_type = metadata
+                .and_then(|metadata| metadata.entry_type)
+                .or_else(|| (children_count > 0).then_some(EntryType::Dir));
+            let is_aggregate =
+                metadata.is_some_and(|metadata| metadata.aggregated_entries.is_some());
             let sibling_count = ancestors.last().map_or(1, |parent| parent.children_count);
             debug_assert_op!(sibling_count != 0);
             debug_assert_op!(index_as_child < sibling_count);
@@ -130,25 +183,51 @@ where
                 children_count,
             };
 
-            initial_table.column_width.size_column_width =
-                max(initial_table.column_width.size_column_width, size.len());
-
             initial_table.push_back(InitialRow {
                 node_info,
                 ancestors,
                 preceding_sibling,
-                size,
-                percentage,
+                cells,
+                entry_type,
+                is_largest_child,
+                is_aggregate,
             });
 
             ActResult { node_info }
         },
         Param {
             index_as_child: 0,
+            is_largest_child: false,
             ancestors: Vec::new(),
             preceding_sibling: None,
         },
     );
 
+    let column_width = &mut initial_table.column_width;
+    for column in [ChartColumn::Percent, ChartColumn::PercentOfParent] {
+        // keep the widths of the percentages stable regardless of the values
+        let cell_width = &mut column_width.cell_widths[column.index()];
+        if *cell_width != 0 {
+            *cell_width = PERCENTAGE_COLUMN_MAX_WIDTH;
+        }
+    }
+    let following_columns = chart
+        .options
+        .columns
+        .iter()
+        .filter(|column| column.follows_bar())
+        .count();
+    let preceding_columns = chart.options.columns.len() - following_columns;
+    let cells_width: usize = chart
+        .options
+        .columns
+        .iter()
+        .map(|column| column_width.cell_widths[column.index()])
+        .sum();
+    column_width.fixed_width = cells_width
+        + preceding_columns // each is followed by a space
+        + following_columns.saturating_sub(1) // they are separated by spaces
+        + BAR_BORDER_COLUMNS;
+
     initial_table
 }
@@ -1,8 +1,9 @@
+use super::constants::AGGREGATE_MARKER;
 use super::initial_table::{InitialColumnWidth, InitialRow, InitialTable};
 use super::table::Table;
 use crate::size;
 use crate::visualizer::{
-    ChildPosition, Parenthood, TreeHorizontalSlice, TreeSkeletalComponent, Visualizer,
+    Chart, ChildPosition, Parenthood, QuotableName, TreeHorizontalSlice, TreeSkeletalComponent,
 };
 use assert_cmp::{debug_assert_op, debug_assert_op_expr};
 use derive_more::{Deref, DerefMut};
@@ -39,12 +40,12 @@ impl TreeColumnWidth {
 pub(super) type TreeTable<Name, NodeData> = Table<TreeRow<Name, NodeData>, TreeColumnWidth>;
 
 pub(super) fn render_tree<'a, Name, Size>(
-    visualizer: Visualizer<'a, Name, Size>,
+    chart: Chart<'a, Name, Size>,
     initial_table: InitialTable<&'a Name, Size>,
     max_width: usize,
 ) -> TreeTable<&'a Name, Size>
 where
-    Name: Display,
+    Name: Display + QuotableName,
     Size: size::Size + Into<u64>,
 {
     let InitialTable {
@@ -66,7 +67,7 @@ where
             let skeletal_component = TreeSkeletalComponent {
                 child_position,
                 parenthood,
-                direction: visualizer.direction,
+                direction: chart.visualizer.direction,
             };
             let ancestor_relative_positions = initial_row
                 .ancestors
@@ -78,7 +79,12 @@ where
             let mut tree_horizontal_slice = TreeHorizontalSlice {
                 ancestor_relative_positions,
                 skeletal_component,
-                name: initial_row.name.to_string(),
+                name: if initial_row.is_aggregate {
+                    // synthetic, nothing to escape
+                    format!("{AGGREGATE_MARKER}{}", initial_row.name)
+                } else {
+                    initial_row.name.quoted(chart.options.quoting_style)
+                },
             };
             if let Ok(()) = tree_horizontal_slice.truncate(max_width) {
                 tree_column_width.tree_column_width = max(
@@ -0,0 +1,29 @@
+/// Format seconds since the Unix epoch as `YYYY-MM-DD HH:MM` in UTC.
+pub(super) fn format_utc_minutes(seconds: u64) -> String {
+    let days = seconds / 86400;
+    let minutes_of_day = seconds % 86400 / 60;
+    let (year, month, day) = civil_from_days(days);
+    let (hour, minute) = (minutes_of_day / 60, minutes_of_day % 60);
+    format!("{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}")
+}
+
+/// Convert the number of days since 1970-01-01 into a date of the proleptic Gregorian calendar.
+///
+/// The algorithm is `civil_from_days` from <https://howardhinnant.github.io/date_algorithms.html>.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719468;
+    let era = days / 146097;
+    let day_of_era = days % 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153; // March is 0
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
@@ -0,0 +1,221 @@
+use crate::os_string_display::OsStringDisplay;
+#[cfg(feature = "cli")]
+use clap::ValueEnum;
+use std::borrow::Cow;
+use std::ffi::OsStr;
+use std::fmt::{Debug, Write};
+
+/// How to write the names of the entries in the chart.
+///
+/// File names may contain control characters, terminal escape sequences, and bidirectional
+/// overrides, all of which can corrupt or spoof the chart when written verbatim.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum QuotingStyle {
+    /// Write the names as they are.
+    Literal,
+    /// Replace the unsafe characters with C-like escapes such as `\n`, `\e`, and `\u{202e}`,
+    /// and the bytes that are not valid UTF-8 with `\xNN`. Backslashes are written as `\\`, so
+    /// that the escapes are unambiguous.
+    #[default]
+    Escape,
+    /// Quote the names for POSIX shells, write the unsafe characters and the bytes that are not
+    /// valid UTF-8 as `$'...'` escapes.
+    Shell,
+}
+
+/// Piece of a name to be quoted.
+#[derive(Clone, Copy)]
+enum Piece {
+    /// A valid character.
+    Char(char),
+    /// A byte that is not part of a valid UTF-8 sequence.
+    Byte(u8),
+}
+
+/// Whether a character should not be written verbatim to a terminal.
+fn is_unsafe(char: char) -> bool {
+    char.is_control()
+        || matches!(
+            char,
+            '\u{061C}' // arabic letter mark
+                | '\u{200E}' | '\u{200F}' // left-to-right mark, right-to-left mark
+                | '\u{202A}'..='\u{202E}' // embeddings and overrides
+                | '\u{2066}'..='\u{2069}' // isolates
+        )
+}
+
+/// Write the C-like escape of a byte that is not part of a valid UTF-8 sequence.
+fn write_byte_escape(target: &mut String, byte: u8) {
+    write!(target, "\\x{byte:02x}").expect("write to a string");
+}
+
+/// Write the C-like escape of an unsafe character.
+///
+/// Non-ASCII characters are written as `\u{XXXX}`, or as `\uXXXX` (as understood by `$'...'`
+/// of POSIX shells) if `shell` is `true`. All unsafe characters are in the Basic Multilingual Plane.
+fn write_escape(target: &mut String, char: char, shell: bool) {
+    let result = match char {
+        '\x07' => target.write_str("\\a"),
+        '\x08' => target.write_str("\\b"),
+        '\t' => target.write_str("\\t"),
+        '\n' => target.write_str("\\n"),
+        '\x0B' => target.write_str("\\v"),
+        '\x0C' => target.write_str("\\f"),
+        '\r' => target.write_str("\\r"),
+        '\x1B' => target.write_str("\\e"),
+        _ if char.is_ascii() => write!(target, "\\x{:02x}", char as u32),
+        _ if shell => write!(target, "\\u{:04X}", char as u32),
+        _ => write!(target, "\\u{{{:x}}}", char as u32),
+    };
+    result.expect("write to a string");
+}
+
+/// Whether a name can be used as a word in a POSIX shell without quotes.
+fn is_shell_word(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('~')
+        && name
+            .chars()
+            .all(|char| char.is_alphanumeric() || "%+,-./:=@_^".contains(char))
+}
+
+impl QuotingStyle {
+    /// Write `name` in this style.
+    pub fn quote(self, name: &str) -> Cow<'_, str> {
+        match self {
+            QuotingStyle::Literal => Cow::Borrowed(name),
+            QuotingStyle::Escape if !name.contains(|char| is_unsafe(char) || char == '\\') => {
+                Cow::Borrowed(name)
+            }
+            QuotingStyle::Shell if is_shell_word(name) => Cow::Borrowed(name),
+            _ => Cow::Owned(self.quote_pieces(name.len(), name.chars().map(Piece::Char))),
+        }
+    }
+
+    /// Write `name`, which may not be valid UTF-8, in this style.
+    ///
+    /// The bytes that are not valid UTF-8 are escaped, except in [`QuotingStyle::Literal`] where
+    /// they are replaced by `U+FFFD`.
+    pub fn quote_os_str(self, name: &OsStr) -> Cow<'_, str> {
+        if let Some(name) = name.to_str() {
+            return self.quote(name);
+        }
+        if self == QuotingStyle::Literal {
+            return name.to_string_lossy();
+        }
+        let bytes = name.as_encoded_bytes();
+        let pieces = bytes.utf8_chunks().flat_map(|chunk| {
+            let valid = chunk.valid().chars().map(Piece::Char);
+            let invalid = chunk.invalid().iter().copied().map(Piece::Byte);
+            valid.chain(invalid)
+        });
+        Cow::Owned(self.quote_pieces(bytes.len(), pieces))
+    }
+
+    /// Write the pieces of a name that needs to be quoted or escaped.
+    fn quote_pieces(self, len: usize, pieces: impl Iterator<Item = Piece>) -> String {
+        let mut result = String::with_capacity(len + 8);
+        match self {
+            QuotingStyle::Literal => {
+                for piece in pieces {
+                    match piece {
+                        Piece::Char(char) => result.push(char),
+                        Piece::Byte(_) => result.push(char::REPLACEMENT_CHARACTER),
+                    }
+                }
+            }
+            QuotingStyle::Escape => {
+                for piece in pieces {
+                    match piece {
+                        Piece::Char('\\') => result.push_str("\\\\"),
+                        Piece::Char(char) if is_unsafe(char) => {
+                            write_escape(&mut result, char, false)
+                        }
+                        Piece::Char(char) => result.push(char),
+                        Piece::Byte(byte) => write_byte_escape(&mut result, byte),
+                    }
+                }
+            }
+            QuotingStyle::Shell => {
+                if len == 0 {
+                    return "''".to_string();
+                }
+                let mut quoted = false; // whether a '...' segment is open
+                for piece in pieces {
+                    let close = |result: &mut String, quoted: &mut bool| {
+                        if *quoted {
+                            result.push('\'');
+                            *quoted = false;
+                        }
+                    };
+                    match piece {
+                        Piece::Char('\'') => {
+                            close(&mut result, &mut quoted);
+                            result.push_str("\\'");
+                        }
+                        Piece::Char(char) if is_unsafe(char) => {
+                            close(&mut result, &mut quoted);
+                            result.push_str("$'");
+                            write_escape(&mut result, char, true);
+                            result.push('\'');
+                        }
+                        Piece::Byte(byte) => {
+                            close(&mut result, &mut quoted);
+                            result.push_str("$'");
+                            write_byte_escape(&mut result, byte);
+                            result.push('\'');
+                        }
+                        Piece::Char(char) => {
+                            if !quoted {
+                                result.push('\'');
+                                quoted = true;
+                            }
+                            result.push(char);
+                        }
+                    }
+                }
+                if quoted {
+                    result.push('\'');
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Name of an entry that can be written in a [`QuotingStyle`].
+///
+/// A name that is not valid UTF-8 is quoted before it is converted to text, so that its invalid
+/// bytes are escaped rather than replaced.
+pub trait QuotableName {
+    /// Write the name in `style`.
+    fn quoted(&self, style: QuotingStyle) -> String;
+}
+
+impl QuotableName for str {
+    fn quoted(&self, style: QuotingStyle) -> String {
+        style.quote(self).into_owned()
+    }
+}
+
+impl QuotableName for String {
+    fn quoted(&self, style: QuotingStyle) -> String {
+        self.as_str().quoted(style)
+    }
+}
+
+impl<Name: QuotableName + ?Sized> QuotableName for &Name {
+    fn quoted(&self, style: QuotingStyle) -> String {
+        Name::quoted(self, style)
+    }
+}
+
+impl<Inner> QuotableName for OsStringDisplay<Inner>
+where
+    Inner: AsRef<OsStr> + Debug,
+{
+    fn quoted(&self, style: QuotingStyle) -> String {
+        style.quote_os_str(self.as_os_str()).into_owned()
+    }
+}
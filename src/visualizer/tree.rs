@@ -3,7 +3,7 @@ use derive_more::{AsRef, Deref, Display, Into};
 use fmt_iter::FmtIter;
 use pipe_trait::Pipe;
 use std::fmt::{Display, Error, Formatter};
-use zero_copy_pads::Width;
+use zero_copy_pads::{UnicodeWidthChar, Width};
 
 /// Determine 3 characters to use as skeletal component that connect a node
 /// to the rest of the tree.
@@ -89,6 +89,12 @@ impl<Name: Width> TreeHorizontalSlice<Name> {
             })
             .pipe(FmtIter::from)
     }
+
+    /// Display the indentation and the skeletal component without the name.
+    #[inline]
+    pub(super) fn skeleton(&self) -> String {
+        format!("{}{}", self.indent(), self.skeletal_component)
+    }
 }
 
 impl<Name: Width> Display for TreeHorizontalSlice<Name> {
@@ -128,7 +134,18 @@ impl TreeHorizontalSlice<String> {
             return Err(min_width);
         }
 
-        self.name.truncate(max_width - min_width);
+        // the name is truncated by width rather than by length to keep wide characters intact
+        let name_max_width = max_width - min_width;
+        let mut name_width = 0;
+        let end = self
+            .name
+            .char_indices()
+            .find(|(_, char)| {
+                name_width += char.width().unwrap_or(0);
+                name_width > name_max_width
+            })
+            .map_or(self.name.len(), |(index, _)| index);
+        self.name.truncate(end);
         self.name += "...";
         Ok(())
     }
@@ -9,7 +9,7 @@ use parallel_disk_usage::get_size::{self, GetSize};
 use parallel_disk_usage::hardlink::HardlinkIgnorant;
 use parallel_disk_usage::os_string_display::OsStringDisplay;
 use parallel_disk_usage::reporter::ErrorOnlyReporter;
-use parallel_disk_usage::size;
+use parallel_disk_usage::size::{self, Bytes};
 use pipe_trait::Pipe;
 use pretty_assertions::assert_eq;
 use rand::distr::Alphanumeric;
@@ -20,7 +20,7 @@ use std::env::temp_dir;
 use std::fs::{create_dir, metadata, remove_dir_all, symlink_metadata};
 use std::io::Error;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Output};
+use std::process::{Command, Output, Stdio};
 
 /// Default size getter method.
 #[cfg(unix)]
@@ -312,6 +312,64 @@ impl SampleWorkspace {
     }
 }
 
+/// Create a directory of a sample tree, whose own size is `inode_size`.
+pub fn sample_dir(
+    name: &str,
+    inode_size: u64,
+    children: Vec<DataTree<OsStringDisplay, Bytes>>,
+) -> DataTree<OsStringDisplay, Bytes> {
+    DataTree::dir(
+        OsStringDisplay::os_string_from(name),
+        inode_size.into(),
+        children,
+    )
+}
+
+/// Create a file of a sample tree.
+pub fn sample_file(name: &str, size: u64) -> DataTree<OsStringDisplay, Bytes> {
+    DataTree::file(OsStringDisplay::os_string_from(name), size.into())
+}
+
+/// Tree to be written to and read from the snapshots of the tests, sorted from the largest.
+pub fn sample_tree() -> DataTree<OsStringDisplay, Bytes> {
+    let dir = |name, children| sample_dir(name, 1024, children);
+    dir(
+        "root",
+        vec![
+            sample_file("foo", 2530),
+            sample_file("bar", 52),
+            dir(
+                "hello",
+                vec![dir(
+                    "world",
+                    vec![sample_file("hello", 45), sample_file("world", 54)],
+                )],
+            ),
+            dir("empty dir", vec![]),
+            dir(
+                "directory with a really long name",
+                vec![dir(
+                    "subdirectory with a really long name",
+                    vec![sample_file("file with a really long name", 475)],
+                )],
+            ),
+            dir(
+                "ディレクトリ",
+                vec![sample_file("ファイル", 475), sample_file("tệp tin", 325)],
+            ),
+        ],
+    )
+    .into_par_sorted(|left, right| left.size().cmp(&right.size()).reverse())
+}
+
+/// Convert a path written with `/` to the separators of the platform, like the paths that pdu prints.
+pub fn native_path(path: &str) -> String {
+    path.split('/')
+        .collect::<PathBuf>()
+        .to_string_lossy()
+        .into_owned()
+}
+
 /// Make the snapshot of a [`TreeReflection`] testable.
 ///
 /// The real filesystem is often messy, causing `children` to mess up its order.
@@ -327,6 +385,7 @@ where
     let DataTreeReflection {
         name,
         size,
+        metadata,
         children,
     } = tree_reflection;
     let children = children
@@ -337,6 +396,7 @@ where
     DataTreeReflection {
         name,
         size,
+        metadata,
         children,
     }
 }
@@ -383,58 +443,58 @@ where
 
     assert_eq!(
         measure("flat"),
-        sanitize_tree_reflection(DataTreeReflection {
-            name: sub("flat"),
-            size: suffix_size!("flat", "flat/0", "flat/1", "flat/2", "flat/3"),
-            children: vec![
-                DataTreeReflection {
-                    name: OsStringDisplay::os_string_from("0"),
-                    size: suffix_size("flat/0"),
-                    children: Vec::new(),
-                },
-                DataTreeReflection {
-                    name: OsStringDisplay::os_string_from("1"),
-                    size: suffix_size("flat/1"),
-                    children: Vec::new(),
-                },
-                DataTreeReflection {
-                    name: OsStringDisplay::os_string_from("2"),
-                    size: suffix_size("flat/2"),
-                    children: Vec::new(),
-                },
-                DataTreeReflection {
-                    name: OsStringDisplay::os_string_from("3"),
-                    size: suffix_size("flat/3"),
-                    children: Vec::new(),
-                },
+        sanitize_tree_reflection(DataTreeReflection::new(
+            sub("flat"),
+            suffix_size!("flat", "flat/0", "flat/1", "flat/2", "flat/3"),
+            vec![
+                DataTreeReflection::new(
+                    OsStringDisplay::os_string_from("0"),
+                    suffix_size("flat/0"),
+                    Vec::new()
+                ),
+                DataTreeReflection::new(
+                    OsStringDisplay::os_string_from("1"),
+                    suffix_size("flat/1"),
+                    Vec::new()
+                ),
+                DataTreeReflection::new(
+                    OsStringDisplay::os_string_from("2"),
+                    suffix_size("flat/2"),
+                    Vec::new()
+                ),
+                DataTreeReflection::new(
+                    OsStringDisplay::os_string_from("3"),
+                    suffix_size("flat/3"),
+                    Vec::new()
+                ),
             ]
-        }),
+        )),
     );
 
     assert_eq!(
         measure("nested"),
-        sanitize_tree_reflection(DataTreeReflection {
-            name: sub("nested"),
-            size: suffix_size!("nested", "nested/0", "nested/0/1"),
-            children: vec![DataTreeReflection {
-                name: OsStringDisplay::os_string_from("0"),
-                size: suffix_size!("nested/0", "nested/0/1"),
-                children: vec![DataTreeReflection {
-                    name: OsStringDisplay::os_string_from("1"),
-                    size: suffix_size!("nested/0/1"),
-                    children: Vec::new(),
-                }]
-            }],
-        }),
+        sanitize_tree_reflection(DataTreeReflection::new(
+            sub("nested"),
+            suffix_size!("nested", "nested/0", "nested/0/1"),
+            vec![DataTreeReflection::new(
+                OsStringDisplay::os_string_from("0"),
+                suffix_size!("nested/0", "nested/0/1"),
+                vec![DataTreeReflection::new(
+                    OsStringDisplay::os_string_from("1"),
+                    suffix_size!("nested/0/1"),
+                    Vec::new()
+                )]
+            )]
+        )),
     );
 
     assert_eq!(
         measure("empty-dir"),
-        sanitize_tree_reflection(DataTreeReflection {
-            name: sub("empty-dir"),
-            size: suffix_size!("empty-dir"),
-            children: Vec::new(),
-        }),
+        sanitize_tree_reflection(DataTreeReflection::new(
+            sub("empty-dir"),
+            suffix_size!("empty-dir"),
+            Vec::new()
+        )),
     );
 }
 
@@ -541,6 +601,43 @@ pub fn stdout_text(
         .to_string()
 }
 
+/// Create a command that runs `pdu` in `workspace` with `args`, without stdin,
+/// and captures its stdout and stderr.
+pub fn pdu_command(workspace: &Path, args: &[&str]) -> Command {
+    Command::new(PDU)
+        .with_current_dir(workspace)
+        .with_args(args)
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+}
+
+/// Run `pdu` in `workspace` with `args`.
+pub fn run_pdu(workspace: &Path, args: &[&str]) -> Output {
+    pdu_command(workspace, args)
+        .output()
+        .expect("spawn command")
+}
+
+/// Run `pdu` in `workspace` with `args` and return its stdout.
+pub fn pdu(workspace: &Path, args: &[&str]) -> String {
+    run_pdu(workspace, args).pipe(stdout_text)
+}
+
+/// Extract the names of the entries in a chart, from the top row to the bottom row.
+pub fn chart_names(chart: &str) -> Vec<&str> {
+    chart
+        .lines()
+        .map(|line| {
+            let tree = line.rsplitn(3, '│').nth(2).expect("get the tree column");
+            tree.trim_end()
+                .rsplit(['─', '┴', '┬'])
+                .next()
+                .expect("get the name")
+        })
+        .collect()
+}
+
 /// Print stderr if it's not empty.
 pub fn inspect_stderr(stderr: &[u8]) {
     let text = String::from_utf8_lossy(stderr);
@@ -0,0 +1,55 @@
+#![cfg(feature = "cli")]
+
+use parallel_disk_usage::args::byte_size::{ByteSize, FromStrError};
+use pretty_assertions::assert_eq;
+
+fn parse(text: &str) -> u64 {
+    text.parse::<ByteSize>().expect("parse byte size").get()
+}
+
+#[test]
+fn plain_number() {
+    assert_eq!(parse("0"), 0);
+    assert_eq!(parse("4096"), 4096);
+    assert_eq!(parse("4096B"), 4096);
+    assert_eq!(parse(" 4096 "), 4096);
+}
+
+#[test]
+fn metric_units() {
+    assert_eq!(parse("500M"), 500_000_000);
+    assert_eq!(parse("500MB"), 500_000_000);
+    assert_eq!(parse("1.5k"), 1_500);
+    assert_eq!(parse("2 GB"), 2_000_000_000);
+    assert_eq!(parse("1T"), 1_000_000_000_000);
+}
+
+#[test]
+fn binary_units() {
+    assert_eq!(parse("1KiB"), 1_024);
+    assert_eq!(parse("1.5GiB"), 1_610_612_736);
+    assert_eq!(parse("2mib"), 2_097_152);
+}
+
+#[test]
+fn unknown_unit() {
+    let actual_error = "5X".parse::<ByteSize>().expect_err("cause unit error");
+    let actual_message = actual_error.to_string();
+    let expected_error = FromStrError::UnknownUnit("X".to_string());
+    let expected_message = r#"Unknown unit: "X""#.to_string();
+    assert_eq!(
+        (actual_error, actual_message),
+        (expected_error, expected_message),
+    );
+}
+
+#[test]
+fn invalid_number() {
+    for text in ["", "M", "-5M", "1.2.3K"] {
+        let actual = text.parse::<ByteSize>();
+        assert!(
+            matches!(actual, Err(FromStrError::InvalidNumber(_))),
+            "unexpected result of {text:?}: {actual:?}",
+        );
+    }
+}
@@ -0,0 +1,183 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use command_extra::CommandExtra;
+use parallel_disk_usage::browser::{Browser, Order};
+use parallel_disk_usage::data_tree::DataTree;
+use parallel_disk_usage::hardlink::HardlinkListReflection;
+use parallel_disk_usage::os_string_display::OsStringDisplay;
+use parallel_disk_usage::size::Bytes;
+use pretty_assertions::assert_eq;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+type SampleTree = DataTree<OsStringDisplay, Bytes>;
+
+fn name(name: &'static str) -> OsStringDisplay {
+    OsStringDisplay::os_string_from(name)
+}
+
+/// Tree to browse in the tests.
+fn browsed_tree() -> SampleTree {
+    let dir = |name, children| sample_dir(name, 1000, children);
+    dir(
+        "root",
+        vec![
+            sample_file("foo", 6000),
+            dir(
+                "bar",
+                vec![sample_file("baz", 1500), sample_file("qux", 500)],
+            ),
+            sample_file("abc", 2000),
+        ],
+    )
+}
+
+/// Names of the entries in the current view.
+fn entry_names(browser: &Browser<Bytes>) -> Vec<String> {
+    browser
+        .entries()
+        .iter()
+        .map(|entry| entry.name().to_string())
+        .collect()
+}
+
+#[test]
+fn remove_descendant_updates_ancestors() {
+    let mut tree = browsed_tree();
+    let removed = tree
+        .remove_descendant(&[name("bar"), name("baz")])
+        .expect("remove bar/baz");
+    assert_eq!(removed.name(), &name("baz"));
+    assert_eq!(tree.size(), Bytes::new(10500));
+    let bar = tree
+        .children()
+        .iter()
+        .find(|child| child.name() == &name("bar"))
+        .expect("find bar");
+    assert_eq!(bar.size(), Bytes::new(1500));
+    assert_eq!(bar.children().len(), 1);
+
+    assert!(
+        tree.remove_descendant(&[name("bar"), name("baz")])
+            .is_none()
+    );
+    assert!(tree.remove_descendant(&[]).is_none());
+    assert_eq!(tree.size(), Bytes::new(10500));
+}
+
+#[test]
+fn navigate_sort_and_select() {
+    let mut browser = Browser::new(browsed_tree(), false);
+    assert_eq!(entry_names(&browser), ["foo", "bar", "abc"]);
+    assert_eq!(browser.selected_path(), Some(PathBuf::from("root/foo")));
+
+    browser.move_cursor(1);
+    assert!(browser.enter());
+    assert_eq!(browser.directory_path(), PathBuf::from("root/bar"));
+    assert_eq!(entry_names(&browser), ["baz", "qux"]);
+    assert!(!browser.enter(), "a file cannot be entered");
+
+    browser.move_cursor(10);
+    assert_eq!(browser.cursor(), 1);
+    browser.move_cursor(-10);
+    assert_eq!(browser.cursor(), 0);
+
+    assert!(browser.leave());
+    assert!(!browser.leave(), "the root has no parent");
+    assert_eq!(
+        browser.selected().map(|entry| entry.name()),
+        Some(&name("bar"))
+    );
+
+    browser.toggle_order();
+    assert_eq!(browser.order(), Order::Name);
+    assert_eq!(entry_names(&browser), ["abc", "bar", "foo"]);
+    assert_eq!(
+        browser.selected().map(|entry| entry.name()),
+        Some(&name("bar"))
+    );
+}
+
+#[test]
+fn remove_selected_updates_view() {
+    let mut browser = Browser::new(browsed_tree(), false);
+    browser.move_cursor(1);
+    browser.enter();
+    let removed = browser
+        .remove_selected()
+        .expect("remove the selected entry");
+    assert_eq!(removed.name(), &name("baz"));
+    assert_eq!(entry_names(&browser), ["qux"]);
+    assert_eq!(browser.directory().size(), Bytes::new(1500));
+    assert_eq!(browser.root().size(), Bytes::new(10500));
+}
+
+#[test]
+fn navigate_restores_location() {
+    let mut browser = Browser::new(browsed_tree(), false);
+    browser.navigate(&[name("bar")]);
+    assert_eq!(browser.location(), [name("bar")]);
+    browser.navigate(&[name("bar"), name("missing")]);
+    assert_eq!(browser.location(), [name("bar")]);
+    browser.navigate(&[name("foo")]);
+    assert_eq!(browser.location(), []);
+    assert_eq!(
+        browser.selected().map(|entry| entry.name()),
+        Some(&name("foo"))
+    );
+}
+
+#[test]
+fn synthetic_root_paths() {
+    let tree = SampleTree::dir(name("(total)"), 0.into(), vec![browsed_tree()]);
+    let mut browser = Browser::new(tree, true);
+    assert_eq!(browser.directory_path(), PathBuf::new());
+    assert_eq!(browser.selected_path(), Some(PathBuf::from("root")));
+    browser.enter();
+    assert_eq!(browser.selected_path(), Some(PathBuf::from("root/foo")));
+}
+
+#[test]
+fn selected_hardlinks() {
+    let hardlinks: HardlinkListReflection<Bytes> = serde_json::from_value(serde_json::json!([
+        {
+            "ino": 123,
+            "dev": 1,
+            "size": 1500,
+            "links": 2,
+            "paths": ["root/bar/baz", "root/foo"],
+        },
+    ]))
+    .expect("parse hardlinks");
+    let mut browser = Browser::new(browsed_tree(), false).with_hardlinks(Some(hardlinks));
+    assert_eq!(
+        browser.selected_hardlinks().map(|entry| entry.links),
+        Some(2),
+    );
+    browser.move_cursor(2);
+    assert_eq!(browser.selected_path(), Some(PathBuf::from("root/abc")));
+    assert!(browser.selected_hardlinks().is_none());
+}
+
+#[test]
+fn allow_delete_requires_live_scan() {
+    let output = Command::new(PDU)
+        .with_arg("--json-input")
+        .with_arg("--interactive")
+        .with_arg("--allow-delete")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command");
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.contains("cannot be used with"),
+        "unexpected stderr: {stderr}",
+    );
+}
@@ -1,4 +1,5 @@
-use parallel_disk_usage::bytes_format::BytesFormat;
+use parallel_disk_usage::bytes_format::formatter::{BINARY, METRIC};
+use parallel_disk_usage::bytes_format::{BytesFormat, FixedUnit, Formatter, SuffixStyle};
 use pretty_assertions::assert_eq;
 
 macro_rules! test_case {
@@ -41,3 +42,29 @@ test_case!(binary_of_1bil   ->               1_000_000_000 in BinaryUnits ==  "9
 test_case!(binary_of_1trill ->           1_000_000_000_000 in BinaryUnits ==  "931.3G");
 test_case!(binary_of_1quard ->       1_000_000_000_000_000 in BinaryUnits ==  "909.5T");
 test_case!(binary_of_1quint ->   1_000_000_000_000_000_000 in BinaryUnits ==  "888.2P");
+
+macro_rules! test_formatter {
+    ($name:ident -> $value:literal in $formatter:expr => $expected:literal) => {
+        #[test]
+        fn $name() {
+            let format = BytesFormat::Custom($formatter);
+            assert_eq!(format.format($value).to_string(), $expected);
+        }
+    };
+}
+
+test_formatter!(fixed_kb_of_0        ->           0 in Formatter::fixed(FixedUnit::Kilo) =>    "0.0K");
+test_formatter!(fixed_kb_of_750      ->         750 in Formatter::fixed(FixedUnit::Kilo) =>    "0.8K");
+test_formatter!(fixed_kb_of_2mil     ->   2_000_000 in Formatter::fixed(FixedUnit::Kilo) => "2000.0K");
+test_formatter!(fixed_mib_of_1mil    ->   1_000_000 in Formatter::fixed(FixedUnit::Mebi) =>    "1.0M");
+test_formatter!(fixed_gib_of_1mil    ->   1_000_000 in Formatter::fixed(FixedUnit::Gibi).with_precision(4) => "0.0009G");
+test_formatter!(fixed_tb_of_1bil     -> 1_000_000_000 in Formatter::fixed(FixedUnit::Tera).with_precision(3) => "0.001T");
+test_formatter!(precision_0_of_1500  ->       1_500 in METRIC.with_precision(0) =>   "2K");
+test_formatter!(precision_0_of_750   ->         750 in METRIC.with_precision(0) => "750 ");
+test_formatter!(precision_3_of_1500  ->       1_500 in METRIC.with_precision(3) =>   "1.500K");
+test_formatter!(precision_3_of_750   ->         750 in METRIC.with_precision(3) => "750     ");
+test_formatter!(long_metric_of_1500  ->       1_500 in METRIC.with_suffix_style(SuffixStyle::Long) => "1.5KB");
+test_formatter!(long_metric_of_750   ->         750 in METRIC.with_suffix_style(SuffixStyle::Long) => "750B   ");
+test_formatter!(long_binary_of_1500  ->       1_500 in BINARY.with_suffix_style(SuffixStyle::Long) => "1.5KiB");
+test_formatter!(long_binary_of_750   ->         750 in BINARY.with_suffix_style(SuffixStyle::Long) => "750B    ");
+test_formatter!(long_fixed_gib_of_2g -> 2_147_483_648 in Formatter::fixed(FixedUnit::Gibi).with_suffix_style(SuffixStyle::Long) => "2.0GiB");
@@ -0,0 +1,183 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+const RULES: &str = r#"
+[[category]]
+name = "build artifacts"
+globs = ["**/target", "**/*.o"]
+
+[[category]]
+name = "logs"
+globs = ["**/*.log"]
+"#;
+
+/// Create a project with build artifacts and logs, and a rules file next to it.
+fn create_workspace() -> Temp {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let root = workspace.join("root");
+    std::fs::create_dir_all(root.join("target/debug")).expect("create directories");
+    std::fs::create_dir_all(root.join("src")).expect("create directories");
+    for (name, size) in [
+        ("target/debug/app", 5000),
+        ("target/debug/app.d", 1000),
+        ("target/release.log", 500),
+        ("src/main.rs", 300),
+        ("src/main.o", 700),
+        ("server.log", 2000),
+        ("src/debug.log", 1500),
+    ] {
+        std::fs::write(root.join(name), "a".repeat(size)).expect("write file");
+    }
+    std::fs::write(workspace.join("rules.toml"), RULES).expect("write rules");
+    workspace
+}
+
+/// Find the child of `node` named `name`.
+fn child<'a>(node: &'a Value, name: &str) -> &'a Value {
+    node["children"]
+        .as_array()
+        .expect("get the children")
+        .iter()
+        .find(|child| child["name"] == name)
+        .unwrap_or_else(|| panic!("find {name:?}"))
+}
+
+/// Names and sizes of the files among the children of `node`.
+fn files(node: &Value) -> Vec<(String, u64)> {
+    node["children"]
+        .as_array()
+        .expect("get the children")
+        .iter()
+        .map(|child| {
+            let name = child["name"].as_str().expect("get the name").to_string();
+            let size = child["size"].as_u64().expect("get the size");
+            (name, size)
+        })
+        .collect()
+}
+
+#[test]
+fn json_output() {
+    let workspace = create_workspace();
+    let json = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--scan-header",
+            "--min-ratio=0",
+            "--category-rules=rules.toml",
+            "--json-output",
+            "root",
+        ],
+    );
+    let json: Value = serde_json::from_str(&json).expect("parse JSON");
+    let tree = &json["tree"];
+
+    // the entries count toward the outermost path that matched
+    let build_artifacts = child(tree, "build artifacts");
+    let dir_size = |name: &str| {
+        std::fs::metadata(workspace.join("root").join(name))
+            .expect("read metadata")
+            .len()
+    };
+    assert_eq!(
+        files(build_artifacts),
+        [
+            (
+                native_path("root/target"),
+                dir_size("target") + dir_size("target/debug") + 5000 + 1000 + 500,
+            ),
+            (native_path("root/src/main.o"), 700),
+        ],
+    );
+
+    let logs = child(tree, "logs");
+    assert_eq!(logs["size"], 2000 + 1500);
+    assert_eq!(
+        files(logs),
+        [
+            (native_path("root/server.log"), 2000),
+            (native_path("root/src/debug.log"), 1500),
+        ],
+    );
+
+    let uncategorized = child(tree, "(uncategorized)");
+    assert!(files(uncategorized).contains(&(native_path("root/src"), dir_size("src") + 300)));
+
+    assert_eq!(json["header"]["settings"]["category-rules"], "rules.toml");
+}
+
+#[test]
+fn chart() {
+    let workspace = create_workspace();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--max-children=1",
+            "--category-rules=rules.toml",
+            "root",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    let lines: Vec<_> = actual.lines().collect();
+    // --max-children limits the paths of each category, not the categories
+    assert_eq!(lines.len(), 7);
+    assert!(lines[4].contains(&native_path("root/target")));
+    assert!(lines[5].contains("build artifacts"));
+    assert!(lines[1].contains("logs"));
+    assert!(lines[3].contains("(uncategorized)"));
+}
+
+#[test]
+fn invalid_rules() {
+    let workspace = create_workspace();
+    std::fs::write(workspace.join("invalid.toml"), "[[category]]\nname = 1\n")
+        .expect("write rules");
+    let output = run_pdu(&workspace, &["--category-rules=invalid.toml", "root"]);
+    assert_eq!(output.status.code(), Some(18));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.starts_with(r#"[error] InvalidCategoryRules: "invalid.toml": "#),
+        "unexpected {stderr:?}",
+    );
+}
+
+#[test]
+fn many_contributors() {
+    let workspace = create_workspace();
+    let logs = workspace.join("root/logs");
+    std::fs::create_dir(&logs).expect("create directory");
+    for index in 1..=100 {
+        std::fs::write(logs.join(format!("{index}.log")), "a".repeat(index)).expect("write file");
+    }
+    let json = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--min-ratio=0",
+            "--max-children=2",
+            "--category-rules=rules.toml",
+            "--json-output",
+            "root",
+        ],
+    );
+    let json: Value = serde_json::from_str(&json).expect("parse JSON");
+    let logs = child(&json["tree"], "logs");
+    assert_eq!(logs["size"], 2000 + 1500 + (1..=100).sum::<u64>());
+    assert_eq!(
+        files(logs),
+        [
+            (native_path("root/server.log"), 2000),
+            (native_path("root/src/debug.log"), 1500),
+        ],
+    );
+}
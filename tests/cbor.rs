@@ -0,0 +1,306 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use command_extra::CommandExtra;
+use parallel_disk_usage::bytes_format::BytesFormat;
+use parallel_disk_usage::data_tree::DataTree;
+use parallel_disk_usage::device::DeviceBoundary;
+use parallel_disk_usage::fs_tree_builder::FsTreeBuilder;
+use parallel_disk_usage::get_size::GetApparentSize;
+use parallel_disk_usage::hardlink::HardlinkIgnorant;
+use parallel_disk_usage::json_data::{BinaryVersion, JsonData, JsonTree, SchemaVersion};
+use parallel_disk_usage::reporter::{ErrorOnlyReporter, ErrorReport};
+use parallel_disk_usage::size::Bytes;
+use parallel_disk_usage::visualizer::{
+    BarAlignment, ColumnWidthDistribution, Direction, Visualizer,
+};
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use std::convert::TryInto;
+use std::fs;
+use std::process::{Command, Output, Stdio};
+
+type SampleSize = Bytes;
+type SampleJsonTree = JsonTree<SampleSize>;
+
+fn sample_json_data() -> JsonData {
+    JsonData {
+        schema_version: SchemaVersion,
+        binary_version: Some(BinaryVersion::current()),
+        header: None,
+        body: JsonTree {
+            tree: sample_tree().into_reflection(),
+            shared: Default::default(),
+        }
+        .into(),
+    }
+}
+
+/// Extract the binary stdout of a successful command.
+fn stdout_bytes(output: Output) -> Vec<u8> {
+    let Output {
+        status,
+        stdout,
+        stderr,
+    } = output;
+    inspect_stderr(&stderr);
+    assert!(
+        status.success(),
+        "progress exits with non-zero status: {status:?}",
+    );
+    stdout
+}
+
+/// Decode a CBOR document into [`JsonData`] and extract the tree.
+fn decode_cbor_tree(cbor: &[u8]) -> SampleJsonTree {
+    ciborium::from_reader::<JsonData, _>(cbor)
+        .expect("parse CBOR as JsonData")
+        .body
+        .pipe(TryInto::<SampleJsonTree>::try_into)
+        .expect("extract reflection")
+}
+
+#[test]
+fn cbor_round_trip() {
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&sample_json_data(), &mut cbor).expect("encode JsonData as CBOR");
+    let actual = decode_cbor_tree(&cbor).tree;
+    let expected = sample_tree().into_reflection();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn cbor_output() {
+    let workspace = SampleWorkspace::default();
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--output-format=cbor")
+        .with_arg("--quantity=apparent-size")
+        .with_arg("--min-ratio=0")
+        .with_arg(&workspace)
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_bytes)
+        .pipe_as_ref(decode_cbor_tree)
+        .tree
+        .pipe(sanitize_tree_reflection);
+    dbg!(&actual);
+    let builder = FsTreeBuilder {
+        root: workspace.to_path_buf(),
+        size_getter: GetApparentSize,
+        hardlinks_recorder: &HardlinkIgnorant,
+        reporter: &ErrorOnlyReporter::new(ErrorReport::SILENT),
+        device_boundary: DeviceBoundary::Cross,
+        max_depth: 10,
+    };
+    let expected = builder
+        .pipe(DataTree::<_, Bytes>::from)
+        .into_reflection()
+        .pipe(sanitize_tree_reflection);
+    dbg!(&expected);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn cbor_output_zstd() {
+    let workspace = SampleWorkspace::default();
+    let output = |zstd: bool| {
+        Command::new(PDU)
+            .with_current_dir(&workspace)
+            .with_arg("--output-format=cbor")
+            .with_arg("--quantity=apparent-size")
+            .with_arg("--min-ratio=0")
+            .pipe(|command| {
+                if zstd {
+                    command.with_arg("--zstd")
+                } else {
+                    command
+                }
+            })
+            .with_arg(&workspace)
+            .with_stdin(Stdio::null())
+            .with_stdout(Stdio::piped())
+            .with_stderr(Stdio::piped())
+            .output()
+            .expect("spawn command")
+            .pipe(stdout_bytes)
+    };
+    let compressed = output(true);
+    let decompressed =
+        zstd::decode_all(compressed.as_slice()).expect("decompress the output with zstd");
+    let actual = decode_cbor_tree(&decompressed)
+        .tree
+        .pipe(sanitize_tree_reflection);
+    let expected = output(false)
+        .pipe_as_ref(decode_cbor_tree)
+        .tree
+        .pipe(sanitize_tree_reflection);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn cbor_input() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&sample_json_data(), &mut cbor).expect("encode JsonData as CBOR");
+    fs::write(workspace.join("sample.cbor"), cbor).expect("write CBOR file");
+
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--input-format=cbor")
+        .with_arg("--bytes-format=metric")
+        .with_arg("--total-width=100")
+        .with_arg("--max-depth=10")
+        .with_arg("--min-ratio=0")
+        .with_arg("sample.cbor")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text);
+    let actual = actual.trim_end();
+    eprintln!("ACTUAL:\n{actual}\n");
+
+    let visualizer = Visualizer {
+        data_tree: &sample_tree(),
+        bytes_format: BytesFormat::MetricUnits,
+        direction: Direction::BottomUp,
+        bar_alignment: BarAlignment::Left,
+        column_width_distribution: ColumnWidthDistribution::total(100),
+    };
+    let expected = format!("{visualizer}");
+    let expected = expected.trim_end();
+    eprintln!("EXPECTED:\n{expected}\n");
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn cbor_output_cbor_input() {
+    let workspace = SampleWorkspace::default();
+
+    let cbor = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--output-format=cbor")
+        .with_arg("--zstd")
+        .with_arg("--quantity=apparent-size")
+        .with_arg(&workspace)
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command with --output-format=cbor")
+        .pipe(stdout_bytes);
+    let snapshot = Temp::new_dir().expect("create temporary directory");
+    fs::write(snapshot.join("snapshot.cbor.zst"), cbor).expect("write CBOR file");
+
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--input-format=cbor")
+        .with_arg("--bytes-format=metric")
+        .with_arg("--total-width=100")
+        .with_arg("--max-depth=10")
+        .with_arg(snapshot.join("snapshot.cbor.zst"))
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command with --input-format=cbor")
+        .pipe(stdout_text);
+    eprintln!("ACTUAL:\n{actual}\n");
+
+    let expected = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--bytes-format=metric")
+        .with_arg("--total-width=100")
+        .with_arg("--max-depth=10")
+        .with_arg("--quantity=apparent-size")
+        .with_arg(&workspace)
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command for expected")
+        .pipe(stdout_text);
+    eprintln!("EXPECTED:\n{expected}\n");
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn json_input_cbor_output() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let json = serde_json::to_string(&sample_json_data()).expect("convert sample tree to JSON");
+    fs::write(workspace.join("sample.json"), json).expect("write JSON file");
+
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-input")
+        .with_arg("--output-format=cbor")
+        .with_arg("sample.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_bytes)
+        .pipe_as_ref(decode_cbor_tree)
+        .tree;
+    let expected = sample_tree().into_reflection();
+    assert_eq!(actual, expected);
+}
+
+#[cfg(unix)]
+#[test]
+fn cbor_round_trip_non_utf8_names() {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let name = OsString::from_vec(vec![b'a', 0xFF, b'b']);
+    fs::create_dir(workspace.join("root")).expect("create root directory");
+    fs::write(workspace.join("root").join(&name), "content").expect("write file");
+
+    let scan = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--output-format=cbor")
+        .with_arg("--quantity=apparent-size")
+        .with_arg("--min-ratio=0")
+        .with_arg("root")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command with --output-format=cbor")
+        .pipe(stdout_bytes);
+    fs::write(workspace.join("root.cbor"), &scan).expect("write CBOR file");
+    let scanned = decode_cbor_tree(&scan).tree;
+    let child_names: Vec<_> = scanned
+        .children
+        .iter()
+        .map(|child| child.name.as_os_str())
+        .collect();
+    assert_eq!(child_names, [name.as_os_str()]);
+
+    let reencoded = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--input-format=cbor")
+        .with_arg("--output-format=cbor")
+        .with_arg("root.cbor")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command with --input-format=cbor")
+        .pipe(stdout_bytes)
+        .pipe_as_ref(decode_cbor_tree)
+        .tree;
+    assert_eq!(reencoded, scanned);
+}
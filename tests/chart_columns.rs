@@ -0,0 +1,87 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use pretty_assertions::assert_eq;
+use std::fs::File;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Find the line of the chart whose tree column ends with `name`.
+fn line<'a>(chart: &'a str, name: &str) -> &'a str {
+    chart
+        .lines()
+        .find(|line| {
+            ["─", "┴", "┬"].iter().any(|joint| {
+                line.split(&format!("{joint}{name}"))
+                    .nth(1)
+                    .is_some_and(|rest| rest.starts_with([' ', '│']))
+            })
+        })
+        .unwrap_or_else(|| panic!("find the line of {name:?} in:\n{chart}"))
+}
+
+#[test]
+fn default_columns() {
+    let workspace = SampleWorkspace::default();
+    let args = ["--quantity=apparent-size", "--total-width=100"];
+    let implicit = pdu(&workspace, &args);
+    let explicit = pdu(
+        &workspace,
+        &[&args[..], &["--columns=size,percent"]].concat(),
+    );
+    assert_eq!(implicit, explicit);
+}
+
+#[test]
+fn percentage_columns() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=80",
+            "--min-ratio=0",
+            "--columns=percent-of-parent,percent",
+            "flat",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    for line in actual.lines() {
+        assert_eq!(line.chars().count(), 80, "unexpected width of {line:?}");
+    }
+    assert!(line(&actual, "3").ends_with("│ 50%  50%"));
+    assert!(line(&actual, "1").ends_with("│ 17%  17%"));
+    assert!(line(&actual, "flat").ends_with("│100% 100%"));
+    assert!(line(&actual, "flat").starts_with("┌─┴flat"));
+}
+
+#[test]
+fn count_and_mtime_columns() {
+    let workspace = SampleWorkspace::default();
+    let mtime = UNIX_EPOCH + Duration::from_secs(1709214300); // 2024-02-29 13:45:00 UTC
+    for name in ["0", "1", "2", "3"] {
+        File::options()
+            .write(true)
+            .open(workspace.join("flat").join(name))
+            .expect("open file")
+            .set_modified(mtime)
+            .expect("set modification time");
+    }
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--columns=count,mtime,size",
+            "flat",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert!(line(&actual, "3").starts_with("1 2024-02-29 13:45 300.0K "));
+    assert!(line(&actual, "0").starts_with("1 2024-02-29 13:45 "));
+    assert!(line(&actual, "flat").starts_with("4 "));
+    assert!(line(&actual, "flat").ends_with('│'));
+}
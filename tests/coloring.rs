@@ -0,0 +1,111 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use command_extra::CommandExtra;
+use parallel_disk_usage::node_metadata::EntryType;
+use parallel_disk_usage::visualizer::LsColors;
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+
+/// Run `pdu` in `workspace` with `args` and `LS_COLORS`, and return its stdout.
+fn pdu_with_ls_colors(workspace: &Path, args: &[&str]) -> String {
+    pdu_command(workspace, args)
+        .with_env("LS_COLORS", "di=01;34:*.log=33:*.LOG=35")
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+}
+
+/// Remove the ANSI escape sequences of SGR parameters.
+fn strip_ansi(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("\x1b[") {
+        result.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('m')
+            .expect("find the end of the escape sequence");
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[test]
+fn ls_colors_style() {
+    let ls_colors = LsColors::parse("di=01;34:ln=target:fi=0:*.log=33:*.LOG=35:*.tar.gz=31");
+    let style = |name, entry_type| ls_colors.style(name, entry_type);
+    assert_eq!(style("src", Some(EntryType::Dir)), Some("01;34"));
+    assert_eq!(style("app.log", Some(EntryType::File)), Some("35"));
+    assert_eq!(style("backup.TAR.GZ", None), Some("31"));
+    assert_eq!(style("README", Some(EntryType::File)), Some("0"));
+    assert_eq!(style("app.log", Some(EntryType::Symlink)), None);
+    assert_eq!(style("socket", Some(EntryType::Other)), None);
+}
+
+#[test]
+fn colors_do_not_break_alignment() {
+    let workspace = SampleWorkspace::default();
+    for bar_coloring in ["--bar-coloring=depth", "--bar-coloring=gradient"] {
+        let args = [
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            bar_coloring,
+        ];
+        let colored = pdu_with_ls_colors(&workspace, &[&args[..], &["--color=always"]].concat());
+        let monochrome = pdu_with_ls_colors(&workspace, &[&args[..], &["--color=never"]].concat());
+        eprintln!("COLORED:\n{colored}\n");
+        assert_ne!(colored, monochrome);
+        assert_eq!(strip_ansi(&colored), monochrome);
+    }
+}
+
+#[test]
+fn auto_color_without_terminal() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu_with_ls_colors(
+        &workspace,
+        &["--quantity=apparent-size", "--total-width=100"],
+    );
+    assert!(!actual.contains('\x1b'), "unexpected escapes in {actual:?}");
+}
+
+#[test]
+fn dim_synthetic_root_and_highlight_largest_child() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu_with_ls_colors(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--max-depth=3",
+            "--color=always",
+            "flat",
+            "nested",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    for (name, style) in [
+        ("(total)", "01;34;2"), // synthetic
+        ("flat", "01;34;1"),    // largest
+        ("nested", "01;34"),
+        ("0", "01;34;2"), // contents are not shown
+        ("3", "1"),       // largest
+        ("1", ""),
+    ] {
+        let expected = if style.is_empty() {
+            format!("─{name} ")
+        } else {
+            format!("\x1b[{style}m{name}\x1b[0m")
+        };
+        assert!(
+            actual.contains(&expected),
+            "expecting {expected:?} in the output",
+        );
+    }
+}
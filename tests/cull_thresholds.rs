@@ -0,0 +1,160 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use pretty_assertions::assert_eq;
+
+#[test]
+fn min_size() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--min-size=150K",
+            "flat",
+            "nested",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(
+        chart_names(&actual),
+        ["1", "0", "nested", "2", "3", "flat", "(total)"],
+    );
+}
+
+#[test]
+fn min_ratio_of_parent() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--min-ratio-of-parent=0.2",
+            "flat",
+            "nested",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(
+        chart_names(&actual),
+        ["1", "0", "nested", "2", "3", "flat", "(total)"],
+    );
+}
+
+#[test]
+fn combined_with_min_ratio() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0.2",
+            "--min-ratio-of-parent=0.4",
+            "--min-size=1",
+            "flat",
+            "nested",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(
+        chart_names(&actual),
+        ["1", "0", "nested", "3", "flat", "(total)"]
+    );
+}
+
+#[test]
+fn json_input() {
+    let workspace = SampleWorkspace::default();
+    let json = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--min-ratio=0",
+            "--json-output",
+            "flat",
+        ],
+    );
+    let json_file = workspace.join("flat.json");
+    std::fs::write(&json_file, json).expect("write JSON file");
+    let actual = pdu(
+        &workspace,
+        &[
+            "--json-input",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--min-size=250000",
+            "flat.json",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(chart_names(&actual), ["3", "flat"]);
+}
+
+#[test]
+fn aggregate_culled() {
+    let workspace = SampleWorkspace::default();
+    let args = [
+        "--quantity=apparent-size",
+        "--total-width=100",
+        "--min-ratio=0",
+        "--min-size=150K",
+        "--aggregate-culled",
+        "flat",
+    ];
+    let actual = pdu(&workspace, &args);
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(
+        chart_names(&actual),
+        ["… (2 smaller items)", "2", "3", "flat"]
+    );
+    let aggregate = actual
+        .lines()
+        .find(|line| line.contains("(2 smaller items)"))
+        .expect("find the aggregate");
+    assert!(aggregate.starts_with("100.0K "), "unexpected {aggregate:?}");
+
+    let json = pdu(&workspace, &[&args[..], &["--json-output"]].concat());
+    let json: serde_json::Value = serde_json::from_str(&json).expect("parse JSON");
+    let aggregate = json["tree"]["children"]
+        .as_array()
+        .expect("get the children")
+        .iter()
+        .find(|child| child["name"] == "(2 smaller items)")
+        .expect("find the aggregate");
+    assert_eq!(aggregate["metadata"]["aggregated-entries"], 2);
+    assert_eq!(aggregate["size"], 100_000);
+
+    let colored = pdu(&workspace, &[&args[..], &["--color=always"]].concat());
+    assert!(colored.contains("\x1b[2m… (2 smaller items)\x1b[0m"));
+}
+
+#[test]
+fn aggregate_many_culled() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let root = workspace.join("root");
+    std::fs::create_dir(&root).expect("create root");
+    std::fs::write(root.join("big"), "a".repeat(4096)).expect("write file");
+    for index in 0..1234 {
+        std::fs::write(root.join(format!("empty-{index}")), "").expect("write file");
+    }
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-size=1",
+            "--aggregate-culled",
+            "root",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert!(actual.contains("… (1,234 smaller items)"));
+}
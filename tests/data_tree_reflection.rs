@@ -10,96 +10,60 @@ type SampleReflection = Reflection<SampleName, SampleData>;
 type SampleTree = DataTree<SampleName, SampleData>;
 
 fn valid_reflection() -> SampleReflection {
-    Reflection {
-        name: "root",
-        size: Bytes::new(7853),
-        children: vec![
-            Reflection {
-                name: "a",
-                size: Bytes::new(78),
-                children: Vec::new(),
-            },
-            Reflection {
-                name: "b",
-                size: Bytes::new(321),
-                children: vec![Reflection {
-                    name: "0",
-                    size: Bytes::new(321),
-                    children: Vec::new(),
-                }],
-            },
-            Reflection {
-                name: "c",
-                size: Bytes::new(3456),
-                children: vec![
-                    Reflection {
-                        name: "0",
-                        size: Bytes::new(732),
-                        children: Vec::new(),
-                    },
-                    Reflection {
-                        name: "1",
-                        size: Bytes::new(352),
-                        children: Vec::new(),
-                    },
+    Reflection::new(
+        "root",
+        Bytes::new(7853),
+        vec![
+            Reflection::new("a", Bytes::new(78), Vec::new()),
+            Reflection::new(
+                "b",
+                Bytes::new(321),
+                vec![Reflection::new("0", Bytes::new(321), Vec::new())],
+            ),
+            Reflection::new(
+                "c",
+                Bytes::new(3456),
+                vec![
+                    Reflection::new("0", Bytes::new(732), Vec::new()),
+                    Reflection::new("1", Bytes::new(352), Vec::new()),
                 ],
-            },
+            ),
         ],
-    }
+    )
 }
 
 fn invalid_reflection_excessive_children() -> SampleReflection {
-    Reflection {
-        name: "root",
-        size: Bytes::new(2468),
-        children: vec![
-            Reflection {
-                name: "a",
-                size: Bytes::new(78),
-                children: Vec::new(),
-            },
-            Reflection {
-                name: "b",
-                size: Bytes::new(321),
-                children: vec![Reflection {
-                    name: "0",
-                    size: Bytes::new(321),
-                    children: vec![
-                        Reflection {
-                            name: "abc",
-                            size: Bytes::new(123),
-                            children: vec![Reflection {
-                                name: "xyz",
-                                size: Bytes::new(4321),
-                                children: Vec::new(),
-                            }],
-                        },
-                        Reflection {
-                            name: "def",
-                            size: Bytes::new(456),
-                            children: Vec::new(),
-                        },
+    Reflection::new(
+        "root",
+        Bytes::new(2468),
+        vec![
+            Reflection::new("a", Bytes::new(78), Vec::new()),
+            Reflection::new(
+                "b",
+                Bytes::new(321),
+                vec![Reflection::new(
+                    "0",
+                    Bytes::new(321),
+                    vec![
+                        Reflection::new(
+                            "abc",
+                            Bytes::new(123),
+                            vec![Reflection::new("xyz", Bytes::new(4321), Vec::new())],
+                        ),
+                        Reflection::new("def", Bytes::new(456), Vec::new()),
                     ],
-                }],
-            },
-            Reflection {
-                name: "c",
-                size: Bytes::new(1084),
-                children: vec![
-                    Reflection {
-                        name: "0",
-                        size: Bytes::new(732),
-                        children: Vec::new(),
-                    },
-                    Reflection {
-                        name: "1",
-                        size: Bytes::new(352),
-                        children: Vec::new(),
-                    },
+                )],
+            ),
+            Reflection::new(
+                "c",
+                Bytes::new(1084),
+                vec![
+                    Reflection::new("0", Bytes::new(732), Vec::new()),
+                    Reflection::new("1", Bytes::new(352), Vec::new()),
                 ],
-            },
+            ),
         ],
-    }
+    )
 }
 
 #[test]
@@ -120,11 +84,7 @@ fn invalid_conversion_excessive_children() {
     let expected = ConversionError::ExcessiveChildren {
         path: vec!["root", "b", "0"].into_iter().collect(),
         size: Bytes::new(321),
-        child: Reflection {
-            name: "def",
-            size: Bytes::new(456),
-            children: Vec::new(),
-        },
+        child: Reflection::new("def", Bytes::new(456), Vec::new()),
     };
     assert_eq!(actual, expected);
 }
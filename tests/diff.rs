@@ -0,0 +1,463 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use command_extra::CommandExtra;
+use parallel_disk_usage::bytes_format::BytesFormat;
+use parallel_disk_usage::data_tree::DataTree;
+use parallel_disk_usage::device::DeviceBoundary;
+use parallel_disk_usage::diff::{Delta, DiffStatus, DiffTree};
+use parallel_disk_usage::json_data::schema_version::SCHEMA_VERSION;
+use parallel_disk_usage::json_data::{
+    JsonData, JsonDataBody, JsonTree, ScanHeader, ScanQuantity, ScanRoot, ScanSettings,
+    SchemaVersion,
+};
+use parallel_disk_usage::os_string_display::OsStringDisplay;
+use parallel_disk_usage::size::{self, Blocks, Bytes};
+use parallel_disk_usage::visualizer::{
+    BarAlignment, ColumnWidthDistribution, Direction, Visualizer,
+};
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+type SampleName = OsStringDisplay;
+type SampleTree = DataTree<SampleName, Bytes>;
+
+fn dir(name: &'static str, children: Vec<SampleTree>) -> SampleTree {
+    SampleTree::dir(OsStringDisplay::os_string_from(name), 1000.into(), children)
+}
+
+fn file(name: &'static str, size: u64) -> SampleTree {
+    SampleTree::file(OsStringDisplay::os_string_from(name), Bytes::from(size))
+}
+
+fn old_tree() -> SampleTree {
+    dir(
+        "root",
+        vec![
+            file("unchanged", 3000),
+            file("grown", 1000),
+            file("removed", 500),
+            dir("shrunk", vec![file("inner", 2000)]),
+        ],
+    )
+}
+
+fn new_tree() -> SampleTree {
+    dir(
+        "root",
+        vec![
+            file("unchanged", 3000),
+            file("grown", 5000),
+            file("added", 700),
+            dir("shrunk", vec![file("inner", 200)]),
+        ],
+    )
+}
+
+/// Write a tree into a JSON snapshot file.
+fn write_snapshot<Size>(path: &Path, tree: DataTree<SampleName, Size>)
+where
+    Size: size::Size,
+    JsonTree<Size>: Into<JsonDataBody>,
+{
+    write_snapshot_with_header(path, tree, None)
+}
+
+/// Serialize a tree with a header into a JSON snapshot file.
+fn write_snapshot_with_header<Size>(
+    path: &Path,
+    tree: DataTree<SampleName, Size>,
+    header: Option<ScanHeader>,
+) where
+    Size: size::Size,
+    JsonTree<Size>: Into<JsonDataBody>,
+{
+    let json_tree = JsonTree {
+        tree: tree.into_reflection(),
+        shared: Default::default(),
+    };
+    let json_data = JsonData {
+        schema_version: SchemaVersion,
+        binary_version: None,
+        header,
+        body: json_tree.into(),
+    };
+    let json = serde_json::to_string_pretty(&json_data).expect("convert sample tree to JSON");
+    fs::write(path, json).expect("write JSON snapshot");
+}
+
+/// Create a header of a scan that measured `quantity`.
+fn sample_header(hostname: &str, quantity: ScanQuantity) -> ScanHeader {
+    ScanHeader {
+        started_at: 1_700_000_000,
+        finished_at: 1_700_000_060,
+        hostname: Some(hostname.to_string()),
+        roots: vec![ScanRoot {
+            path: OsStringDisplay::os_string_from("/data"),
+            filesystem: Some("ext4".to_string()),
+            device: None,
+        }],
+        settings: ScanSettings {
+            quantity,
+            threads: 4,
+            device_boundary: DeviceBoundary::Cross,
+            deduplicate_hardlinks: false,
+            max_depth: Some(10),
+            min_ratio: 0.01,
+            min_ratio_of_parent: None,
+            min_size: None,
+            max_children: None,
+            max_rows: None,
+            group_by: None,
+            category_rules: None,
+            largest_files: None,
+        },
+    }
+}
+
+/// Create a workspace that contains `old.json` and `new.json`.
+fn snapshot_workspace() -> Temp {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    write_snapshot(&workspace.join("old.json"), old_tree());
+    write_snapshot(&workspace.join("new.json"), new_tree());
+    workspace
+}
+
+#[test]
+fn par_compare() {
+    let diff = DiffTree::par_compare(old_tree(), new_tree());
+    let find = |name: &str| {
+        diff.children()
+            .iter()
+            .find(|child| child.name().as_os_str() == name)
+            .unwrap_or_else(|| panic!("find child named {name:?}"))
+    };
+
+    assert_eq!(diff.status(), DiffStatus::Retained);
+    assert_eq!(diff.delta(), Delta::Increase(Bytes::new(2400)));
+
+    let unchanged = find("unchanged");
+    assert_eq!(unchanged.status(), DiffStatus::Retained);
+    assert_eq!(unchanged.delta(), Delta::Increase(Bytes::new(0)));
+
+    let grown = find("grown");
+    assert_eq!(grown.old_size(), Some(Bytes::new(1000)));
+    assert_eq!(grown.new_size(), Some(Bytes::new(5000)));
+    assert_eq!(grown.delta(), Delta::Increase(Bytes::new(4000)));
+
+    let added = find("added");
+    assert_eq!(added.status(), DiffStatus::Added);
+    assert_eq!(added.old_size(), None);
+    assert_eq!(added.delta(), Delta::Increase(Bytes::new(700)));
+
+    let removed = find("removed");
+    assert_eq!(removed.status(), DiffStatus::Removed);
+    assert_eq!(removed.new_size(), None);
+    assert_eq!(removed.delta(), Delta::Decrease(Bytes::new(500)));
+
+    let shrunk = find("shrunk");
+    assert_eq!(shrunk.delta(), Delta::Decrease(Bytes::new(1800)));
+    assert_eq!(
+        shrunk.children()[0].delta(),
+        Delta::Decrease(Bytes::new(1800)),
+    );
+}
+
+#[test]
+fn delta_display() {
+    let display = |delta: Delta<Bytes>| delta.display(BytesFormat::PlainNumber).to_string();
+    assert_eq!(display(Delta::Increase(Bytes::new(700))), "+700");
+    assert_eq!(display(Delta::Decrease(Bytes::new(500))), "-500");
+    assert_eq!(display(Delta::Increase(Bytes::new(0))), "0");
+}
+
+#[test]
+fn diff_chart() {
+    let workspace = snapshot_workspace();
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--diff")
+        .with_arg("old.json")
+        .with_arg("new.json")
+        .with_arg("--bytes-format=metric")
+        .with_arg("--total-width=100")
+        .with_arg("--min-ratio=0")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text);
+    let actual = actual.trim_end();
+    eprintln!("ACTUAL:\n{actual}\n");
+
+    let data_tree = DiffTree::par_compare(old_tree(), new_tree())
+        .into_chart_tree(BytesFormat::MetricUnits)
+        .into_par_sorted(|left, right| left.size().cmp(&right.size()).reverse());
+    let visualizer = Visualizer {
+        data_tree: &data_tree,
+        bytes_format: BytesFormat::MetricUnits,
+        direction: Direction::BottomUp,
+        bar_alignment: BarAlignment::Left,
+        column_width_distribution: ColumnWidthDistribution::total(100),
+    };
+    let expected = format!("{visualizer}");
+    let expected = expected.trim_end();
+    eprintln!("EXPECTED:\n{expected}\n");
+
+    assert_eq!(actual, expected);
+
+    for fragment in [
+        "[+] added (+700)",
+        "[-] removed (-500)",
+        "grown (+4.0K)",
+        "shrunk (-1.8K)",
+    ] {
+        assert!(
+            actual.contains(fragment),
+            "expecting {fragment:?} in the output",
+        );
+    }
+}
+
+#[test]
+fn diff_chart_aggregate_culled() {
+    let workspace = snapshot_workspace();
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--diff")
+        .with_arg("old.json")
+        .with_arg("new.json")
+        .with_arg("--bytes-format=plain")
+        .with_arg("--total-width=100")
+        .with_arg("--min-ratio=0")
+        .with_arg("--min-size=1000")
+        .with_arg("--aggregate-culled")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text);
+    eprintln!("ACTUAL:\n{actual}\n");
+    // "unchanged", "added", and "removed" are culled, their changes add up to +200
+    let aggregate = actual
+        .lines()
+        .find(|line| line.contains("… (3 smaller items) (+200)"))
+        .expect("find the aggregate");
+    assert!(
+        aggregate.trim_start().starts_with("1200 "),
+        "unexpected {aggregate:?}"
+    );
+    assert!(!actual.contains("added"));
+    assert!(!actual.contains("removed"));
+}
+
+#[test]
+fn diff_json_output() {
+    let workspace = snapshot_workspace();
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--diff")
+        .with_arg("old.json")
+        .with_arg("new.json")
+        .with_arg("--json-output")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+        .pipe_as_ref(serde_json::from_str::<serde_json::Value>)
+        .expect("parse stdout as JSON");
+    dbg!(&actual);
+
+    assert_eq!(actual["schema-version"], json!(SCHEMA_VERSION));
+    assert_eq!(actual["unit"], json!("bytes"));
+    let tree = &actual["tree"];
+    assert_eq!(tree["name"], json!("root"));
+    assert_eq!(tree["delta"], json!(2400));
+    let find = |name: &str| {
+        tree["children"]
+            .as_array()
+            .expect("get children")
+            .iter()
+            .find(|child| child["name"] == json!(name))
+            .cloned()
+            .unwrap_or_else(|| panic!("find child named {name:?}"))
+    };
+    assert_eq!(
+        find("added"),
+        json!({ "name": "added", "new-size": 700, "delta": 700, "children": [] }),
+    );
+    assert_eq!(
+        find("removed"),
+        json!({ "name": "removed", "old-size": 500, "delta": -500, "children": [] }),
+    );
+    assert_eq!(
+        find("grown"),
+        json!({ "name": "grown", "old-size": 1000, "new-size": 5000, "delta": 4000, "children": [] }),
+    );
+}
+
+#[test]
+fn diff_unit_mismatch() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    write_snapshot(&workspace.join("old.json"), old_tree());
+    let blocks_tree = DataTree::<SampleName, Blocks>::file(
+        OsStringDisplay::os_string_from("root"),
+        Blocks::new(8),
+    );
+    write_snapshot(&workspace.join("new.json"), blocks_tree);
+    let output = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--diff")
+        .with_arg("old.json")
+        .with_arg("new.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command");
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    let stderr = stderr.trim_end();
+    assert_eq!(output.status.code(), Some(8));
+    assert_eq!(
+        stderr,
+        "[error] UnitMismatch: Data in bytes cannot be combined with data in blocks",
+    );
+}
+
+#[test]
+fn diff_missing_snapshot() {
+    let workspace = snapshot_workspace();
+    let output = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--diff")
+        .with_arg("old.json")
+        .with_arg("missing.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command");
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    assert_eq!(output.status.code(), Some(7));
+    assert!(
+        stderr.starts_with(r#"[error] ReadInputFailure: "missing.json": "#),
+        "unexpected stderr: {stderr:?}",
+    );
+}
+
+#[test]
+fn diff_rejects_flat() {
+    let workspace = snapshot_workspace();
+    let output = run_pdu(&workspace, &["--diff", "old.json", "new.json", "--flat"]);
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.contains("'--diff <OLD> <NEW>' cannot be used with '--flat'"),
+        "unexpected stderr: {stderr:?}",
+    );
+}
+
+#[test]
+fn diff_comparable_headers() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let header = |hostname| Some(sample_header(hostname, ScanQuantity::ApparentSize));
+    write_snapshot_with_header(&workspace.join("old.json"), old_tree(), header("old-host"));
+    write_snapshot_with_header(&workspace.join("new.json"), new_tree(), header("new-host"));
+    let output = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--diff")
+        .with_arg("old.json")
+        .with_arg("new.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command");
+    inspect_stderr(&output.stderr);
+    assert!(output.status.success(), "status: {:?}", output.status);
+}
+
+#[test]
+fn diff_incomparable_headers() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let header = |quantity| Some(sample_header("host", quantity));
+    write_snapshot_with_header(
+        &workspace.join("old.json"),
+        old_tree(),
+        header(ScanQuantity::BlockSize),
+    );
+    write_snapshot_with_header(
+        &workspace.join("new.json"),
+        new_tree(),
+        header(ScanQuantity::ApparentSize),
+    );
+    let output = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--diff")
+        .with_arg("old.json")
+        .with_arg("new.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command");
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    let stderr = stderr.trim_end();
+    assert_eq!(output.status.code(), Some(13));
+    assert_eq!(
+        stderr,
+        r#"[error] IncomparableSnapshots: "old.json" and "new.json" were scanned with different quantity"#,
+    );
+}
+
+#[test]
+fn diff_headers_of_different_scans() {
+    fn different_root(header: &mut ScanHeader) {
+        header.roots[0].path = OsStringDisplay::os_string_from("/backup");
+    }
+    fn different_depth(header: &mut ScanHeader) {
+        header.settings.max_depth = None;
+    }
+    let cases = [
+        ("roots", different_root as fn(&mut ScanHeader)),
+        ("max-depth", different_depth),
+    ];
+    for (difference, modify) in cases {
+        let workspace = Temp::new_dir().expect("create temporary directory");
+        let old_header = sample_header("host", ScanQuantity::ApparentSize);
+        let mut new_header = old_header.clone();
+        modify(&mut new_header);
+        write_snapshot_with_header(&workspace.join("old.json"), old_tree(), Some(old_header));
+        write_snapshot_with_header(&workspace.join("new.json"), new_tree(), Some(new_header));
+        let output = Command::new(PDU)
+            .with_current_dir(&workspace)
+            .with_arg("--diff")
+            .with_arg("old.json")
+            .with_arg("new.json")
+            .with_stdin(Stdio::null())
+            .with_stdout(Stdio::piped())
+            .with_stderr(Stdio::piped())
+            .output()
+            .expect("spawn command");
+        let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+        let stderr = stderr.trim_end();
+        assert_eq!(output.status.code(), Some(13));
+        assert_eq!(
+            stderr,
+            format!(
+                r#"[error] IncomparableSnapshots: "old.json" and "new.json" were scanned with different {difference}"#,
+            ),
+        );
+    }
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use command_extra::CommandExtra;
+use pretty_assertions::assert_eq;
+use std::process::{Command, Stdio};
+
+/// Extract the size column of the chart.
+fn sizes(chart: &str) -> Vec<&str> {
+    chart
+        .lines()
+        .map(|line| line.split_whitespace().next().expect("get the size"))
+        .collect()
+}
+
+#[test]
+fn same_unit_across_the_chart() {
+    let workspace = SampleWorkspace::default();
+    let output = run_pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--unit=MiB",
+            "--precision=2",
+            "--suffix-style=long",
+            "flat",
+        ],
+    );
+    inspect_stderr(&output.stderr);
+    assert!(output.status.success(), "status: {:?}", output.status);
+    let actual = stdout_text(output);
+    eprintln!("ACTUAL:\n{actual}\n");
+    let sizes = sizes(&actual);
+    assert_eq!(sizes[..4], ["0.00MiB", "0.10MiB", "0.19MiB", "0.29MiB"]);
+    assert!(sizes[4].starts_with("0.") && sizes[4].ends_with("MiB")); // the size of the directory varies
+}
+
+#[test]
+fn same_unit_for_json_input() {
+    let workspace = SampleWorkspace::default();
+    let output = run_pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--min-ratio=0",
+            "--json-output",
+            "flat",
+        ],
+    );
+    inspect_stderr(&output.stderr);
+    assert!(output.status.success(), "status: {:?}", output.status);
+    let json = output.stdout;
+
+    let mut command = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_args([
+            "--json-input",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--unit=kb",
+            "--precision=0",
+        ])
+        .with_stdin(Stdio::piped())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .spawn()
+        .expect("spawn command");
+    {
+        use std::io::Write;
+        let stdin = command.stdin.as_mut().expect("get stdin");
+        stdin.write_all(&json).expect("write JSON to stdin");
+    }
+    let output = command.wait_with_output().expect("wait for command");
+    inspect_stderr(&output.stderr);
+    assert!(output.status.success(), "status: {:?}", output.status);
+    let actual = stdout_text(output);
+    eprintln!("ACTUAL:\n{actual}\n");
+    let sizes = sizes(&actual);
+    assert_eq!(sizes[..4], ["0K", "100K", "200K", "300K"]);
+    assert!(sizes[4].ends_with('K')); // the size of the directory varies
+}
+
+#[test]
+fn unit_conflicts_with_bytes_format() {
+    let workspace = SampleWorkspace::default();
+    let output = run_pdu(&workspace, &["--unit=GiB", "--bytes-format=metric"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot be used with"), "stderr: {stderr}");
+}
+
+#[test]
+fn unit_settings_conflict_with_plain_bytes_format() {
+    let workspace = SampleWorkspace::default();
+    for setting in ["--precision=2", "--suffix-style=long"] {
+        let output = run_pdu(&workspace, &["--bytes-format=plain", setting]);
+        assert_eq!(output.status.code(), Some(2), "exit code with {setting}");
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(
+            stderr.contains("cannot be used with --bytes-format=plain"),
+            "stderr: {stderr}",
+        );
+    }
+    let output = run_pdu(&workspace, &["--bytes-format=metric", "--precision=2"]);
+    assert!(output.status.success());
+}
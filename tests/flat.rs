@@ -0,0 +1,128 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use pretty_assertions::assert_eq;
+
+/// Extract the paths from the lines of a flat list.
+fn paths(list: &str) -> Vec<&str> {
+    list.lines()
+        .map(|line| line.split_once("% ").expect("find the percentage").1)
+        .collect()
+}
+
+#[test]
+fn flat() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--bytes-format=plain",
+            "--min-ratio=0",
+            "--flat",
+            "flat",
+            "nested",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    let expected = [
+        "(total)".to_string(),
+        native_path("flat"),
+        native_path("nested"),
+        native_path("nested/0"),
+        native_path("nested/0/1"),
+        native_path("flat/3"),
+        native_path("flat/2"),
+        native_path("flat/1"),
+        native_path("flat/0"),
+    ];
+    assert_eq!(paths(&actual), expected);
+    let first = actual.lines().next().expect("get the first line");
+    assert!(
+        first.ends_with(" 100% (total)"),
+        "unexpected line {first:?}"
+    );
+}
+
+#[test]
+fn exclude_ancestors() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--bytes-format=plain",
+            "--min-ratio=0",
+            "--flat",
+            "--exclude-ancestors",
+            "flat",
+            "nested",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    let expected = [
+        native_path("nested/0/1"),
+        native_path("flat/3"),
+        native_path("flat/2"),
+        native_path("flat/1"),
+        native_path("flat/0"),
+    ];
+    assert_eq!(paths(&actual), expected);
+    let first = actual.lines().next().expect("get the first line");
+    assert!(first.starts_with("500000 "), "unexpected line {first:?}");
+}
+
+#[test]
+fn shaped_by_max_depth() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--min-ratio=0",
+            "--max-depth=2",
+            "--flat",
+            "--exclude-ancestors",
+            "nested",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(paths(&actual), [native_path("nested/0")]);
+}
+
+#[test]
+fn json_input() {
+    let workspace = SampleWorkspace::default();
+    let json = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--min-ratio=0",
+            "--json-output",
+            "flat",
+        ],
+    );
+    let json_file = workspace.join("flat.json");
+    std::fs::write(&json_file, json).expect("write JSON file");
+    let actual = pdu(
+        &workspace,
+        &[
+            "--json-input",
+            "--min-ratio=0",
+            "--flat",
+            "--exclude-ancestors",
+            "flat.json",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    let expected = [
+        native_path("flat/3"),
+        native_path("flat/2"),
+        native_path("flat/1"),
+        native_path("flat/0"),
+    ];
+    assert_eq!(paths(&actual), expected);
+}
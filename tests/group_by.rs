@@ -0,0 +1,152 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+/// Extract the name and the size of each node in a JSON tree, recursively.
+fn summarize(node: &Value) -> (String, u64, Vec<(String, u64)>) {
+    let name = node["name"].as_str().expect("get the name").to_string();
+    let size = node["size"].as_u64().expect("get the size");
+    let children = node["children"]
+        .as_array()
+        .expect("get the children")
+        .iter()
+        .map(|child| {
+            let name = child["name"].as_str().expect("get the name").to_string();
+            let size = child["size"].as_u64().expect("get the size");
+            (name, size)
+        })
+        .collect();
+    (name, size, children)
+}
+
+/// Create a directory of files with various extensions.
+fn create_workspace() -> Temp {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let root = workspace.join("root");
+    std::fs::create_dir_all(root.join("sub")).expect("create directories");
+    for (name, size) in [
+        ("a.log", 3000),
+        ("b.log", 1000),
+        ("c.parquet", 5000),
+        ("d", 200),
+        ("sub/e.log", 2000),
+    ] {
+        std::fs::write(root.join(name), "a".repeat(size)).expect("write file");
+    }
+    workspace
+}
+
+#[test]
+fn group_by_extension() {
+    let workspace = create_workspace();
+    let json = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--scan-header",
+            "--min-ratio=0",
+            "--max-children=2",
+            "--group-by=extension",
+            "--json-output",
+            "root",
+        ],
+    );
+    let json: Value = serde_json::from_str(&json).expect("parse JSON");
+    let tree = &json["tree"];
+    // --max-children limits the paths of each category, not the categories
+    assert_eq!(
+        summarize(tree),
+        (
+            "root".to_string(),
+            11200,
+            vec![
+                (".log".to_string(), 6000),
+                (".parquet".to_string(), 5000),
+                ("(no extension)".to_string(), 200),
+            ],
+        ),
+    );
+    assert_eq!(
+        summarize(&tree["children"][0]).2,
+        [
+            (native_path("root/a.log"), 3000),
+            (native_path("root/sub/e.log"), 2000),
+        ],
+    );
+    assert_eq!(
+        summarize(&tree["children"][1]).2,
+        [(native_path("root/c.parquet"), 5000)],
+    );
+    assert_eq!(json["header"]["settings"]["group-by"], "extension");
+}
+
+#[test]
+fn group_by_extension_chart() {
+    let workspace = create_workspace();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--top-down",
+            "--group-by=extension",
+            "root",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    let lines: Vec<_> = actual.lines().collect();
+    assert_eq!(lines.len(), 9);
+    assert!(lines[1].contains(".log "), "unexpected {:?}", lines[1]);
+    assert!(
+        lines[2].contains(&native_path("root/a.log")),
+        "unexpected {:?}",
+        lines[2]
+    );
+    assert!(actual.contains("(no extension)"));
+}
+
+#[test]
+fn group_by_type() {
+    let workspace = create_workspace();
+    let json = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--min-ratio=0",
+            "--group-by=type",
+            "--json-output",
+            "root",
+        ],
+    );
+    let json: Value = serde_json::from_str(&json).expect("parse JSON");
+    let (_, _, categories) = summarize(&json["tree"]);
+    let names: Vec<_> = categories.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["file", "dir"]);
+    assert_eq!(categories[0].1, 11200);
+}
+
+#[cfg(unix)]
+#[test]
+fn group_by_owner() {
+    let workspace = create_workspace();
+    let json = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--min-ratio=0",
+            "--group-by=owner",
+            "--json-output",
+            "root",
+        ],
+    );
+    let json: Value = serde_json::from_str(&json).expect("parse JSON");
+    let (_, total, categories) = summarize(&json["tree"]);
+    let uid = unsafe { libc::getuid() };
+    assert_eq!(categories, [(format!("uid {uid}"), total)]);
+}
@@ -15,6 +15,7 @@ use parallel_disk_usage::hardlink::hardlink_list::Summary;
 use parallel_disk_usage::hardlink::hardlink_list::reflection::ReflectionEntry;
 use parallel_disk_usage::inode::InodeNumber;
 use parallel_disk_usage::json_data::{JsonData, JsonTree};
+use parallel_disk_usage::os_string_display::OsStringDisplay;
 use parallel_disk_usage::size::Bytes;
 use pipe_trait::Pipe;
 use pretty_assertions::assert_eq;
@@ -106,77 +107,77 @@ fn simple_tree_with_some_hardlinks() {
     assert_eq!(actual_size, expected_size);
 
     let actual_tree = &tree.tree;
-    let mut expected_tree = Reflection {
-        name: "main".to_string(),
-        size: expected_size,
-        children: vec![
-            Reflection {
-                name: "sources".to_string(),
-                size: inode_size("main/sources")
+    let mut expected_tree = Reflection::new(
+        OsStringDisplay::os_string_from("main"),
+        expected_size,
+        vec![
+            Reflection::new(
+                OsStringDisplay::os_string_from("sources"),
+                inode_size("main/sources")
                     + file_size("no-hardlinks.txt")
                     + file_size("one-internal-hardlink.txt")
                     + file_size("two-internal-hardlinks.txt")
                     + file_size("one-external-hardlink.txt")
                     + file_size("one-internal-one-external-hardlinks.txt"),
-                children: vec![
-                    Reflection {
-                        name: "no-hardlinks.txt".to_string(),
-                        size: file_size("no-hardlinks.txt"),
-                        children: Vec::new(),
-                    },
-                    Reflection {
-                        name: "one-internal-hardlink.txt".to_string(),
-                        size: file_size("one-internal-hardlink.txt"),
-                        children: Vec::new(),
-                    },
-                    Reflection {
-                        name: "two-internal-hardlinks.txt".to_string(),
-                        size: file_size("two-internal-hardlinks.txt"),
-                        children: Vec::new(),
-                    },
-                    Reflection {
-                        name: "one-external-hardlink.txt".to_string(),
-                        size: file_size("one-external-hardlink.txt"),
-                        children: Vec::new(),
-                    },
-                    Reflection {
-                        name: "one-internal-one-external-hardlinks.txt".to_string(),
-                        size: file_size("one-internal-one-external-hardlinks.txt"),
-                        children: Vec::new(),
-                    },
+                vec![
+                    Reflection::new(
+                        OsStringDisplay::os_string_from("no-hardlinks.txt"),
+                        file_size("no-hardlinks.txt"),
+                        Vec::new(),
+                    ),
+                    Reflection::new(
+                        OsStringDisplay::os_string_from("one-internal-hardlink.txt"),
+                        file_size("one-internal-hardlink.txt"),
+                        Vec::new(),
+                    ),
+                    Reflection::new(
+                        OsStringDisplay::os_string_from("two-internal-hardlinks.txt"),
+                        file_size("two-internal-hardlinks.txt"),
+                        Vec::new(),
+                    ),
+                    Reflection::new(
+                        OsStringDisplay::os_string_from("one-external-hardlink.txt"),
+                        file_size("one-external-hardlink.txt"),
+                        Vec::new(),
+                    ),
+                    Reflection::new(
+                        OsStringDisplay::os_string_from("one-internal-one-external-hardlinks.txt"),
+                        file_size("one-internal-one-external-hardlinks.txt"),
+                        Vec::new(),
+                    ),
                 ],
-            },
-            Reflection {
-                name: "internal-hardlinks".to_string(),
-                size: inode_size("main/internal-hardlinks")
+            ),
+            Reflection::new(
+                OsStringDisplay::os_string_from("internal-hardlinks"),
+                inode_size("main/internal-hardlinks")
                     + file_size("one-internal-hardlink.txt")
                     + file_size("two-internal-hardlinks.txt")
                     + file_size("one-internal-one-external-hardlinks.txt"),
-                children: vec![
-                    Reflection {
-                        name: "link-0.txt".to_string(),
-                        size: file_size("one-internal-hardlink.txt"),
-                        children: Vec::new(),
-                    },
-                    Reflection {
-                        name: "link-1a.txt".to_string(),
-                        size: file_size("two-internal-hardlinks.txt"),
-                        children: Vec::new(),
-                    },
-                    Reflection {
-                        name: "link-1b.txt".to_string(),
-                        size: file_size("two-internal-hardlinks.txt"),
-                        children: Vec::new(),
-                    },
-                    Reflection {
-                        name: "link-3a.txt".to_string(),
-                        size: file_size("one-internal-one-external-hardlinks.txt"),
-                        children: Vec::new(),
-                    },
+                vec![
+                    Reflection::new(
+                        OsStringDisplay::os_string_from("link-0.txt"),
+                        file_size("one-internal-hardlink.txt"),
+                        Vec::new(),
+                    ),
+                    Reflection::new(
+                        OsStringDisplay::os_string_from("link-1a.txt"),
+                        file_size("two-internal-hardlinks.txt"),
+                        Vec::new(),
+                    ),
+                    Reflection::new(
+                        OsStringDisplay::os_string_from("link-1b.txt"),
+                        file_size("two-internal-hardlinks.txt"),
+                        Vec::new(),
+                    ),
+                    Reflection::new(
+                        OsStringDisplay::os_string_from("link-3a.txt"),
+                        file_size("one-internal-one-external-hardlinks.txt"),
+                        Vec::new(),
+                    ),
                 ],
-            },
+            ),
         ],
-    };
+    );
     sort_reflection_by(&mut expected_tree, |a, b| a.name.cmp(&b.name));
     assert_eq!(actual_tree, &expected_tree);
 
@@ -357,15 +358,12 @@ fn multiple_hardlinks_to_a_single_file() {
         .into_sorted_by(|a, b| a.name.cmp(&b.name));
     let expected_children: Vec<_> = {
         let links = (0..links).map(|num| format!("link.{num}"));
-        let node = |name| Reflection {
-            name,
-            size: file_size,
-            children: Vec::new(),
-        };
+        let node = |name| Reflection::new(name, file_size, Vec::new());
         "file.txt"
             .to_string()
             .pipe(iter::once)
             .chain(links)
+            .map(OsStringDisplay::os_string_from)
             .map(node)
             .collect()
     };
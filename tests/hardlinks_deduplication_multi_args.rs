@@ -14,6 +14,7 @@ use parallel_disk_usage::hardlink::hardlink_list::Summary;
 use parallel_disk_usage::hardlink::hardlink_list::reflection::ReflectionEntry;
 use parallel_disk_usage::inode::InodeNumber;
 use parallel_disk_usage::json_data::{JsonData, JsonTree};
+use parallel_disk_usage::os_string_display::OsStringDisplay;
 use parallel_disk_usage::size::Bytes;
 use pipe_trait::Pipe;
 use pretty_assertions::assert_eq;
@@ -120,15 +121,15 @@ fn simple_tree_with_some_hardlinks() {
             .expect("get tree of bytes")
             .tree;
         sort_reflection_by(&mut tree, |a, b| a.name.cmp(&b.name));
-        tree.name = "(total)".to_string();
+        tree.name = OsStringDisplay::os_string_from("(total)");
         tree.size = expected_size;
         for child in &mut tree.children {
-            let name = match child.name.as_str() {
+            let name = match child.name.to_string().as_str() {
                 "sources" => "main/sources",
                 "internal-hardlinks" => "main/internal-hardlinks",
                 name => panic!("Unexpected name: {name:?}"),
             };
-            child.name = name.to_string();
+            child.name = OsStringDisplay::os_string_from(name);
         }
         tree
     };
@@ -309,11 +310,8 @@ fn multiple_hardlinks_to_a_single_file() {
         .children
         .clone()
         .into_sorted_by(|a, b| a.name.cmp(&b.name));
-    let expected_children = args.map(|name| Reflection {
-        name: name.to_string(),
-        size: file_size,
-        children: Vec::new(),
-    });
+    let expected_children = args
+        .map(|name| Reflection::new(OsStringDisplay::os_string_from(name), file_size, Vec::new()));
     assert_eq!(actual_children, expected_children);
 
     let actual_shared_details: Vec<_> = tree
@@ -486,17 +484,17 @@ fn multiple_duplicated_arguments() {
             .pipe(JsonTree::<Bytes>::try_from)
             .expect("get tree of bytes")
             .tree;
-        tree.name = "(total)".to_string();
+        tree.name = OsStringDisplay::os_string_from("(total)");
         tree.size = expected_size;
         for child in &mut tree.children {
-            let name = match child.name.as_str() {
+            let name = match child.name.to_string().as_str() {
                 "sources" => "main/sources",
                 "internal-hardlinks" => {
                     "workspace-itself/main/parent-of-main/main-mirror/internal-hardlinks"
                 }
                 name => panic!("Unexpected name: {name:?}"),
             };
-            child.name = name.to_string();
+            child.name = OsStringDisplay::os_string_from(name);
         }
         sort_reflection_by(&mut tree, |a, b| a.name.cmp(&b.name));
         tree
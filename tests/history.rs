@@ -0,0 +1,211 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use rusqlite::Connection;
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+/// Scan `nested` and append it to `history.sqlite`.
+fn save_scan(workspace: &Path, quantity: &str) {
+    let output = run_pdu(
+        workspace,
+        &[
+            "--save-to=history.sqlite",
+            "--scan-header",
+            quantity,
+            "--min-ratio=0",
+            "nested",
+        ],
+    );
+    inspect_stderr(&output.stderr);
+    assert!(output.status.success(), "status: {:?}", output.status);
+}
+
+#[test]
+fn save_to_nodes_keyed_by_path() {
+    let workspace = SampleWorkspace::default();
+    save_scan(&workspace, "--quantity=apparent-size");
+    save_scan(&workspace, "--quantity=apparent-size");
+
+    let connection = Connection::open(workspace.join("history.sqlite")).expect("open database");
+    let snapshots: Vec<(i64, String)> = connection
+        .prepare("SELECT id, unit FROM snapshots ORDER BY id")
+        .expect("prepare query")
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .expect("query snapshots")
+        .collect::<Result<_, _>>()
+        .expect("read snapshots");
+    assert_eq!(
+        snapshots,
+        [(1, "bytes".to_string()), (2, "bytes".to_string())],
+    );
+
+    let nodes: Vec<(String, Option<String>, u64, u64)> = connection
+        .prepare("SELECT path, parent, depth, size FROM nodes WHERE snapshot_id = 2 ORDER BY path")
+        .expect("prepare query")
+        .query_map([], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+        })
+        .expect("query nodes")
+        .collect::<Result<_, _>>()
+        .expect("read nodes");
+    let paths: Vec<_> = nodes
+        .iter()
+        .map(|(path, parent, depth, _)| (path.as_str(), parent.as_deref(), *depth))
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            ("nested", None, 0),
+            ("nested/0", Some("nested"), 1),
+            ("nested/0/1", Some("nested/0"), 2),
+        ],
+    );
+    assert_eq!(nodes[2].3, 500_000);
+}
+
+#[test]
+fn growth_report_chart() {
+    let workspace = SampleWorkspace::default();
+    save_scan(&workspace, "--quantity=apparent-size");
+    fs::write(workspace.join("nested/0/2"), "a".repeat(300_000)).expect("write a new file");
+    save_scan(&workspace, "--quantity=apparent-size");
+
+    let output = run_pdu(
+        &workspace,
+        &[
+            "--growth-report=history.sqlite",
+            "--total-width=100",
+            "--min-ratio=0",
+        ],
+    );
+    inspect_stderr(&output.stderr);
+    let actual = stdout_text(output);
+    eprintln!("ACTUAL:\n{actual}\n");
+    for fragment in ["[+] 2 (+300.0K)", "1 (0)", "nested (+300.0K)"] {
+        assert!(
+            actual.contains(fragment),
+            "expecting {fragment:?} in the output",
+        );
+    }
+}
+
+#[test]
+fn growth_report_spans_snapshots() {
+    let workspace = SampleWorkspace::default();
+    save_scan(&workspace, "--quantity=apparent-size");
+    fs::write(workspace.join("nested/0/2"), "a".repeat(300_000)).expect("write a new file");
+    save_scan(&workspace, "--quantity=apparent-size");
+    fs::write(workspace.join("nested/0/3"), "a".repeat(100_000)).expect("write a new file");
+    save_scan(&workspace, "--quantity=apparent-size");
+
+    let delta = |snapshots: &str| {
+        run_pdu(
+            &workspace,
+            &[
+                "--growth-report=history.sqlite",
+                &format!("--growth-snapshots={snapshots}"),
+                "--json-output",
+            ],
+        )
+        .pipe(stdout_text)
+        .pipe_as_ref(serde_json::from_str::<serde_json::Value>)
+        .expect("parse stdout as JSON")["tree"]["delta"]
+            .clone()
+    };
+    assert_eq!(delta("2"), json!(100_000));
+    assert_eq!(delta("3"), json!(400_000));
+    assert_eq!(delta("10"), json!(400_000));
+}
+
+#[test]
+fn growth_report_insufficient_snapshots() {
+    let workspace = SampleWorkspace::default();
+    save_scan(&workspace, "--quantity=apparent-size");
+    let output = run_pdu(&workspace, &["--growth-report=history.sqlite"]);
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    assert_eq!(output.status.code(), Some(16));
+    assert_eq!(
+        stderr.trim_end(),
+        "[error] InsufficientSnapshots: At least 2 snapshots are required, but only 1 found",
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn growth_report_incomparable_snapshots() {
+    let workspace = SampleWorkspace::default();
+    save_scan(&workspace, "--quantity=apparent-size");
+    save_scan(&workspace, "--quantity=block-size");
+    let output = run_pdu(&workspace, &["--growth-report=history.sqlite"]);
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    assert_eq!(output.status.code(), Some(13));
+    assert_eq!(
+        stderr.trim_end(),
+        r#"[error] IncomparableSnapshots: "history.sqlite#1" and "history.sqlite#2" were scanned with different quantity"#,
+    );
+}
+
+#[test]
+fn save_to_records_unshaped_tree() {
+    let workspace = SampleWorkspace::default();
+    let output = run_pdu(
+        &workspace,
+        &[
+            "--save-to=history.sqlite",
+            "--quantity=apparent-size",
+            "--max-depth=1",
+            "--min-size=1M",
+            "nested",
+        ],
+    );
+    let chart = stdout_text(output);
+    assert_eq!(chart.lines().count(), 1, "chart:\n{chart}");
+
+    let connection = Connection::open(workspace.join("history.sqlite")).expect("open database");
+    let paths: Vec<String> = connection
+        .prepare("SELECT path FROM nodes ORDER BY path")
+        .expect("prepare query")
+        .query_map([], |row| row.get(0))
+        .expect("query nodes")
+        .collect::<Result<_, _>>()
+        .expect("read nodes");
+    assert_eq!(paths, ["nested", "nested/0", "nested/0/1"]);
+}
+
+#[test]
+fn save_to_from_json_input() {
+    let workspace = SampleWorkspace::default();
+    let snapshot = run_pdu(
+        &workspace,
+        &["--json-output", "--quantity=apparent-size", "nested"],
+    )
+    .pipe(stdout_text);
+    fs::write(workspace.join("old.json"), snapshot).expect("write JSON snapshot");
+    fs::write(workspace.join("nested/0/2"), "a".repeat(300_000)).expect("write a new file");
+    save_scan(&workspace, "--quantity=apparent-size");
+
+    // import the older snapshot last, the report compares by insertion order
+    let output = run_pdu(
+        &workspace,
+        &["--json-input", "--save-to=history.sqlite", "old.json"],
+    );
+    inspect_stderr(&output.stderr);
+    assert!(output.status.success(), "status: {:?}", output.status);
+
+    let actual = run_pdu(
+        &workspace,
+        &["--growth-report=history.sqlite", "--json-output"],
+    )
+    .pipe(stdout_text)
+    .pipe_as_ref(serde_json::from_str::<serde_json::Value>)
+    .expect("parse stdout as JSON");
+    assert_eq!(actual["tree"]["name"], json!("nested"));
+    assert_eq!(actual["tree"]["delta"], json!(-300_000));
+}
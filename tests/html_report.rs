@@ -0,0 +1,240 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use command_extra::CommandExtra;
+use parallel_disk_usage::bytes_format::BytesFormat;
+use parallel_disk_usage::data_tree::DataTree;
+use parallel_disk_usage::html_report::HtmlReport;
+use parallel_disk_usage::json_data::{JsonData, JsonDataBody, JsonTree, SchemaVersion};
+use parallel_disk_usage::os_string_display::OsStringDisplay;
+use parallel_disk_usage::size::Bytes;
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use std::fs;
+use std::process::{Command, Stdio};
+
+type SampleTree = DataTree<OsStringDisplay, Bytes>;
+
+/// Tree whose names could break out of the script elements of the report.
+fn markup_names_tree() -> SampleTree {
+    sample_dir(
+        "root",
+        1000,
+        vec![sample_file("</script>", 3000), sample_file("<!--", 2000)],
+    )
+}
+
+fn sample_json_data() -> JsonData {
+    JsonData {
+        schema_version: SchemaVersion,
+        binary_version: None,
+        header: None,
+        body: JsonTree {
+            tree: markup_names_tree().into_reflection(),
+            shared: Default::default(),
+        }
+        .into(),
+    }
+}
+
+/// Extract the description of the format of the sizes embedded in an HTML report.
+fn embedded_bytes_format(html: &str) -> serde_json::Value {
+    let (_, rest) = html
+        .split_once(r#"<script type="application/json" id="pdu-bytes-format">"#)
+        .expect("find the embedded bytes format");
+    let (json, _) = rest
+        .split_once("</script>")
+        .expect("find the end of the bytes format");
+    serde_json::from_str(json).expect("parse the embedded bytes format")
+}
+
+/// Extract the data embedded in an HTML report.
+fn embedded_json(html: &str) -> JsonData {
+    let (_, rest) = html
+        .split_once(r#"<script type="application/json" id="pdu-data">"#)
+        .expect("find the embedded data");
+    let (json, _) = rest
+        .split_once("</script>")
+        .expect("find the end of the data");
+    serde_json::from_str(json).expect("parse the embedded data")
+}
+
+#[test]
+fn embedded_data_cannot_end_script() {
+    let json = serde_json::to_string(&sample_json_data()).expect("serialize sample data");
+    let html = HtmlReport {
+        json: &json,
+        bytes_format: BytesFormat::MetricUnits,
+    }
+    .to_string();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(!html.contains("\"</script>"));
+    assert!(!html.contains("<!--\""));
+
+    let JsonDataBody::Bytes(actual) = embedded_json(&html).body else {
+        panic!("expecting data in bytes");
+    };
+    let mut actual = actual
+        .tree
+        .par_try_into_tree()
+        .expect("convert reflection into tree");
+    let mut expected = markup_names_tree();
+    actual.par_sort_by(|left, right| left.name().cmp(right.name()));
+    expected.par_sort_by(|left, right| left.name().cmp(right.name()));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn html_output_from_scan() {
+    let workspace = SampleWorkspace::default();
+    let html = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--output-format=html")
+        .with_arg("--scan-header")
+        .with_arg("--quantity=apparent-size")
+        .with_arg("--min-ratio=0")
+        .with_arg("nested")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text);
+    for fragment in ["<!DOCTYPE html>", "Treemap", "Icicle", "Hardlinks"] {
+        assert!(
+            html.contains(fragment),
+            "expecting {fragment:?} in the page"
+        );
+    }
+    assert!(
+        !html.contains("<script src=") && !html.contains("<link "),
+        "the page should not load external resources",
+    );
+
+    let json_data = embedded_json(&html);
+    assert!(json_data.header.is_some());
+    let JsonDataBody::Bytes(tree) = json_data.body else {
+        panic!("expecting data in bytes");
+    };
+    assert_eq!(tree.tree.name.to_string(), "nested");
+    assert_eq!(tree.tree.children[0].children[0].size, Bytes::new(500_000));
+}
+
+#[test]
+fn html_bytes_format() {
+    let json = serde_json::to_string(&sample_json_data()).expect("serialize sample data");
+    let bytes_format = |bytes_format| {
+        HtmlReport {
+            json: &json,
+            bytes_format,
+        }
+        .to_string()
+        .pipe_as_ref(embedded_bytes_format)
+    };
+    assert_eq!(
+        bytes_format(BytesFormat::PlainNumber),
+        serde_json::Value::Null
+    );
+    assert_eq!(
+        bytes_format(BytesFormat::BinaryUnits),
+        serde_json::json!({
+            "scale-base": 1024,
+            "exponent": null,
+            "precision": 1,
+            "big-suffix": "",
+            "small-suffix": "",
+        }),
+    );
+}
+
+#[test]
+fn html_output_follows_bytes_format() {
+    let workspace = SampleWorkspace::default();
+    let html = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--output-format=html")
+        .with_arg("--unit=MiB")
+        .with_arg("--precision=2")
+        .with_arg("--suffix-style=long")
+        .with_arg("nested")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text);
+    assert_eq!(
+        embedded_bytes_format(&html),
+        serde_json::json!({
+            "scale-base": 1024,
+            "exponent": 2,
+            "precision": 2,
+            "big-suffix": "iB",
+            "small-suffix": "B",
+        }),
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn html_output_non_utf8_names() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let name = OsStr::from_bytes(b"bad\xFFname");
+    fs::create_dir(workspace.join("root")).expect("create directory");
+    fs::write(workspace.join("root").join(name), "a".repeat(1000)).expect("write file");
+    let html = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--output-format=html")
+        .with_arg("--min-ratio=0")
+        .with_arg("root")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text);
+    assert!(
+        html.contains(r#"{"bytes":[98,97,100,255,110,97,109,101]}"#),
+        "the raw name should be embedded",
+    );
+    let JsonDataBody::Bytes(tree) = embedded_json(&html).body else {
+        panic!("expecting data in bytes");
+    };
+    let names: Vec<_> = tree
+        .tree
+        .children
+        .iter()
+        .map(|child| child.name.as_os_str())
+        .collect();
+    assert_eq!(names, [name]);
+}
+
+#[test]
+fn html_output_of_diff_is_unsupported() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let json = serde_json::to_string(&sample_json_data()).expect("serialize sample data");
+    fs::write(workspace.join("old.json"), &json).expect("write JSON snapshot");
+    fs::write(workspace.join("new.json"), &json).expect("write JSON snapshot");
+    let output = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--diff")
+        .with_arg("old.json")
+        .with_arg("new.json")
+        .with_arg("--output-format=html")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command");
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.contains("--output-format=html cannot be used with --diff"),
+        "unexpected stderr {stderr:?}",
+    );
+}
@@ -6,64 +6,45 @@ pub use _utils::*;
 
 use assert_cmp::assert_op_expr;
 use command_extra::CommandExtra;
+use flate2::Compression;
+use flate2::write::GzEncoder;
 use parallel_disk_usage::bytes_format::BytesFormat;
-use parallel_disk_usage::data_tree::DataTree;
+use parallel_disk_usage::data_tree::{DataTree, DataTreeReflection};
 use parallel_disk_usage::device::DeviceBoundary;
 use parallel_disk_usage::fs_tree_builder::FsTreeBuilder;
 use parallel_disk_usage::get_size::GetApparentSize;
-use parallel_disk_usage::hardlink::HardlinkIgnorant;
-use parallel_disk_usage::json_data::{JsonData, JsonTree, SchemaVersion};
+use parallel_disk_usage::hardlink::{
+    HardlinkIgnorant, HardlinkList, HardlinkListReflection, LinkPathListReflection,
+};
+use parallel_disk_usage::json_data::{JsonData, JsonTree, ScanQuantity, SchemaVersion};
+use parallel_disk_usage::os_string_display::OsStringDisplay;
 use parallel_disk_usage::reporter::{ErrorOnlyReporter, ErrorReport};
-use parallel_disk_usage::size::Bytes;
+use parallel_disk_usage::size::{Blocks, Bytes};
 use parallel_disk_usage::visualizer::{
     BarAlignment, ColumnWidthDistribution, Direction, Visualizer,
 };
 use pipe_trait::Pipe;
 use pretty_assertions::assert_eq;
+use std::collections::HashSet;
 use std::convert::TryInto;
+use std::fs::{self, File};
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
-type SampleName = String;
+type SampleName = OsStringDisplay;
 type SampleSize = Bytes;
 type SampleJsonTree = JsonTree<SampleSize>;
 type SampleTree = DataTree<SampleName, SampleSize>;
 
-fn sample_tree() -> SampleTree {
-    let dir = |name: &'static str, children: Vec<SampleTree>| {
-        SampleTree::dir(name.to_string(), 1024.into(), children)
-    };
-    let file =
-        |name: &'static str, size: u64| SampleTree::file(name.to_string(), Bytes::from(size));
-    dir(
-        "root",
-        vec![
-            file("foo", 2530),
-            file("bar", 52),
-            dir(
-                "hello",
-                vec![dir("world", vec![file("hello", 45), file("world", 54)])],
-            ),
-            dir("empty dir", vec![]),
-            dir(
-                "directory with a really long name",
-                vec![dir(
-                    "subdirectory with a really long name",
-                    vec![file("file with a really long name", 475)],
-                )],
-            ),
-        ],
-    )
-    .into_par_sorted(|left, right| left.size().cmp(&right.size()).reverse())
-}
-
 /// Sample tree whose entries are deliberately stored in ascending order of size,
 /// which is the opposite of the descending order produced by the default sorting.
 fn ascending_sample_tree() -> SampleTree {
-    let file =
-        |name: &'static str, size: u64| SampleTree::file(name.to_string(), Bytes::from(size));
+    let file = |name: &'static str, size: u64| {
+        SampleTree::file(OsStringDisplay::os_string_from(name), Bytes::from(size))
+    };
     SampleTree::dir(
-        "root".to_string(),
+        OsStringDisplay::os_string_from("root"),
         1024.into(),
         vec![file("a", 50), file("b", 500), file("c", 5000)],
     )
@@ -78,6 +59,7 @@ fn run_json_input(tree: SampleTree, extra_args: &[&str]) -> String {
     let json_data = JsonData {
         schema_version: SchemaVersion,
         binary_version: None,
+        header: None,
         body: json_tree.into(),
     };
     let json = serde_json::to_string_pretty(&json_data).expect("convert sample tree to JSON");
@@ -141,13 +123,393 @@ fn json_output() {
     let expected = builder
         .pipe(DataTree::<_, Bytes>::from)
         .into_reflection()
-        .par_convert_names_to_utf8()
-        .expect("convert all names from raw strings to UTF-8")
         .pipe(sanitize_tree_reflection);
     dbg!(&expected);
     assert_eq!(actual, expected);
 }
 
+#[cfg(unix)]
+#[test]
+fn json_output_json_input_non_utf8_name() {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let non_utf8_name = OsString::from_vec(vec![b'f', 0xFF, 0xDD]);
+    fs::write(workspace.join("utf8.txt"), "a".repeat(100)).expect("write UTF-8 file");
+    fs::write(workspace.join(&non_utf8_name), "b".repeat(200)).expect("write non-UTF-8 file");
+
+    let json = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-output")
+        .with_arg("--quantity=apparent-size")
+        .with_arg("--min-ratio=0")
+        .with_arg(".")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command with --json-output")
+        .pipe(stdout_text);
+    eprintln!("JSON: {json}\n");
+    assert!(json.contains(r#"{"bytes":[102,255,221]}"#));
+
+    let tree = serde_json::from_str::<JsonData>(&json)
+        .expect("parse stdout as JsonData")
+        .body
+        .pipe(TryInto::<SampleJsonTree>::try_into)
+        .expect("extract reflection")
+        .tree;
+    let mut names: Vec<_> = tree.children.iter().map(|child| &child.name).collect();
+    names.sort();
+    assert_eq!(
+        names,
+        [
+            &OsStringDisplay(non_utf8_name),
+            &OsStringDisplay::os_string_from("utf8.txt"),
+        ],
+    );
+
+    let visualize = |command: Command| {
+        command
+            .with_current_dir(&workspace)
+            .with_arg("--bytes-format=metric")
+            .with_arg("--total-width=100")
+            .with_arg("--min-ratio=0")
+            .with_stdout(Stdio::piped())
+            .with_stderr(Stdio::piped())
+    };
+
+    let mut child = Command::new(PDU)
+        .with_arg("--json-input")
+        .pipe(visualize)
+        .with_stdin(Stdio::piped())
+        .spawn()
+        .expect("spawn command with --json-input");
+    child
+        .stdin
+        .as_mut()
+        .expect("get stdin of child process")
+        .write_all(json.as_bytes())
+        .expect("write JSON string to child process's stdin");
+    let actual = child
+        .wait_with_output()
+        .expect("wait for output of child process")
+        .pipe(stdout_text);
+    eprintln!("ACTUAL:\n{actual}\n");
+
+    let expected = Command::new(PDU)
+        .with_arg("--quantity=apparent-size")
+        .with_arg(".")
+        .pipe(visualize)
+        .with_stdin(Stdio::null())
+        .output()
+        .expect("spawn command for expected")
+        .pipe(stdout_text);
+    eprintln!("EXPECTED:\n{expected}\n");
+
+    assert_eq!(actual, expected);
+}
+
+/// Count the entries other than directories in a subtree and find their newest modification time.
+#[cfg(unix)]
+fn expected_subtree_stats(path: &Path) -> (u64, u64) {
+    use std::time::UNIX_EPOCH;
+    let stats = fs::symlink_metadata(path).expect("read metadata");
+    let mtime = stats
+        .modified()
+        .expect("read mtime")
+        .duration_since(UNIX_EPOCH)
+        .expect("mtime is after the epoch")
+        .as_secs();
+    if !stats.is_dir() {
+        return (1, mtime);
+    }
+    fs::read_dir(path)
+        .expect("read directory")
+        .map(|entry| entry.expect("read entry").path())
+        .map(|path| expected_subtree_stats(&path))
+        .fold((0, mtime), |(count, mtime), (child_count, child_mtime)| {
+            (count + child_count, mtime.max(child_mtime))
+        })
+}
+
+/// Verify the metadata of every node in a tree against the filesystem.
+#[cfg(unix)]
+fn assert_node_metadata(path: &Path, node: &DataTreeReflection<OsStringDisplay, Bytes>) {
+    use parallel_disk_usage::device::DeviceNumber;
+    use parallel_disk_usage::inode::InodeNumber;
+    use parallel_disk_usage::node_metadata::{EntryType, NodeMetadata};
+    use std::os::unix::fs::MetadataExt;
+    let stats = fs::symlink_metadata(path).expect("read metadata");
+    let (count, mtime) = expected_subtree_stats(path);
+    let expected = NodeMetadata {
+        entry_type: Some(stats.file_type().into()),
+        count: Some(count),
+        mtime: Some(mtime),
+        uid: Some(stats.uid()),
+        gid: Some(stats.gid()),
+        inode: Some(InodeNumber::get(&stats)),
+        device: Some(DeviceNumber::get(&stats)),
+        aggregated_entries: None,
+    };
+    let actual = node.metadata.as_deref().expect("node has metadata");
+    assert_eq!(actual, &expected, "metadata of {path:?}");
+    if stats.is_dir() {
+        assert_eq!(actual.entry_type, Some(EntryType::Dir));
+    }
+    for child in &node.children {
+        assert_node_metadata(&path.join(child.name.as_os_str()), child);
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn json_output_json_fields() {
+    let workspace = SampleWorkspace::default();
+    let tree = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-output")
+        .with_arg("--json-fields=type,count,mtime,owner,inode")
+        .with_arg("--quantity=apparent-size")
+        .with_arg("--min-ratio=0")
+        .with_arg(&workspace)
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+        .pipe_as_ref(serde_json::from_str::<JsonData>)
+        .expect("parse stdout as JsonData")
+        .body
+        .pipe(TryInto::<SampleJsonTree>::try_into)
+        .expect("extract reflection")
+        .tree;
+    dbg!(&tree);
+    assert_node_metadata(&workspace, &tree);
+}
+
+#[cfg(unix)]
+#[test]
+fn json_fields_beyond_max_depth() {
+    let workspace = SampleWorkspace::default();
+    let tree = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-output")
+        .with_arg("--json-fields=count,mtime")
+        .with_arg("--max-depth=1")
+        .with_arg(&workspace)
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+        .pipe_as_ref(serde_json::from_str::<JsonData>)
+        .expect("parse stdout as JsonData")
+        .body
+        .pipe(TryInto::<SampleJsonTree>::try_into)
+        .expect("extract reflection")
+        .tree;
+    dbg!(&tree);
+    assert_eq!(tree.children, Vec::new());
+    let (count, mtime) = expected_subtree_stats(&workspace);
+    let metadata = tree.metadata.expect("root has metadata");
+    assert_eq!(metadata.count, Some(count));
+    assert_eq!(metadata.mtime, Some(mtime));
+    assert_eq!(metadata.entry_type, None);
+    assert_eq!(metadata.uid, None);
+    assert_eq!(metadata.inode, None);
+}
+
+#[test]
+fn json_output_without_json_fields() {
+    let workspace = SampleWorkspace::default();
+    let json = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-output")
+        .with_arg("--min-ratio=0")
+        .with_arg(&workspace)
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+        .pipe_as_ref(serde_json::from_str::<serde_json::Value>)
+        .expect("parse stdout as JSON");
+    let tree = &json["tree"];
+    assert_eq!(tree["name"], workspace.to_str().expect("UTF-8 path"));
+    let mut keys: Vec<_> = tree
+        .as_object()
+        .expect("get the fields of the root")
+        .keys()
+        .collect();
+    keys.sort();
+    assert_eq!(keys, ["children", "name", "size"]);
+}
+
+#[test]
+fn json_fields_are_nested_in_metadata() {
+    let workspace = SampleWorkspace::default();
+    let json = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-output")
+        .with_arg("--json-fields=count")
+        .with_arg("--min-ratio=0")
+        .with_arg(&workspace)
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+        .pipe_as_ref(serde_json::from_str::<serde_json::Value>)
+        .expect("parse stdout as JSON");
+    let tree = &json["tree"];
+    assert_eq!(tree.get("count"), None);
+    assert_eq!(tree["metadata"]["count"], 5);
+}
+
+/// Scan the sample workspace and return the JSON output.
+fn scan_json_output(workspace: &Path, quantity: &str) -> String {
+    Command::new(PDU)
+        .with_current_dir(workspace)
+        .with_arg("--json-output")
+        .with_arg("--scan-header")
+        .with_arg(format!("--quantity={quantity}"))
+        .with_arg("--max-depth=inf")
+        .with_arg("--min-ratio=0")
+        .with_arg(workspace)
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+}
+
+#[test]
+fn json_output_header() {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    let workspace = SampleWorkspace::default();
+    let header = scan_json_output(&workspace, "apparent-size")
+        .pipe_as_ref(serde_json::from_str::<JsonData>)
+        .expect("parse stdout as JsonData")
+        .header
+        .expect("get header");
+    dbg!(&header);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("get current time")
+        .as_secs();
+    assert!(header.started_at <= header.finished_at);
+    assert!(header.finished_at <= now);
+    assert_eq!(header.roots.len(), 1);
+    let root = &header.roots[0];
+    let canonical_root = workspace.canonicalize().expect("canonicalize workspace");
+    assert_eq!(root.path, OsStringDisplay::os_string_from(canonical_root));
+    #[cfg(unix)]
+    assert_eq!(root.device, Some(read_device_number(&workspace)));
+    let settings = &header.settings;
+    assert_eq!(settings.quantity, ScanQuantity::ApparentSize);
+    assert_op_expr!(settings.threads, >=, 1);
+    assert_eq!(settings.device_boundary, DeviceBoundary::Cross);
+    assert!(!settings.deduplicate_hardlinks);
+    assert_eq!(settings.max_depth, None);
+    assert_eq!(settings.min_ratio, 0.0);
+}
+
+#[test]
+fn json_output_without_header() {
+    let workspace = SampleWorkspace::default();
+    let json = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-output")
+        .with_arg("--quantity=apparent-size")
+        .with_arg(&workspace)
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+        .pipe_as_ref(serde_json::from_str::<serde_json::Value>)
+        .expect("parse stdout as JSON");
+    assert_eq!(json.get("header"), None);
+}
+
+#[test]
+fn json_input_json_output_keeps_header() {
+    let workspace = SampleWorkspace::default();
+    let json = scan_json_output(&workspace, "apparent-size");
+    let snapshot = Temp::new_dir().expect("create temporary directory");
+    fs::write(snapshot.join("snapshot.json"), &json).expect("write JSON file");
+    let header = |json: &str| {
+        serde_json::from_str::<JsonData>(json)
+            .expect("parse JSON")
+            .header
+            .expect("get header")
+    };
+    let actual = Command::new(PDU)
+        .with_current_dir(&snapshot)
+        .with_arg("--json-input")
+        .with_arg("--json-output")
+        .with_arg("snapshot.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+        .pipe_as_ref(header);
+    assert_eq!(actual, header(&json));
+}
+
+#[test]
+fn json_input_multiple_files_incomparable() {
+    let workspace = SampleWorkspace::default();
+    let snapshot = Temp::new_dir().expect("create temporary directory");
+    fs::write(
+        snapshot.join("first.json"),
+        scan_json_output(&workspace, "apparent-size"),
+    )
+    .expect("write JSON file");
+    let mut second = scan_json_output(&workspace, "apparent-size")
+        .pipe_as_ref(serde_json::from_str::<JsonData>)
+        .expect("parse JSON");
+    second
+        .header
+        .as_mut()
+        .expect("get header")
+        .settings
+        .device_boundary = DeviceBoundary::Stay;
+    fs::write(
+        snapshot.join("second.json"),
+        serde_json::to_string(&second).expect("serialize JSON"),
+    )
+    .expect("write JSON file");
+
+    let output = Command::new(PDU)
+        .with_current_dir(&snapshot)
+        .with_arg("--json-input")
+        .with_arg("first.json")
+        .with_arg("second.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command");
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    let stderr = stderr.trim_end();
+    assert_eq!(output.status.code(), Some(13));
+    assert_eq!(
+        stderr,
+        r#"[error] IncomparableSnapshots: "first.json" and "second.json" were scanned with different device-boundary"#,
+    );
+}
+
 #[test]
 fn json_input() {
     let json_tree = JsonTree {
@@ -157,6 +519,7 @@ fn json_input() {
     let json_data = JsonData {
         schema_version: SchemaVersion,
         binary_version: None,
+        header: None,
         body: json_tree.into(),
     };
     let json = serde_json::to_string_pretty(&json_data).expect("convert sample tree to JSON");
@@ -310,3 +673,393 @@ fn json_output_json_input() {
         .expect("wait for the command with --json-output to terminate");
     assert!(json_output_status.success());
 }
+
+/// Serialize a tree into a JSON file.
+fn write_json_file(path: &Path, tree: SampleTree) {
+    let json_data = JsonData {
+        schema_version: SchemaVersion,
+        binary_version: None,
+        header: None,
+        body: JsonTree {
+            tree: tree.into_reflection(),
+            shared: Default::default(),
+        }
+        .into(),
+    };
+    let json = serde_json::to_string_pretty(&json_data).expect("convert sample tree to JSON");
+    fs::write(path, json).expect("write JSON file");
+}
+
+#[test]
+fn json_input_multiple_files() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    write_json_file(&workspace.join("first.json"), sample_tree());
+    write_json_file(&workspace.join("second.json"), ascending_sample_tree());
+
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-input")
+        .with_arg("--bytes-format=metric")
+        .with_arg("--total-width=100")
+        .with_arg("--max-depth=10")
+        .with_arg("--min-ratio=0")
+        .with_arg("first.json")
+        .with_arg("second.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text);
+    let actual = actual.trim_end();
+    eprintln!("ACTUAL:\n{actual}\n");
+
+    let label = |mut tree: SampleTree, name: &str| {
+        *tree.name_mut() = OsStringDisplay::os_string_from(name);
+        tree
+    };
+    let data_tree = SampleTree::dir(
+        OsStringDisplay::os_string_from("(total)"),
+        0.into(),
+        vec![
+            label(sample_tree(), "first.json"),
+            label(ascending_sample_tree(), "second.json"),
+        ],
+    )
+    .into_par_sorted(|left, right| left.size().cmp(&right.size()).reverse());
+    let visualizer = Visualizer {
+        data_tree: &data_tree,
+        bytes_format: BytesFormat::MetricUnits,
+        direction: Direction::BottomUp,
+        bar_alignment: BarAlignment::Left,
+        column_width_distribution: ColumnWidthDistribution::total(100),
+    };
+    let expected = format!("{visualizer}");
+    let expected = expected.trim_end();
+    eprintln!("EXPECTED:\n{expected}\n");
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn json_input_multiple_files_json_output() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    write_json_file(&workspace.join("first.json"), sample_tree());
+    write_json_file(&workspace.join("second.json"), ascending_sample_tree());
+
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-input")
+        .with_arg("--json-output")
+        .with_arg("first.json")
+        .with_arg("second.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+        .pipe_as_ref(serde_json::from_str::<JsonData>)
+        .expect("parse stdout as JsonData")
+        .body
+        .pipe(TryInto::<SampleJsonTree>::try_into)
+        .expect("extract reflection")
+        .tree;
+    dbg!(&actual);
+
+    let first = sample_tree().into_reflection();
+    let second = ascending_sample_tree().into_reflection();
+    assert_eq!(actual.name, OsStringDisplay::os_string_from("(total)"));
+    assert_eq!(actual.size, first.size + second.size);
+    assert_eq!(actual.children.len(), 2);
+    assert_eq!(
+        actual.children[0].name,
+        OsStringDisplay::os_string_from("first.json")
+    );
+    assert_eq!(actual.children[0].children, first.children);
+    assert_eq!(
+        actual.children[1].name,
+        OsStringDisplay::os_string_from("second.json")
+    );
+    assert_eq!(actual.children[1].children, second.children);
+}
+
+#[test]
+fn json_input_schema_versions() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    write_json_file(&workspace.join("sample.json"), sample_tree());
+    let run = |schema_version: &str| {
+        let path = workspace.join("sample.json");
+        let mut json: serde_json::Value = fs::read_to_string(&path)
+            .expect("read JSON file")
+            .pipe_as_ref(serde_json::from_str)
+            .expect("parse JSON file");
+        json["schema-version"] = schema_version.into();
+        let versioned = workspace.join(format!("{schema_version}.json"));
+        fs::write(&versioned, json.to_string()).expect("write JSON file");
+        Command::new(PDU)
+            .with_current_dir(&workspace)
+            .with_arg("--json-input")
+            .with_arg(&versioned)
+            .with_stdin(Stdio::null())
+            .with_stdout(Stdio::piped())
+            .with_stderr(Stdio::piped())
+            .output()
+            .expect("spawn command")
+    };
+
+    // Names in documents of the previous schema are always strings, so they remain readable.
+    let previous = run("2026-04-02");
+    inspect_stderr(&previous.stderr);
+    assert!(previous.status.success(), "status: {:?}", previous.status);
+
+    let unknown = run("2000-01-01");
+    let stderr = String::from_utf8(unknown.stderr).expect("parse stderr as UTF-8");
+    assert_eq!(unknown.status.code(), Some(3));
+    assert!(
+        stderr.contains("InvalidSchema"),
+        "unexpected stderr {stderr:?}"
+    );
+}
+
+#[test]
+fn json_input_multiple_files_unit_mismatch() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    write_json_file(&workspace.join("bytes.json"), sample_tree());
+    let blocks_data = JsonData {
+        schema_version: SchemaVersion,
+        binary_version: None,
+        header: None,
+        body: JsonTree {
+            tree: DataTree::<SampleName, Blocks>::file(
+                OsStringDisplay::os_string_from("root"),
+                Blocks::new(8),
+            )
+            .into_reflection(),
+            shared: Default::default(),
+        }
+        .into(),
+    };
+    let json = serde_json::to_string_pretty(&blocks_data).expect("convert blocks tree to JSON");
+    fs::write(workspace.join("blocks.json"), json).expect("write JSON file");
+
+    let output = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-input")
+        .with_arg("bytes.json")
+        .with_arg("blocks.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command");
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    assert_eq!(output.status.code(), Some(8));
+    assert_eq!(
+        stderr.trim_end(),
+        "[error] UnitMismatch: Data in bytes cannot be combined with data in blocks",
+    );
+}
+
+#[test]
+fn json_input_multiple_files_shared_details() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    // The snapshots come from different hosts that happen to reuse the same inode number
+    // and device number for unrelated files.
+    let write_snapshot = |file_name: &str, root: &str, link: &str, size: u64, links: u64| {
+        let tree = SampleTree::dir(
+            OsStringDisplay::os_string_from(root),
+            0.into(),
+            vec![SampleTree::file(
+                OsStringDisplay::os_string_from(link),
+                size.into(),
+            )],
+        );
+        let json_data = JsonData {
+            schema_version: SchemaVersion,
+            binary_version: None,
+            header: None,
+            body: JsonTree {
+                tree: tree.into_reflection(),
+                shared: Default::default(),
+            }
+            .into(),
+        };
+        let mut json = serde_json::to_value(&json_data).expect("convert sample tree to JSON");
+        json["shared"] = serde_json::json!({
+            "details": [{
+                "ino": 7,
+                "dev": 1,
+                "size": size,
+                "links": links,
+                "paths": [format!("{root}/{link}")],
+            }],
+        });
+        let json = serde_json::to_string_pretty(&json).expect("serialize JSON");
+        fs::write(workspace.join(file_name), json).expect("write JSON file");
+    };
+    write_snapshot("first.json", "/mnt/a", "x", 100, 2);
+    write_snapshot("second.json", "/mnt/b", "y", 200, 3);
+
+    let output = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-input")
+        .with_arg("--json-output")
+        .with_arg("first.json")
+        .with_arg("second.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command");
+    inspect_stderr(&output.stderr);
+    assert!(output.status.success(), "status: {:?}", output.status);
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout_text(output)).expect("parse stdout as JSON");
+    assert_eq!(json["shared"]["details"], serde_json::Value::Null);
+    let sections: Vec<_> = json["shared"]["sections"]
+        .as_array()
+        .expect("get the sections")
+        .iter()
+        .map(|section| {
+            let details: HardlinkListReflection<Bytes> =
+                serde_json::from_value(section["details"].clone()).expect("parse the details");
+            HardlinkList::try_from(details.clone()).expect("convert the details into a list");
+            let details: Vec<_> = details
+                .iter()
+                .map(|entry| (entry.size, entry.paths.clone()))
+                .collect();
+            (section["root"].clone(), details)
+        })
+        .collect();
+    let path = |path: &str| Path::new(path).to_path_buf();
+    let paths = |path: PathBuf| LinkPathListReflection(HashSet::from([path]));
+    let expected = [
+        (
+            serde_json::json!("first.json"),
+            vec![(Bytes::new(100), paths(path("first.json/x")))],
+        ),
+        (
+            serde_json::json!("second.json"),
+            vec![(Bytes::new(200), paths(path("second.json/y")))],
+        ),
+    ];
+    assert_eq!(sections, expected);
+}
+
+#[test]
+fn json_input_multiple_files_repeated() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let json_data = JsonData {
+        schema_version: SchemaVersion,
+        binary_version: None,
+        header: None,
+        body: JsonTree {
+            tree: sample_tree().into_reflection(),
+            shared: Default::default(),
+        }
+        .into(),
+    };
+    let json = serde_json::to_string_pretty(&json_data).expect("convert sample tree to JSON");
+    fs::write(workspace.join("tree.json"), json).expect("write JSON file");
+    let output = run_pdu(&workspace, &["--json-input", "tree.json", "tree.json"]);
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    assert_eq!(output.status.code(), Some(9));
+    assert_eq!(
+        stderr.trim_end(),
+        r#"[error] MergeConflict: "tree.json" is given more than once"#,
+    );
+}
+
+/// Run `pdu --json-input` on files within a workspace and return its trimmed stdout.
+fn run_json_input_files(workspace: &Path, files: &[&str]) -> String {
+    Command::new(PDU)
+        .with_current_dir(workspace)
+        .with_arg("--json-input")
+        .with_arg("--bytes-format=metric")
+        .with_arg("--total-width=100")
+        .with_arg("--max-depth=10")
+        .with_arg("--min-ratio=0")
+        .with_args(files)
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+        .trim_end()
+        .to_string()
+}
+
+#[test]
+fn json_input_compressed_files() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    write_json_file(&workspace.join("plain.json"), sample_tree());
+    let json = fs::read(workspace.join("plain.json")).expect("read JSON file");
+
+    let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+    gzip.write_all(&json).expect("compress JSON with gzip");
+    let gzip = gzip.finish().expect("finish gzip stream");
+    fs::write(workspace.join("compressed.json.gz"), gzip).expect("write gzip file");
+
+    let zstd = zstd::encode_all(json.as_slice(), 0).expect("compress JSON with zstd");
+    fs::write(workspace.join("compressed.json.zst"), zstd).expect("write zstd file");
+
+    let expected = run_json_input_files(&workspace, &["plain.json"]);
+    eprintln!("EXPECTED:\n{expected}\n");
+    assert!(expected.contains("root"));
+
+    let actual = run_json_input_files(&workspace, &["compressed.json.gz"]);
+    eprintln!("GZIP:\n{actual}\n");
+    assert_eq!(actual, expected);
+
+    let actual = run_json_input_files(&workspace, &["compressed.json.zst"]);
+    eprintln!("ZSTD:\n{actual}\n");
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn json_input_dash_means_stdin() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    write_json_file(&workspace.join("first.json"), sample_tree());
+    write_json_file(&workspace.join("second.json"), ascending_sample_tree());
+
+    let stdin = File::open(workspace.join("first.json")).expect("open JSON file");
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-input")
+        .with_arg("--bytes-format=metric")
+        .with_arg("--total-width=100")
+        .with_arg("--max-depth=10")
+        .with_arg("--min-ratio=0")
+        .with_arg("-")
+        .with_arg("second.json")
+        .with_stdin(stdin.into())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text);
+    let actual = actual.trim_end();
+    eprintln!("ACTUAL:\n{actual}\n");
+
+    let expected = run_json_input_files(&workspace, &["first.json", "second.json"]);
+    let expected = expected.replace("first.json", "-         ");
+    eprintln!("EXPECTED:\n{expected}\n");
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn json_input_dash_at_most_once() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    for args in [&["--json-input", "-", "-"], &["--diff", "-", "-"]] {
+        let output = run_pdu(&workspace, args);
+        let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(
+            stderr.contains("- (stdin) cannot be given more than once"),
+            "unexpected stderr {stderr:?}",
+        );
+    }
+}
@@ -0,0 +1,268 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use command_extra::CommandExtra;
+use parallel_disk_usage::data_tree::DataTree;
+use parallel_disk_usage::device::DeviceBoundary;
+use parallel_disk_usage::fs_tree_builder::{Extras, FsTreeBuilder};
+use parallel_disk_usage::get_size::GetApparentSize;
+use parallel_disk_usage::hardlink::HardlinkIgnorant;
+use parallel_disk_usage::json_data::{JsonData, JsonTree, SchemaVersion};
+use parallel_disk_usage::json_lines::JsonLine;
+use parallel_disk_usage::node_metadata::{EntryType, MetadataFields};
+use parallel_disk_usage::os_string_display::OsStringDisplay;
+use parallel_disk_usage::reporter::{ErrorOnlyReporter, ErrorReport};
+use parallel_disk_usage::size::Bytes;
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use std::convert::Infallible;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+type SampleLine = JsonLine<Bytes>;
+type SampleTree = DataTree<OsStringDisplay, Bytes>;
+
+/// Parse every line of the stdout of `pdu --output-format=jsonl`.
+fn parse_json_lines(stdout: &str) -> Vec<SampleLine> {
+    stdout
+        .lines()
+        .map(serde_json::from_str::<SampleLine>)
+        .collect::<Result<_, _>>()
+        .expect("parse each line of stdout as JsonLine")
+}
+
+/// Find the line whose path is `path`.
+fn find_line<'a>(lines: &'a [SampleLine], path: &str) -> &'a SampleLine {
+    lines
+        .iter()
+        .find(|line| line.path.as_os_str() == path)
+        .unwrap_or_else(|| panic!("find line of {path:?}"))
+}
+
+/// Sort the lines by their paths, since the streamed lines of siblings are in no particular order.
+fn sort_by_path(mut lines: Vec<SampleLine>) -> Vec<SampleLine> {
+    lines.sort_by(|left, right| left.path.cmp(&right.path));
+    lines
+}
+
+/// Assert that the parent of every line appears after the line itself.
+fn assert_children_before_parents(lines: &[SampleLine]) {
+    for (index, line) in lines.iter().enumerate() {
+        let Some(parent) = &line.parent else {
+            continue;
+        };
+        assert!(
+            lines[index + 1..].iter().any(|other| &other.path == parent),
+            "expecting {parent:?} to appear after {:?}",
+            line.path,
+        );
+    }
+}
+
+#[test]
+fn json_lines_output() {
+    let workspace = SampleWorkspace::default();
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--output-format=jsonl")
+        .with_arg("--quantity=apparent-size")
+        .with_arg("--min-ratio=0")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+        .pipe_as_ref(parse_json_lines);
+    dbg!(&actual);
+
+    let builder = FsTreeBuilder {
+        root: workspace.join("."),
+        size_getter: GetApparentSize,
+        hardlinks_recorder: &HardlinkIgnorant,
+        reporter: &ErrorOnlyReporter::new(ErrorReport::SILENT),
+        device_boundary: DeviceBoundary::Cross,
+        max_depth: 10,
+    };
+    let extras = Extras::default().with_metadata_fields(MetadataFields {
+        entry_type: true,
+        ..MetadataFields::NONE
+    });
+    let mut expected_tree: SampleTree = builder.build_with(extras);
+    *expected_tree.name_mut() = OsStringDisplay::os_string_from(".");
+    let mut expected = Vec::new();
+    JsonLine::visit(&expected_tree, false, &mut |line| {
+        expected.push(line);
+        Ok::<_, Infallible>(())
+    })
+    .expect("visit every node");
+
+    assert_children_before_parents(&actual);
+    assert_eq!(actual.clone().pipe(sort_by_path), sort_by_path(expected));
+
+    let root = actual.last().expect("get the last line");
+    assert_eq!(root.path.as_os_str(), ".");
+    assert_eq!(root.parent, None);
+    assert_eq!(root.depth, 0);
+    assert_eq!(root.kind, Some(EntryType::Dir));
+
+    let nested_file = find_line(&actual, "./nested/0/1");
+    assert_eq!(
+        nested_file.parent,
+        Some(OsStringDisplay::os_string_from("./nested/0")),
+    );
+    assert_eq!(nested_file.depth, 3);
+    assert_eq!(nested_file.size, Bytes::new(500_000));
+    assert_eq!(nested_file.kind, Some(EntryType::File));
+}
+
+#[test]
+fn json_lines_output_zstd() {
+    let workspace = SampleWorkspace::default();
+    let command = |zstd: bool| {
+        Command::new(PDU)
+            .with_current_dir(&workspace)
+            .with_arg("--output-format=jsonl")
+            .with_arg("--quantity=apparent-size")
+            .with_arg("--min-ratio=0")
+            .with_arg("--max-depth=2")
+            .pipe(|command| {
+                if zstd {
+                    command.with_arg("--zstd")
+                } else {
+                    command
+                }
+            })
+            .with_stdin(Stdio::null())
+            .with_stdout(Stdio::piped())
+            .with_stderr(Stdio::piped())
+            .output()
+            .expect("spawn command")
+    };
+    let compressed = command(true);
+    assert!(
+        compressed.status.success(),
+        "status: {:?}",
+        compressed.status
+    );
+    let decompressed =
+        zstd::decode_all(compressed.stdout.as_slice()).expect("decompress the output with zstd");
+    let actual = String::from_utf8(decompressed)
+        .expect("parse the decompressed output as UTF-8")
+        .pipe_as_ref(parse_json_lines);
+    dbg!(&actual);
+    assert_children_before_parents(&actual);
+    let expected = command(false)
+        .pipe(stdout_text)
+        .pipe_as_ref(parse_json_lines);
+    assert_eq!(sort_by_path(actual), sort_by_path(expected));
+}
+
+#[test]
+fn json_lines_output_max_depth_min_ratio() {
+    let workspace = SampleWorkspace::default();
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--output-format=jsonl")
+        .with_arg("--quantity=apparent-size")
+        .with_arg("--max-depth=2")
+        .with_arg("--min-ratio=0.1")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+        .pipe_as_ref(parse_json_lines);
+    dbg!(&actual);
+
+    let mut paths: Vec<_> = actual.iter().map(|line| line.path.to_string()).collect();
+    paths.sort();
+    assert_eq!(paths, [".", "./flat", "./nested"]);
+    assert_children_before_parents(&actual);
+}
+
+#[test]
+fn json_lines_json_input_multiple_files() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let write_snapshot = |path: &Path, size: u64| {
+        let tree = SampleTree::dir(
+            OsStringDisplay::os_string_from("root"),
+            Bytes::new(1000),
+            vec![SampleTree::file(
+                OsStringDisplay::os_string_from("file"),
+                Bytes::new(size),
+            )],
+        );
+        let json_data = JsonData {
+            schema_version: SchemaVersion,
+            binary_version: None,
+            header: None,
+            body: JsonTree {
+                tree: tree.into_reflection(),
+                shared: Default::default(),
+            }
+            .into(),
+        };
+        let json = serde_json::to_string(&json_data).expect("convert sample tree to JSON");
+        fs::write(path, json).expect("write JSON snapshot");
+    };
+    write_snapshot(&workspace.join("a.json"), 3000);
+    write_snapshot(&workspace.join("b.json"), 5000);
+
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-input")
+        .with_arg("--output-format=jsonl")
+        .with_arg("--min-ratio=0")
+        .with_arg("a.json")
+        .with_arg("b.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text)
+        .pipe_as_ref(parse_json_lines);
+    dbg!(&actual);
+
+    let line = |path: &str, parent: Option<&str>, depth, size| SampleLine {
+        path: OsStringDisplay::os_string_from(path),
+        parent: parent.map(OsStringDisplay::os_string_from),
+        depth,
+        size: Bytes::new(size),
+        kind: None,
+        metadata: Default::default(),
+    };
+    let expected = [
+        line("b.json/file", Some("b.json"), 1, 5000),
+        line("b.json", None, 0, 6000),
+        line("a.json/file", Some("a.json"), 1, 3000),
+        line("a.json", None, 0, 4000),
+    ];
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn json_lines_output_of_diff_is_unsupported() {
+    let output = Command::new(PDU)
+        .with_arg("--diff")
+        .with_arg("old.json")
+        .with_arg("new.json")
+        .with_arg("--output-format=jsonl")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command");
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.contains("--output-format=jsonl cannot be used with --diff"),
+        "stderr: {stderr}",
+    );
+}
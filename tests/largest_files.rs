@@ -0,0 +1,151 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+
+#[test]
+fn largest_files_only() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--bytes-format=plain",
+            "--largest-files=3",
+            "--largest-files-only",
+            "flat",
+            "nested",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    let expected = [
+        format!("1. 500000 {}", native_path("nested/0/1")),
+        format!("2. 300000 {}", native_path("flat/3")),
+        format!("3. 200000 {}", native_path("flat/2")),
+    ];
+    assert_eq!(actual.lines().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn largest_files_after_chart() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--bytes-format=plain",
+            "--total-width=100",
+            "--largest-files=2",
+            "flat",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    let (chart, list) = actual
+        .split_once("\n\n")
+        .expect("split the chart and the list");
+    assert!(chart.ends_with("│100%"), "unexpected chart {chart:?}");
+    let expected = [
+        format!("1. 300000 {}", native_path("flat/3")),
+        format!("2. 200000 {}", native_path("flat/2")),
+    ];
+    assert_eq!(list.lines().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn largest_files_more_than_files() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--largest-files=100",
+            "--largest-files-only",
+            "flat",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(actual.lines().count(), 4);
+}
+
+#[test]
+fn json_output() {
+    let workspace = SampleWorkspace::default();
+    let json = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--scan-header",
+            "--largest-files=2",
+            "--largest-files-only",
+            "--json-output",
+            "flat",
+            "nested",
+        ],
+    );
+    let json: Value = serde_json::from_str(&json).expect("parse JSON");
+    let tree = &json["tree"];
+    assert_eq!(tree["name"], "(total)");
+    assert_eq!(tree["size"], 800_000);
+    let children: Vec<_> = tree["children"]
+        .as_array()
+        .expect("get the children")
+        .iter()
+        .map(|child| {
+            (
+                child["name"].as_str().expect("get the name"),
+                child["size"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        children,
+        [
+            (native_path("nested/0/1").as_str(), Value::from(500_000)),
+            (native_path("flat/3").as_str(), Value::from(300_000)),
+        ],
+    );
+    assert_eq!(json["header"]["settings"]["largest-files"], 2);
+}
+
+#[test]
+fn largest_files_only_conflicts_with_deduplicate_hardlinks() {
+    let workspace = SampleWorkspace::default();
+    let output = run_pdu(
+        &workspace,
+        &[
+            "--largest-files=2",
+            "--largest-files-only",
+            "--deduplicate-hardlinks",
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("'--largest-files-only' cannot be used with '--deduplicate-hardlinks'"),
+        "unexpected {stderr:?}",
+    );
+}
+
+#[test]
+fn list_conflicts_with_output_formats() {
+    let workspace = SampleWorkspace::default();
+    for (flag, expected) in [
+        (
+            "--json-output",
+            "--largest-files cannot be used with --json-output without --largest-files-only",
+        ),
+        (
+            "--output-format=csv",
+            "--largest-files cannot be used with --output-format=csv without --largest-files-only",
+        ),
+    ] {
+        let output = run_pdu(&workspace, &["--largest-files=2", flag, "flat"]);
+        assert_eq!(output.status.code(), Some(2));
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(stderr.contains(expected), "unexpected {stderr:?}");
+    }
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use pretty_assertions::assert_eq;
+
+#[test]
+fn max_children() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--max-children=1",
+            "flat",
+            "nested",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(chart_names(&actual), ["3", "flat", "(total)"]);
+}
+
+#[test]
+fn max_rows() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--max-rows=4",
+            "flat",
+            "nested",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(chart_names(&actual), ["0", "nested", "flat", "(total)"]);
+}
+
+#[test]
+fn max_children_and_max_rows() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--max-children=2",
+            "--max-rows=3",
+            "flat",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(chart_names(&actual), ["2", "3", "flat"]);
+}
+
+#[test]
+fn json_input() {
+    let workspace = SampleWorkspace::default();
+    let json = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--min-ratio=0",
+            "--json-output",
+            "flat",
+        ],
+    );
+    let json_file = workspace.join("flat.json");
+    std::fs::write(&json_file, json).expect("write JSON file");
+    let actual = pdu(
+        &workspace,
+        &[
+            "--json-input",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--max-children=2",
+            "flat.json",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(chart_names(&actual), ["2", "3", "flat"]);
+}
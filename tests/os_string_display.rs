@@ -109,3 +109,89 @@ fn non_utf8_os_string_display() {
     let expected = r#""\xFF\xDD""#;
     assert_eq!(actual, expected);
 }
+
+#[cfg(feature = "json")]
+#[test]
+fn serialize_utf8_name() {
+    let actual = "abc"
+        .pipe(OsStringDisplay::os_string_from)
+        .pipe_ref(serde_json::to_string)
+        .expect("serialize name as JSON");
+    let expected = r#""abc""#;
+    assert_eq!(actual, expected);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn serialize_non_utf8_name() {
+    let actual = vec![0xFF, 0xDD]
+        .pipe(OsString::from_vec)
+        .pipe(OsStringDisplay)
+        .pipe_ref(serde_json::to_string)
+        .expect("serialize name as JSON");
+    let expected = r#"{"bytes":[255,221]}"#;
+    assert_eq!(actual, expected);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn deserialize_utf8_name() {
+    let actual: OsStringDisplay =
+        serde_json::from_str(r#""abc""#).expect("deserialize name from JSON");
+    let expected = OsStringDisplay::os_string_from("abc");
+    assert_eq!(actual, expected);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn deserialize_non_utf8_name() {
+    let actual: OsStringDisplay =
+        serde_json::from_str(r#"{"bytes":[255,221]}"#).expect("deserialize name from JSON");
+    let expected = vec![0xFF, 0xDD]
+        .pipe(OsString::from_vec)
+        .pipe(OsStringDisplay);
+    assert_eq!(actual, expected);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn deserialize_path_buf_name() {
+    let actual: OsStringDisplay<PathBuf> =
+        serde_json::from_str(r#"{"bytes":[255,221]}"#).expect("deserialize name from JSON");
+    let expected = vec![0xFF, 0xDD]
+        .pipe(OsString::from_vec)
+        .pipe(PathBuf::from)
+        .pipe(OsStringDisplay);
+    assert_eq!(actual, expected);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn deserialize_foreign_wide_name() {
+    let actual: OsStringDisplay =
+        serde_json::from_str(r#"{"wide":[97,98,99]}"#).expect("deserialize name from JSON");
+    let expected = OsStringDisplay::os_string_from("abc");
+    assert_eq!(actual, expected);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn deserialize_name_with_unknown_key() {
+    let error = serde_json::from_str::<OsStringDisplay>(r#"{"raw":[255,221]}"#)
+        .expect_err("unknown key should be rejected");
+    let message = error.to_string();
+    assert!(message.contains("unknown field `raw`"), "{message}");
+}
+
+#[cfg(feature = "cli")]
+#[test]
+fn cbor_round_trip_non_utf8_name() {
+    let name = vec![0xFF, 0xDD]
+        .pipe(OsString::from_vec)
+        .pipe(OsStringDisplay);
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&name, &mut cbor).expect("serialize name as CBOR");
+    let actual: OsStringDisplay =
+        ciborium::from_reader(cbor.as_slice()).expect("deserialize name from CBOR");
+    assert_eq!(actual, name);
+}
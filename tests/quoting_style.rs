@@ -0,0 +1,144 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use parallel_disk_usage::visualizer::QuotingStyle;
+use pretty_assertions::assert_eq;
+use std::fs::{create_dir, write};
+use zero_copy_pads::UnicodeWidthStr;
+
+#[test]
+fn quote_literal() {
+    let quote = |name| QuotingStyle::Literal.quote(name).into_owned();
+    assert_eq!(quote("abc"), "abc");
+    assert_eq!(quote("a\nb"), "a\nb");
+    assert_eq!(quote(""), "");
+}
+
+#[test]
+fn quote_escape() {
+    let quote = |name| QuotingStyle::Escape.quote(name).into_owned();
+    assert_eq!(quote("abc def.txt"), "abc def.txt");
+    assert_eq!(quote("日本語"), "日本語");
+    assert_eq!(quote("a\nb\tc"), "a\\nb\\tc");
+    assert_eq!(quote("\x1b[31mred"), "\\e[31mred");
+    assert_eq!(quote("\x7f\x01"), "\\x7f\\x01");
+    assert_eq!(quote("abc\u{202e}txt.exe"), "abc\\u{202e}txt.exe");
+    assert_eq!(quote("back\\slash"), "back\\\\slash");
+}
+
+#[test]
+#[cfg(unix)]
+fn quote_non_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    let name = OsStr::from_bytes(b"abc\xff\\\n.txt");
+    let quote = |style: QuotingStyle| style.quote_os_str(name).into_owned();
+    assert_eq!(quote(QuotingStyle::Literal), "abc\u{fffd}\\\n.txt");
+    assert_eq!(quote(QuotingStyle::Escape), "abc\\xff\\\\\\n.txt");
+    assert_eq!(quote(QuotingStyle::Shell), "'abc'$'\\xff''\\'$'\\n''.txt'");
+}
+
+#[test]
+fn quote_shell() {
+    let quote = |name| QuotingStyle::Shell.quote(name).into_owned();
+    assert_eq!(quote("abc-def_1.txt"), "abc-def_1.txt");
+    assert_eq!(quote(""), "''");
+    assert_eq!(quote("~home"), "'~home'");
+    assert_eq!(quote("abc def"), "'abc def'");
+    assert_eq!(quote("it's"), "'it'\\''s'");
+    assert_eq!(quote("a\nb"), "'a'$'\\n''b'");
+    assert_eq!(quote("\x1b[31m"), "$'\\e''[31m'");
+    assert_eq!(quote("abc\u{202e}txt"), "'abc'$'\\u202E''txt'");
+}
+
+#[test]
+#[cfg(unix)] // control characters are not allowed in file names on Windows
+fn escape_names_in_chart() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let root = workspace.join("root");
+    create_dir(&root).expect("create root");
+    write(root.join("evil\x1b[31mred\nline"), "a".repeat(4096)).expect("write file");
+    write(root.join("abc\u{202e}txt.exe"), "a".repeat(2048)).expect("write file");
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=80",
+            "--min-ratio=0",
+            "root",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert!(!actual.contains('\x1b'), "unexpected escape in {actual:?}");
+    assert!(
+        !actual.contains('\u{202e}'),
+        "unexpected override in {actual:?}"
+    );
+    assert!(actual.contains("evil\\e[31mred\\nline"));
+    assert!(actual.contains("abc\\u{202e}txt.exe"));
+    assert_eq!(actual.lines().count(), 3);
+    for line in actual.lines() {
+        assert_eq!(line.width(), 80, "unexpected width of {line:?}");
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn escape_non_utf8_names_in_chart() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let root = workspace.join("root");
+    create_dir(&root).expect("create root");
+    write(root.join(OsStr::from_bytes(b"raw\xff")), "a".repeat(4096)).expect("write file");
+    write(root.join("back\\slash"), "a".repeat(2048)).expect("write file");
+    let chart = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=80",
+            "--min-ratio=0",
+            "root",
+        ],
+    );
+    eprintln!("CHART:\n{chart}\n");
+    assert!(chart.contains("──raw\\xff"));
+    assert!(chart.contains("──back\\\\slash"));
+    let flat = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--min-ratio=0",
+            "--flat",
+            "root",
+        ],
+    );
+    eprintln!("FLAT:\n{flat}\n");
+    assert!(flat.contains("root/raw\\xff"));
+    assert!(flat.contains("root/back\\\\slash"));
+}
+
+#[test]
+fn truncate_wide_names() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let root = workspace.join("root");
+    create_dir(&root).expect("create root");
+    write(root.join("日本語のとても長いファイル名".repeat(4)), "a").expect("write file");
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=60",
+            "--min-ratio=0",
+            "root",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert!(actual.contains("..."));
+    for line in actual.lines() {
+        assert_eq!(line.width(), 60, "unexpected width of {line:?}");
+    }
+}
@@ -0,0 +1,125 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use pretty_assertions::assert_eq;
+
+#[test]
+fn sort_by_size_reversed() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--top-down",
+            "--reverse",
+            "flat",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(chart_names(&actual), ["flat", "0", "1", "2", "3"]);
+}
+
+#[test]
+fn sort_by_name() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--top-down",
+            "--sort=name",
+            "flat",
+            "nested",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(
+        chart_names(&actual),
+        ["(total)", "flat", "0", "1", "2", "3", "nested", "0", "1"],
+    );
+}
+
+#[test]
+fn sort_by_natural_name() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    let root = workspace.join("root");
+    std::fs::create_dir(&root).expect("create root");
+    for name in ["file10", "file9", "file1", "file100"] {
+        std::fs::write(root.join(name), name).expect("write file");
+    }
+    let args = [
+        "--quantity=apparent-size",
+        "--total-width=100",
+        "--min-ratio=0",
+        "--top-down",
+        "root",
+    ];
+    let actual = pdu(&workspace, &[&args[..], &["--sort=natural-name"]].concat());
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(
+        chart_names(&actual),
+        ["root", "file1", "file9", "file10", "file100"],
+    );
+    let actual = pdu(&workspace, &[&args[..], &["--sort=name"]].concat());
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(
+        chart_names(&actual),
+        ["root", "file1", "file10", "file100", "file9"],
+    );
+}
+
+#[test]
+fn sort_by_count() {
+    let workspace = SampleWorkspace::default();
+    let actual = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--top-down",
+            "--sort=count",
+            "flat",
+            "nested",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(chart_names(&actual)[..2], ["(total)", "flat"]);
+}
+
+#[test]
+fn json_input() {
+    let workspace = SampleWorkspace::default();
+    let json = pdu(
+        &workspace,
+        &[
+            "--quantity=apparent-size",
+            "--min-ratio=0",
+            "--json-output",
+            "flat",
+        ],
+    );
+    let json_file = workspace.join("flat.json");
+    std::fs::write(&json_file, json).expect("write JSON file");
+    let actual = pdu(
+        &workspace,
+        &[
+            "--json-input",
+            "--total-width=100",
+            "--min-ratio=0",
+            "--top-down",
+            "--sort=name",
+            "--reverse",
+            "flat.json",
+        ],
+    );
+    eprintln!("ACTUAL:\n{actual}\n");
+    assert_eq!(chart_names(&actual), ["flat", "3", "2", "1", "0"]);
+}
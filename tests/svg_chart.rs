@@ -0,0 +1,155 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use command_extra::CommandExtra;
+use parallel_disk_usage::bytes_format::BytesFormat;
+use parallel_disk_usage::data_tree::DataTree;
+use parallel_disk_usage::os_string_display::OsStringDisplay;
+use parallel_disk_usage::size::Bytes;
+use parallel_disk_usage::svg_chart::{SvgChart, SvgLayout};
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use std::process::{Command, Stdio};
+
+type SampleTree = DataTree<OsStringDisplay, Bytes>;
+
+/// Tree whose directories take no space by themselves, so that the areas are easy to predict.
+fn chart_tree() -> SampleTree {
+    let dir = |name, children| sample_dir(name, 0, children);
+    dir(
+        "root",
+        vec![
+            sample_file("foo", 6000),
+            dir(
+                "bar",
+                vec![sample_file("<b&z>", 1500), sample_file("qux", 500)],
+            ),
+        ],
+    )
+}
+
+fn render(tree: &SampleTree, layout: SvgLayout) -> String {
+    let (width, height) = layout.dimensions();
+    SvgChart {
+        data_tree: tree,
+        layout,
+        bytes_format: BytesFormat::MetricUnits,
+        width,
+        height,
+    }
+    .to_string()
+}
+
+/// Extract the tooltips of an SVG image.
+fn titles(svg: &str) -> Vec<&str> {
+    svg.split("<title>")
+        .skip(1)
+        .map(|rest| rest.split_once("</title>").expect("find end of title").0)
+        .collect()
+}
+
+/// Extract the values of an attribute of every `<rect>` of an SVG image.
+fn rect_attributes(svg: &str, attribute: &str) -> Vec<f64> {
+    svg.split("<rect ")
+        .skip(1)
+        .map(|rest| {
+            let (_, rest) = rest
+                .split_once(&format!(" {attribute}=\""))
+                .expect("find attribute");
+            let (value, _) = rest.split_once('"').expect("find end of attribute");
+            value.parse().expect("parse attribute as a number")
+        })
+        .collect()
+}
+
+#[test]
+fn treemap_areas_are_proportional() {
+    let svg = render(&chart_tree(), SvgLayout::Treemap);
+    eprintln!("SVG:\n{svg}\n");
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(
+        titles(&svg),
+        [
+            "root (8.0K)",
+            "root/foo (6.0K)",
+            "root/bar (2.0K)",
+            "root/bar/&lt;b&amp;z&gt; (1.5K)",
+            "root/bar/qux (500)",
+        ],
+    );
+    assert!(!svg.contains("<b&z>"));
+
+    let widths = rect_attributes(&svg, "width");
+    let heights = rect_attributes(&svg, "height");
+    let area = |index: usize| widths[index] * heights[index];
+    let ratio = area(1) / area(2);
+    assert!(
+        (ratio - 3.0).abs() < 0.01,
+        "foo should be 3 times bar: {ratio}"
+    );
+}
+
+#[test]
+fn icicle_widths_are_proportional() {
+    let svg = render(&chart_tree(), SvgLayout::Icicle);
+    eprintln!("SVG:\n{svg}\n");
+    let widths = rect_attributes(&svg, "width");
+    let heights = rect_attributes(&svg, "height");
+    let ys = rect_attributes(&svg, "y");
+    assert_eq!(widths, [1200.0, 900.0, 300.0, 225.0, 75.0]);
+    assert!(
+        heights
+            .iter()
+            .all(|height| (height - 800.0 / 3.0).abs() < 0.01)
+    );
+    assert_eq!(ys[0], 0.0);
+    assert_eq!(ys[1], ys[2]);
+    assert!(ys[3] > ys[1]);
+}
+
+#[test]
+fn sunburst_full_ring() {
+    let tree = SampleTree::dir(
+        OsStringDisplay::os_string_from("root"),
+        0.into(),
+        vec![SampleTree::file(
+            OsStringDisplay::os_string_from("only"),
+            1000.into(),
+        )],
+    );
+    let svg = render(&tree, SvgLayout::Sunburst);
+    eprintln!("SVG:\n{svg}\n");
+    assert_eq!(titles(&svg), ["root (1.0K)", "root/only (1.0K)"]);
+    assert!(svg.contains("<circle "));
+    assert_eq!(svg.matches("<path ").count(), 1);
+    assert!(!svg.contains("NaN"));
+}
+
+#[test]
+fn svg_output_is_shaped() {
+    let workspace = SampleWorkspace::default();
+    let svg = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--output-format=svg")
+        .with_arg("--svg-layout=icicle")
+        .with_arg("--quantity=apparent-size")
+        .with_arg("--bytes-format=plain")
+        .with_arg("--max-depth=2")
+        .with_arg("--min-ratio=0")
+        .with_arg("nested")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text);
+    eprintln!("SVG:\n{svg}\n");
+    let titles = titles(&svg);
+    assert_eq!(titles.len(), 2);
+    assert!(titles[0].starts_with("nested ("));
+    assert!(titles[1].starts_with("nested/0 ("));
+}
@@ -0,0 +1,242 @@
+#![cfg(feature = "cli")]
+#![cfg(feature = "json")]
+
+pub mod _utils;
+pub use _utils::*;
+
+use command_extra::CommandExtra;
+use parallel_disk_usage::bytes_format::BytesFormat;
+use parallel_disk_usage::data_tree::DataTree;
+use parallel_disk_usage::json_data::{JsonData, JsonTree, SchemaVersion};
+use parallel_disk_usage::os_string_display::OsStringDisplay;
+use parallel_disk_usage::size::Bytes;
+use parallel_disk_usage::table::{Column, Delimiter, Table};
+use pipe_trait::Pipe;
+use pretty_assertions::assert_eq;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use text_block_macros::{text_block, text_block_fnl};
+
+type SampleTree = DataTree<OsStringDisplay, Bytes>;
+
+/// Tree whose names have to be quoted in CSV.
+fn quoted_names_tree() -> SampleTree {
+    let dir = |name, children| sample_dir(name, 1000, children);
+    dir(
+        "root",
+        vec![
+            sample_file("foo", 6000),
+            dir(
+                "bar",
+                vec![
+                    sample_file("baz, \"qux\"", 1500),
+                    sample_file("tab\tname", 500),
+                ],
+            ),
+        ],
+    )
+}
+
+/// Write a tree into a JSON snapshot file.
+fn write_snapshot(path: &Path, tree: SampleTree) {
+    let json_data = JsonData {
+        schema_version: SchemaVersion,
+        binary_version: None,
+        header: None,
+        body: JsonTree {
+            tree: tree.into_reflection(),
+            shared: Default::default(),
+        }
+        .into(),
+    };
+    let json = serde_json::to_string(&json_data).expect("convert sample tree to JSON");
+    fs::write(path, json).expect("write JSON snapshot");
+}
+
+#[test]
+fn csv_all_columns() {
+    let tree = quoted_names_tree();
+    let actual = Table {
+        data_tree: &tree,
+        columns: Column::ALL,
+        delimiter: Delimiter::Comma,
+        bytes_format: None,
+        synthetic_root: false,
+    }
+    .to_string();
+    let expected = text_block_fnl! {
+        "path,depth,size,percent-of-parent,percent-of-root"
+        "root,0,10000,100.00,100.00"
+        "root/foo,1,6000,60.00,60.00"
+        "root/bar,1,3000,30.00,30.00"
+        r#""root/bar/baz, ""qux""",2,1500,50.00,15.00"#
+        "root/bar/tab\tname,2,500,16.67,5.00"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn tsv_selected_columns_human_readable() {
+    let tree = quoted_names_tree();
+    let actual = Table {
+        data_tree: &tree,
+        columns: &[Column::Size, Column::Path],
+        delimiter: Delimiter::Tab,
+        bytes_format: Some(BytesFormat::MetricUnits),
+        synthetic_root: false,
+    }
+    .to_string();
+    let expected = text_block_fnl! {
+        "size\tpath"
+        "10.0K\troot"
+        "6.0K\troot/foo"
+        "3.0K\troot/bar"
+        "1.5K\troot/bar/baz, \"qux\""
+        "500\troot/bar/tab\\tname"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn tsv_names_are_written_verbatim() {
+    use std::ffi::OsString;
+    #[cfg(unix)]
+    let invalid_name = {
+        use std::os::unix::ffi::OsStringExt;
+        OsString::from_vec(b"bad\xFFname".to_vec())
+    };
+    #[cfg(not(unix))]
+    let invalid_name = OsString::from("bad\u{FFFD}name");
+    let tree = SampleTree::dir(
+        OsStringDisplay::os_string_from("root"),
+        0.into(),
+        vec![
+            SampleTree::file(OsStringDisplay::os_string_from(" padded "), 750.into()),
+            SampleTree::file(OsStringDisplay::from(invalid_name), 250.into()),
+        ],
+    );
+    let mut actual = Vec::new();
+    Table {
+        data_tree: &tree,
+        columns: &[Column::Path, Column::Size],
+        delimiter: Delimiter::Tab,
+        bytes_format: Some(BytesFormat::MetricUnits),
+        synthetic_root: false,
+    }
+    .write_to(&mut actual)
+    .expect("write table");
+    let mut expected = b"path\tsize\nroot\t1.0K\nroot/ padded \t750\nroot/bad".to_vec();
+    #[cfg(unix)]
+    expected.push(0xFF);
+    #[cfg(not(unix))]
+    expected.extend("\u{FFFD}".as_bytes());
+    expected.extend(b"name\t250\n");
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn csv_synthetic_root() {
+    let tree = SampleTree::dir(
+        OsStringDisplay::os_string_from("(total)"),
+        0.into(),
+        vec![
+            SampleTree::file(OsStringDisplay::os_string_from("a"), 750.into()),
+            SampleTree::file(OsStringDisplay::os_string_from("b"), 250.into()),
+        ],
+    );
+    let actual = Table {
+        data_tree: &tree,
+        columns: &[Column::Path, Column::PercentOfRoot],
+        delimiter: Delimiter::Comma,
+        bytes_format: None,
+        synthetic_root: true,
+    }
+    .to_string();
+    let expected = text_block_fnl! {
+        "path,percent-of-root"
+        "a,100.00"
+        "b,100.00"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn csv_output_from_scan() {
+    let workspace = SampleWorkspace::default();
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--output-format=csv")
+        .with_arg("--csv-columns=path,depth,size")
+        .with_arg("--quantity=apparent-size")
+        .with_arg("--min-ratio=0")
+        .with_arg("nested")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text);
+    eprintln!("ACTUAL:\n{actual}\n");
+
+    let rows: Vec<Vec<&str>> = actual
+        .lines()
+        .map(|line| line.split(',').collect())
+        .collect();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[0], ["path", "depth", "size"]);
+    assert_eq!(rows[1][..2], ["nested", "0"]);
+    assert_eq!(rows[2][..2], ["nested/0", "1"]);
+    assert_eq!(rows[3], ["nested/0/1", "2", "500000"]);
+}
+
+#[test]
+fn tsv_output_from_json_input() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    write_snapshot(&workspace.join("snapshot.json"), quoted_names_tree());
+    let actual = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--json-input")
+        .with_arg("--output-format=tsv")
+        .with_arg("--csv-columns=path,percent-of-root")
+        .with_arg("--min-ratio=0.1")
+        .with_arg("snapshot.json")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command")
+        .pipe(stdout_text);
+    let expected = text_block! {
+        "path\tpercent-of-root"
+        "root\t100.00"
+        "root/foo\t60.00"
+        "root/bar\t30.00"
+        "root/bar/baz, \"qux\"\t15.00"
+    };
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn csv_output_of_diff_is_unsupported() {
+    let workspace = Temp::new_dir().expect("create temporary directory");
+    write_snapshot(&workspace.join("old.json"), quoted_names_tree());
+    write_snapshot(&workspace.join("new.json"), quoted_names_tree());
+    let output = Command::new(PDU)
+        .with_current_dir(&workspace)
+        .with_arg("--diff")
+        .with_arg("old.json")
+        .with_arg("new.json")
+        .with_arg("--output-format=csv")
+        .with_stdin(Stdio::null())
+        .with_stdout(Stdio::piped())
+        .with_stderr(Stdio::piped())
+        .output()
+        .expect("spawn command");
+    let stderr = String::from_utf8(output.stderr).expect("parse stderr as UTF-8");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr.contains("--output-format=csv cannot be used with --diff"),
+        "unexpected stderr {stderr:?}",
+    );
+}
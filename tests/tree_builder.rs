@@ -66,51 +66,35 @@ impl SampleTree {
 #[test]
 fn flat() {
     let actual = SampleTree::create_sample().tree("flat").into_reflection();
-    let expected = DataTreeReflection {
-        name: "flat".to_string(),
-        size: len("") + len("a") + len("ab") + len("abc") + SAMPLE_DIR_SIZE,
-        children: vec![
-            DataTreeReflection {
-                name: "0".to_string(),
-                size: len(""),
-                children: Vec::new(),
-            },
-            DataTreeReflection {
-                name: "1".to_string(),
-                size: len("a"),
-                children: Vec::new(),
-            },
-            DataTreeReflection {
-                name: "2".to_string(),
-                size: len("ab"),
-                children: Vec::new(),
-            },
-            DataTreeReflection {
-                name: "3".to_string(),
-                size: len("abc"),
-                children: Vec::new(),
-            },
+    let expected = DataTreeReflection::new(
+        "flat".to_string(),
+        len("") + len("a") + len("ab") + len("abc") + SAMPLE_DIR_SIZE,
+        vec![
+            DataTreeReflection::new("0".to_string(), len(""), Vec::new()),
+            DataTreeReflection::new("1".to_string(), len("a"), Vec::new()),
+            DataTreeReflection::new("2".to_string(), len("ab"), Vec::new()),
+            DataTreeReflection::new("3".to_string(), len("abc"), Vec::new()),
         ],
-    };
+    );
     assert_eq!(actual, expected);
 }
 
 #[test]
 fn nested() {
     let actual = SampleTree::create_sample().tree("nested").into_reflection();
-    let expected = DataTreeReflection {
-        name: "nested".to_string(),
-        size: len("abcdef") + SAMPLE_DIR_SIZE + SAMPLE_DIR_SIZE,
-        children: vec![DataTreeReflection {
-            name: "0".to_string(),
-            size: len("abcdef") + SAMPLE_DIR_SIZE,
-            children: vec![DataTreeReflection {
-                name: "1".to_string(),
-                size: len("abcdef"),
-                children: Vec::new(),
-            }],
-        }],
-    };
+    let expected = DataTreeReflection::new(
+        "nested".to_string(),
+        len("abcdef") + SAMPLE_DIR_SIZE + SAMPLE_DIR_SIZE,
+        vec![DataTreeReflection::new(
+            "0".to_string(),
+            len("abcdef") + SAMPLE_DIR_SIZE,
+            vec![DataTreeReflection::new(
+                "1".to_string(),
+                len("abcdef"),
+                Vec::new(),
+            )],
+        )],
+    );
     assert_eq!(actual, expected);
 }
 
@@ -119,10 +103,6 @@ fn empty_dir() {
     let actual = SampleTree::create_sample()
         .tree("empty-dir")
         .into_reflection();
-    let expected = DataTreeReflection {
-        name: "empty-dir".to_string(),
-        size: SAMPLE_DIR_SIZE,
-        children: Vec::new(),
-    };
+    let expected = DataTreeReflection::new("empty-dir".to_string(), SAMPLE_DIR_SIZE, Vec::new());
     assert_eq!(actual, expected);
 }